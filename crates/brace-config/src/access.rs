@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::value::{leaves, Table};
+
+#[derive(Debug, Default)]
+pub(crate) struct Access(Mutex<HashSet<String>>);
+
+impl Access {
+    pub(crate) fn record(&self, path: String) {
+        self.with(|paths| {
+            paths.insert(path);
+        });
+    }
+
+    pub(crate) fn reset(&self) {
+        self.with(HashSet::clear);
+    }
+
+    pub(crate) fn accessed(&self) -> Vec<String> {
+        let mut paths = self.with(|paths| paths.iter().cloned().collect::<Vec<_>>());
+
+        paths.sort();
+        paths
+    }
+
+    pub(crate) fn unused(&self, table: &Table) -> Vec<String> {
+        let accessed = self.accessed();
+        let mut paths = Vec::new();
+
+        for (key, value) in table {
+            leaves(value, key, &mut paths);
+        }
+
+        paths
+            .into_iter()
+            .filter(|path| {
+                !accessed
+                    .iter()
+                    .any(|read| path == read || path.starts_with(&format!("{}.", read)))
+            })
+            .collect()
+    }
+
+    fn with<F, T>(&self, func: F) -> T
+    where
+        F: FnOnce(&mut HashSet<String>) -> T,
+    {
+        match self.0.lock() {
+            Ok(mut paths) => func(&mut paths),
+            Err(poisoned) => func(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+impl Clone for Access {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.with(|paths| paths.clone())))
+    }
+}
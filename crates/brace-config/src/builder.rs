@@ -0,0 +1,218 @@
+use std::fmt;
+
+use crate::interpolate::InterpolationMode;
+use crate::layer::{Layer, Layers};
+use crate::source::{Metadata, Source};
+use crate::value::{conflicts, ArrayMerge, Error, MergeOptions, Table, TableMerge};
+use crate::Config;
+
+type Fragment = (Option<i32>, usize, Metadata, Table);
+
+#[derive(Default)]
+pub struct ConfigBuilder {
+    sources: Vec<(Option<i32>, Box<dyn Source>)>,
+    options: MergeOptions,
+    layers: bool,
+    strict: bool,
+    commands: bool,
+    mode: InterpolationMode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub key: String,
+    pub first: Metadata,
+    pub second: Metadata,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "conflicting values for key '{}' in {} and {}",
+            self.key,
+            describe(&self.first),
+            describe(&self.second)
+        )
+    }
+}
+
+impl ConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_source<S>(self, source: S) -> Self
+    where
+        S: Source + 'static,
+    {
+        self.add_boxed_source(Box::new(source))
+    }
+
+    pub fn add_boxed_source(mut self, source: Box<dyn Source>) -> Self {
+        self.sources.push((None, source));
+        self
+    }
+
+    pub fn add_source_with_priority<S>(self, source: S, priority: i32) -> Self
+    where
+        S: Source + 'static,
+    {
+        self.add_boxed_source_with_priority(Box::new(source), priority)
+    }
+
+    pub fn add_boxed_source_with_priority(
+        mut self,
+        source: Box<dyn Source>,
+        priority: i32,
+    ) -> Self {
+        self.sources.push((Some(priority), source));
+        self
+    }
+
+    pub fn merge_options(mut self, options: MergeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn array_merge(mut self, arrays: ArrayMerge) -> Self {
+        self.options = self.options.arrays(arrays);
+        self
+    }
+
+    pub fn deletions(mut self, deletions: bool) -> Self {
+        self.options = self.options.deletions(deletions);
+        self
+    }
+
+    pub fn merge_strategy<K>(mut self, path: K, strategy: ArrayMerge) -> Self
+    where
+        K: Into<String>,
+    {
+        self.options = self.options.strategy(path, strategy);
+        self
+    }
+
+    pub fn table_strategy<K>(mut self, path: K, strategy: TableMerge) -> Self
+    where
+        K: Into<String>,
+    {
+        self.options = self.options.table_strategy(path, strategy);
+        self
+    }
+
+    pub fn keep_layers(mut self, layers: bool) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn allow_commands(mut self, commands: bool) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    pub fn interpolation_mode(mut self, mode: InterpolationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn sources(&self) -> Vec<Metadata> {
+        self.sources
+            .iter()
+            .map(|(_, source)| source.metadata())
+            .collect()
+    }
+
+    pub fn build(&self) -> Result<Config, Error> {
+        let tables = self.collect()?;
+        let conflicts = find_conflicts(&tables);
+
+        if self.strict && !conflicts.is_empty() {
+            let messages = conflicts
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            return Err(Error::custom(messages.join("; ")));
+        }
+
+        let mut config = Config::new();
+        let mut layers = Layers::new(self.options.clone());
+
+        for (_, _, metadata, table) in tables {
+            if self.layers {
+                layers.push(Layer::new(metadata, table.clone()));
+            }
+
+            config.merge_with(Config::from(table), &self.options);
+        }
+
+        if self.layers {
+            config.set_layers(layers);
+        }
+
+        config.set_conflicts(conflicts);
+        config.set_commands(self.commands);
+        config.set_interpolation_mode(self.mode)?;
+
+        Ok(config)
+    }
+
+    fn collect(&self) -> Result<Vec<Fragment>, Error> {
+        let mut tables = Vec::new();
+
+        for (index, (priority, source)) in self.sources.iter().enumerate() {
+            let metadata = source.metadata();
+            let fragments = source.fragments().map_err(|err| match &metadata.origin {
+                Some(origin) => {
+                    Error::custom(format!("{} source '{}': {}", metadata.name, origin, err))
+                }
+                None => Error::custom(format!("{} source: {}", metadata.name, err)),
+            })?;
+
+            for (metadata, table) in fragments {
+                tables.push((*priority, index, metadata, table));
+            }
+        }
+
+        tables.sort_by_key(|(priority, _, _, _)| priority.unwrap_or_default());
+
+        Ok(tables)
+    }
+}
+
+fn find_conflicts(tables: &[Fragment]) -> Vec<Conflict> {
+    let mut found = Vec::new();
+
+    for (index, (priority, source, first, table)) in tables.iter().enumerate() {
+        for (other_priority, other_source, second, other) in &tables[index + 1..] {
+            let explicit = priority.is_some() && other_priority == priority;
+
+            if !explicit && other_source != source {
+                continue;
+            }
+
+            for key in conflicts(table, other) {
+                found.push(Conflict {
+                    key,
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+    }
+
+    found
+}
+
+fn describe(metadata: &Metadata) -> String {
+    match &metadata.origin {
+        Some(origin) => format!("{} source '{}'", metadata.name, origin),
+        None => format!("{} source", metadata.name),
+    }
+}
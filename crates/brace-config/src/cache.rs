@@ -0,0 +1,58 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+type Entries = HashMap<String, HashMap<TypeId, Box<dyn Any + Send + Sync>>>;
+
+#[derive(Default)]
+pub(crate) struct Cache(Mutex<Entries>);
+
+impl Cache {
+    pub(crate) fn get<V>(&self, path: &str) -> Option<V>
+    where
+        V: Clone + 'static,
+    {
+        self.with(|entries| {
+            entries
+                .get(path)?
+                .get(&TypeId::of::<V>())?
+                .downcast_ref::<V>()
+                .cloned()
+        })
+    }
+
+    pub(crate) fn insert<V>(&self, path: String, value: V)
+    where
+        V: Send + Sync + 'static,
+    {
+        self.with(|entries| {
+            entries
+                .entry(path)
+                .or_default()
+                .insert(TypeId::of::<V>(), Box::new(value));
+        });
+    }
+
+    pub(crate) fn clear(&mut self) {
+        match self.0.get_mut() {
+            Ok(entries) => entries.clear(),
+            Err(poisoned) => poisoned.into_inner().clear(),
+        }
+    }
+
+    fn with<F, T>(&self, func: F) -> T
+    where
+        F: FnOnce(&mut Entries) -> T,
+    {
+        match self.0.lock() {
+            Ok(mut entries) => func(&mut entries),
+            Err(poisoned) => func(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+impl Clone for Cache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
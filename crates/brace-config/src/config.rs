@@ -1,25 +1,294 @@
-use std::path::Path;
-
-use serde::{Deserialize, Serialize};
-
-use crate::file::{load, save};
-use crate::value::{Error, Key, Table};
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+use std::borrow::Cow;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::access::Access;
+use crate::cache::Cache;
+#[cfg(feature = "encryption")]
+use crate::file::encryption::encrypt_value;
+#[cfg(feature = "http")]
+use crate::file::http::{load_url, load_url_async};
+use crate::file::{
+    discover, discover_from, expand_tilde, load, load_any, load_any_with, load_standard, load_with,
+    load_with_profile, save, save_with, to_string, Format, LoadOptions, SaveOptions,
+};
+#[cfg(feature = "encryption")]
+use crate::file::{load_encrypted, save_encrypted};
+use crate::interpolate::{InterpolationMode, Interpolator, Resolver};
+use crate::layer::{Layer, Layers};
+use crate::lazy::Sections;
+#[cfg(feature = "json-schema")]
+use crate::schema::JsonSchema;
+use crate::schema::{self, Schema, ValidationReport};
+use crate::secret::{SecretAccess, SecretResolver};
+use crate::sensitive::Sensitive;
+use crate::value::de::ValueDeserializer;
+#[cfg(feature = "zeroize")]
+use crate::value::leaves;
+use crate::value::{Diff, Error, Key, MergeOptions, Table, Value};
+use crate::{ConfigBuilder, Conflict};
+
+#[derive(Deserialize, Clone, Default)]
 #[serde(transparent)]
-pub struct Config(Table);
+pub struct Config {
+    table: Table,
+    #[serde(skip)]
+    extensions: Extensions,
+}
+
+#[derive(Clone, Default)]
+struct Extensions {
+    origin: Option<PathBuf>,
+    layers: Option<Layers>,
+    schema: Option<(Schema, bool)>,
+    access: Access,
+    sensitive: Sensitive,
+    interpolator: Interpolator,
+    sections: Sections,
+    cache: Cache,
+    conflicts: Vec<Conflict>,
+}
 
 impl Config {
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+
     pub fn get<'de, K, V>(&'de self, key: K) -> Result<V, Error>
     where
         K: Into<Key>,
         V: 'de + Deserialize<'de>,
     {
-        self.0.get(key)
+        self.read(key.into(), None)
+    }
+
+    pub fn get_with_context<'de, K, V>(&'de self, key: K, context: &str) -> Result<V, Error>
+    where
+        K: Into<Key>,
+        V: 'de + Deserialize<'de>,
+    {
+        self.read(key.into(), Some(context))
+    }
+
+    pub fn get_cached<K, V>(&self, key: K) -> Result<V, Error>
+    where
+        K: Into<Key>,
+        V: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let key = key.into();
+        let path = key.path();
+
+        if let Some(value) = self.extensions.cache.get::<V>(&path) {
+            self.extensions
+                .sensitive
+                .audit(self.find(&key).ok(), &path, None);
+            self.extensions.access.record(path);
+
+            return Ok(value);
+        }
+
+        let value = self.read::<V>(key, None)?;
+
+        self.extensions.cache.insert(path, value.clone());
+
+        Ok(value)
+    }
+
+    fn read<'de, V>(&'de self, key: Key, context: Option<&str>) -> Result<V, Error>
+    where
+        V: 'de + Deserialize<'de>,
+    {
+        let path = key.path();
+        let value = self.find(&key);
+
+        self.extensions
+            .sensitive
+            .audit(value.as_ref().ok().copied(), &path, context);
+        self.extensions.access.record(path.clone());
+
+        let value = value?;
+
+        if self.extensions.interpolator.is_empty()
+            || self.extensions.interpolator.mode() == InterpolationMode::Eager
+        {
+            return Ok(V::deserialize(ValueDeserializer::new(value)).map_err(|err| err.at(path))?);
+        }
+
+        Ok(V::deserialize(ValueDeserializer::with_interpolator(
+            value,
+            &self.extensions.interpolator,
+            &self.table,
+        ))
+        .map_err(|err| err.at(path))?)
+    }
+
+    fn find(&self, key: &Key) -> Result<&Value, Error> {
+        match key
+            .segment(0)
+            .and_then(|head| self.extensions.sections.get(head))
+        {
+            Some(value) => match key.segment(1) {
+                Some(_) => value?.find(key, 1),
+                None => value,
+            },
+            None => self.table.find(key, 0),
+        }
+    }
+
+    fn table(&self) -> Cow<'_, Table> {
+        if self.extensions.sections.is_empty() {
+            Cow::Borrowed(&self.table)
+        } else {
+            Cow::Owned(self.extensions.sections.materialized(&self.table))
+        }
+    }
+
+    fn materialize(&mut self) {
+        self.extensions.sections.materialize(&mut self.table);
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn set_value_key(&mut self, key: [u8; 32]) -> &mut Config {
+        self.interpolator_mut().set_value_key(Some(key));
+        self
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn set_encrypted<K>(&mut self, key: K, value: &str) -> Result<&mut Config, Error>
+    where
+        K: Into<Key>,
+    {
+        let encrypted = match self.extensions.interpolator.value_key() {
+            Some(value_key) => encrypt_value(value, &value_key).map_err(Error::custom)?,
+            None => return Err(Error::custom("no value key set")),
+        };
+
+        self.set(key, encrypted)
+    }
+
+    pub fn add_secret_resolver<N, R>(&mut self, name: N, resolver: R) -> &mut Config
+    where
+        N: Into<String>,
+        R: SecretResolver + 'static,
+    {
+        self.interpolator_mut()
+            .add_secret_resolver(name.into(), Arc::new(resolver));
+        self
+    }
+
+    pub fn interpolate_on_get(&mut self, enabled: bool) -> &mut Config {
+        self.interpolator_mut().set_references(enabled);
+        self
+    }
+
+    pub fn interpolate_files(&mut self, enabled: bool) -> &mut Config {
+        self.interpolator_mut().set_files(enabled);
+        self
+    }
+
+    pub fn interpolate_env(&mut self, enabled: bool) -> &mut Config {
+        self.interpolator_mut().set_env(enabled);
+        self
+    }
+
+    pub fn interpolate_paths(&mut self, enabled: bool) -> &mut Config {
+        self.interpolator_mut().set_paths(enabled);
+        self
+    }
+
+    pub fn interpolation_mode(&self) -> InterpolationMode {
+        self.extensions.interpolator.mode()
+    }
+
+    pub fn set_interpolation_mode(
+        &mut self,
+        mode: InterpolationMode,
+    ) -> Result<&mut Config, Error> {
+        if mode == InterpolationMode::Eager {
+            self.interpolate()?;
+        }
+
+        self.interpolator_mut().set_mode(mode);
+
+        Ok(self)
+    }
+
+    pub fn interpolate(&mut self) -> Result<&mut Config, Error> {
+        self.materialize();
+        self.extensions.cache.clear();
+
+        let mut interpolator = self.extensions.interpolator.clone();
+        let mut value = Value::Table(self.table.clone());
+
+        interpolator.set_references(true);
+        interpolator.interpolate(&mut value, &self.table)?;
+
+        if let Value::Table(table) = value {
+            #[cfg(feature = "zeroize")]
+            self.extensions.sensitive.wipe(&mut self.table, "");
+
+            self.table = table;
+        }
+
+        Ok(self)
+    }
+
+    pub fn add_resolver<S, R>(&mut self, scheme: S, resolver: R) -> &mut Config
+    where
+        S: Into<String>,
+        R: Resolver + 'static,
+    {
+        self.interpolator_mut()
+            .add_resolver(scheme.into(), Arc::new(resolver));
+        self
+    }
+
+    pub fn mark_sensitive<P>(&mut self, pattern: P) -> &mut Config
+    where
+        P: AsRef<str>,
+    {
+        self.extensions.sensitive.mark(pattern.as_ref());
+        self
+    }
+
+    pub fn on_secret_access<F>(&mut self, hook: F) -> &mut Config
+    where
+        F: Fn(&SecretAccess) + Send + Sync + 'static,
+    {
+        self.extensions.sensitive.add_hook(Arc::new(hook));
+        self
+    }
+
+    pub fn is_sensitive(&self, key: &str) -> bool {
+        self.extensions.sensitive.contains(key)
+    }
+
+    pub fn redacted(&self) -> Config {
+        let mut config = Config::from(self.extensions.sensitive.redact(&self.table()));
+
+        config.extensions.origin = self.extensions.origin.clone();
+        config.extensions.sensitive = self.extensions.sensitive.clone();
+        config
+    }
+
+    pub fn accessed_keys(&self) -> Vec<String> {
+        self.extensions.access.accessed()
+    }
+
+    pub fn unused_keys(&self) -> Vec<String> {
+        self.extensions.access.unused(&self.table())
+    }
+
+    pub fn reset_access(&self) {
+        self.extensions.access.reset();
     }
 
     pub fn set<K, V>(&mut self, key: K, value: V) -> Result<&mut Config, Error>
@@ -27,11 +296,227 @@ impl Config {
         K: Into<Key>,
         V: Serialize,
     {
-        self.0.set(key, value)?;
+        let key = key.into();
+
+        if let Some((schema, true)) = &self.extensions.schema {
+            let mut table = Table::new();
+
+            table.set(key.clone(), &value)?;
+
+            let errors = schema
+                .validate_key(&table, &key.path())
+                .errors()
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            if !errors.is_empty() {
+                return Err(Error::custom(errors.join("; ")));
+            }
+        }
+
+        if key
+            .segment(0)
+            .is_some_and(|head| self.extensions.sections.contains(head))
+        {
+            self.materialize();
+        }
+
+        self.extensions.cache.clear();
+
+        #[cfg(feature = "zeroize")]
+        self.extensions.sensitive.wipe(&mut self.table, &key.path());
+
+        self.table.set(key, value)?;
 
         Ok(self)
     }
 
+    pub fn lookup<K>(&self, key: K) -> Option<&Value>
+    where
+        K: Into<Key>,
+    {
+        self.find(&key.into()).ok()
+    }
+
+    pub fn validate(&self, schema: &Schema) -> ValidationReport {
+        schema.validate(&self.table())
+    }
+
+    pub fn check_against<T>(&self) -> ValidationReport
+    where
+        T: DeserializeOwned,
+    {
+        schema::check::<T>(&self.table())
+    }
+
+    pub fn attach_schema(&mut self, schema: Schema, validate_on_set: bool) -> &mut Config {
+        self.extensions.schema = Some((schema, validate_on_set));
+        self
+    }
+
+    pub fn detach_schema(&mut self) -> Option<Schema> {
+        self.extensions.schema.take().map(|(schema, _)| schema)
+    }
+
+    pub fn schema(&self) -> Option<&Schema> {
+        self.extensions.schema.as_ref().map(|(schema, _)| schema)
+    }
+
+    #[cfg(feature = "json-schema")]
+    pub fn validate_json_schema(&self, schema: &JsonSchema) -> ValidationReport {
+        schema.validate(&self.table())
+    }
+
+    pub fn origin(&self) -> Option<&Path> {
+        self.extensions.origin.as_deref()
+    }
+
+    pub fn resolve_path<K>(&self, key: K) -> Result<PathBuf, Error>
+    where
+        K: Into<Key>,
+    {
+        let path = PathBuf::from(self.get::<_, String>(key)?);
+
+        match self.origin().and_then(Path::parent) {
+            Some(dir) if path.is_relative() => Ok(dir.join(path)),
+            _ => Ok(path),
+        }
+    }
+
+    pub fn get_path<K>(&self, key: K) -> Result<PathBuf, Error>
+    where
+        K: Into<Key>,
+    {
+        let path = self.get::<_, String>(key)?;
+
+        expand_tilde(&path)
+            .ok_or_else(|| Error::custom(format!("failed to expand home directory in '{}'", path)))
+    }
+
+    pub(crate) fn set_origin<P>(&mut self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        self.extensions.origin = Some(path.as_ref().to_path_buf());
+    }
+
+    pub fn merge(&mut self, other: Config) -> &mut Config {
+        self.merge_with(other, &MergeOptions::default())
+    }
+
+    pub fn merge_with(&mut self, mut other: Config, options: &MergeOptions) -> &mut Config {
+        self.materialize();
+        self.extensions.cache.clear();
+        other.materialize();
+
+        #[cfg(feature = "zeroize")]
+        self.wipe_overwritten(&other.table);
+
+        self.table
+            .merge_with(std::mem::take(&mut other.table), options);
+        self
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn wipe_overwritten(&mut self, other: &Table) {
+        let mut paths = Vec::new();
+
+        for (key, value) in other {
+            leaves(value, key, &mut paths);
+        }
+
+        for path in paths {
+            if other.lookup(path.as_str()).is_some_and(Value::is_entry) {
+                self.extensions.sensitive.wipe(&mut self.table, &path);
+            }
+        }
+    }
+
+    pub fn diff(&self, other: &Config) -> Diff {
+        Diff::new(&self.table(), &other.table())
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        match &self.extensions.layers {
+            Some(layers) => layers.all(),
+            None => &[],
+        }
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Table> {
+        self.extensions
+            .layers
+            .as_ref()
+            .and_then(|layers| layers.get(name))
+            .map(Layer::table)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut Table> {
+        self.extensions
+            .layers
+            .as_mut()
+            .and_then(|layers| layers.get_mut(name))
+            .map(Layer::table_mut)
+    }
+
+    pub fn provenance<K>(&self, key: K) -> Vec<&Layer>
+    where
+        K: Into<Key>,
+    {
+        let key = key.into();
+
+        self.layers()
+            .iter()
+            .filter(|layer| layer.contains(key.clone()))
+            .collect()
+    }
+
+    pub fn remerge(&mut self) -> &mut Config {
+        if let Some(layers) = &self.extensions.layers {
+            self.table = layers.merge();
+            self.extensions.sections = Sections::default();
+            self.extensions.cache.clear();
+        }
+
+        self
+    }
+
+    pub(crate) fn set_layers(&mut self, layers: Layers) {
+        self.extensions.layers = Some(layers);
+    }
+
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.extensions.conflicts
+    }
+
+    pub(crate) fn set_conflicts(&mut self, conflicts: Vec<Conflict>) {
+        self.extensions.conflicts = conflicts;
+    }
+
+    pub(crate) fn set_commands(&mut self, enabled: bool) {
+        self.interpolator_mut().set_commands(enabled);
+    }
+
+    #[cfg(feature = "lazy")]
+    pub(crate) fn set_sections(&mut self, sections: Sections) {
+        self.extensions.sections = sections;
+        self.extensions.cache.clear();
+    }
+
+    fn interpolator_mut(&mut self) -> &mut Interpolator {
+        self.extensions.cache.clear();
+
+        &mut self.extensions.interpolator
+    }
+
+    pub(crate) fn table_mut(&mut self) -> &mut Table {
+        self.materialize();
+        self.extensions.cache.clear();
+
+        &mut self.table
+    }
+
     pub fn load<P>(path: P) -> Result<Self, Error>
     where
         P: AsRef<Path>,
@@ -39,23 +524,191 @@ impl Config {
         load(path.as_ref()).map_err(Error::custom)
     }
 
+    #[cfg(feature = "http")]
+    pub fn load_url(url: &str) -> Result<Self, Error> {
+        load_url(url).map_err(Error::custom)
+    }
+
+    #[cfg(feature = "http")]
+    pub async fn load_url_async(url: &str) -> Result<Self, Error> {
+        load_url_async(url).await.map_err(Error::custom)
+    }
+
+    pub fn load_with<P>(path: P, options: &LoadOptions) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        load_with(path.as_ref(), options).map_err(Error::custom)
+    }
+
+    pub fn load_any<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        load_any(path.as_ref()).map_err(Error::custom)
+    }
+
+    pub fn load_any_with<P>(path: P, formats: &[Format]) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        load_any_with(path.as_ref(), formats).map_err(Error::custom)
+    }
+
+    pub fn load_standard(app: &str) -> Result<Self, Error> {
+        load_standard(app).map_err(Error::custom)
+    }
+
+    pub fn load_with_profile<P>(path: P, profile: &str) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        load_with_profile(path, profile).map_err(Error::custom)
+    }
+
+    pub fn discover(name: &str) -> Result<Self, Error> {
+        discover(name).map_err(Error::custom)
+    }
+
+    pub fn discover_from<P>(start: P, name: &str) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        discover_from(start, name).map_err(Error::custom)
+    }
+
     pub fn save<P>(&self, path: P) -> Result<(), Error>
     where
         P: AsRef<Path>,
     {
-        save(path.as_ref(), &self).map_err(Error::custom)
+        save(path.as_ref(), self).map_err(Error::custom)
+    }
+
+    pub fn save_redacted<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.redacted().save(path)
+    }
+
+    pub fn to_string_redacted(&self, format: Format) -> Result<String, Error> {
+        to_string(&self.redacted(), format).map_err(Error::custom)
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn load_encrypted<P>(path: P, key: &[u8; 32]) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        load_encrypted(path.as_ref(), key).map_err(Error::custom)
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn save_encrypted<P>(&self, path: P, key: &[u8; 32]) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        save_encrypted(path.as_ref(), self, key).map_err(Error::custom)
+    }
+
+    pub fn save_with<P>(&self, path: P, options: &SaveOptions) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        save_with(path.as_ref(), self, options).map_err(Error::custom)
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let table = self.extensions.sensitive.redact(&self.table());
+
+        f.debug_struct("Config")
+            .field("table", &table)
+            .field("origin", &self.extensions.origin)
+            .finish()
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let table = self.extensions.sensitive.redact(&self.table());
+
+        display(f, &table, "")
+    }
+}
+
+fn display(f: &mut fmt::Formatter, table: &Table, prefix: &str) -> fmt::Result {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match value {
+            Value::Table(table) if !table.0.is_empty() => display(f, table, &path)?,
+            _ => writeln!(f, "{} = {}", path, inline(value))?,
+        }
+    }
+
+    Ok(())
+}
+
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Entry(entry) => format!("{:?}", entry.value()),
+        Value::Array(array) => format!(
+            "[{}]",
+            array.into_iter().map(inline).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Table(table) => format!(
+            "{{ {} }}",
+            table
+                .into_iter()
+                .map(|(key, value)| format!("{} = {}", key, inline(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Config {
+    fn drop(&mut self) {
+        self.extensions.sensitive.wipe(&mut self.table, "");
+    }
+}
+
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        self.table() == other.table()
+    }
+}
+
+impl Serialize for Config {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.table().serialize(serializer)
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self(Table::new())
+impl From<Config> for Table {
+    fn from(mut config: Config) -> Self {
+        config.materialize();
+
+        std::mem::take(&mut config.table)
     }
 }
 
 impl From<Table> for Config {
     fn from(table: Table) -> Self {
-        Self(table)
+        Self {
+            table,
+            extensions: Extensions::default(),
+        }
     }
 }
 
@@ -67,6 +720,7 @@ mod tests {
     use serde::{Deserialize, Serialize};
 
     use super::Config;
+    use crate::{Entry, Value};
 
     #[test]
     fn test_boolean() {
@@ -83,11 +737,11 @@ mod tests {
     fn test_integer_signed() {
         let mut cfg = Config::new();
 
-        assert!(cfg.set("i8", 8 as i8).is_ok());
-        assert!(cfg.set("i16", 16 as i16).is_ok());
-        assert!(cfg.set("i32", 32 as i32).is_ok());
-        assert!(cfg.set("i64", 64 as i64).is_ok());
-        assert!(cfg.set("i128", 128 as i128).is_ok());
+        assert!(cfg.set("i8", 8_i8).is_ok());
+        assert!(cfg.set("i16", 16_i16).is_ok());
+        assert!(cfg.set("i32", 32_i32).is_ok());
+        assert!(cfg.set("i64", 64_i64).is_ok());
+        assert!(cfg.set("i128", 128_i128).is_ok());
 
         assert_eq!(cfg.get::<_, i8>("i8"), Ok(8));
         assert_eq!(cfg.get::<_, i16>("i8"), Ok(8));
@@ -101,11 +755,11 @@ mod tests {
     fn test_integer_unsigned() {
         let mut cfg = Config::new();
 
-        assert!(cfg.set("u8", 8 as u8).is_ok());
-        assert!(cfg.set("u16", 16 as u16).is_ok());
-        assert!(cfg.set("u32", 32 as u32).is_ok());
-        assert!(cfg.set("u64", 64 as u64).is_ok());
-        assert!(cfg.set("u128", 128 as u128).is_ok());
+        assert!(cfg.set("u8", 8_u8).is_ok());
+        assert!(cfg.set("u16", 16_u16).is_ok());
+        assert!(cfg.set("u32", 32_u32).is_ok());
+        assert!(cfg.set("u64", 64_u64).is_ok());
+        assert!(cfg.set("u128", 128_u128).is_ok());
 
         assert_eq!(cfg.get::<_, u8>("u8"), Ok(8));
         assert_eq!(cfg.get::<_, u16>("u8"), Ok(8));
@@ -116,107 +770,594 @@ mod tests {
     }
 
     #[test]
-    fn test_float() {
-        let mut cfg = Config::new();
+    fn test_float() {
+        let mut cfg = Config::new();
+
+        assert!(cfg.set::<_, f32>("f32", 32.0).is_ok());
+        assert!(cfg.set::<_, f64>("f64", 64.0).is_ok());
+
+        assert_eq!(cfg.get::<_, f32>("f32"), Ok(32.0_f32));
+        assert_eq!(cfg.get::<_, f64>("f64"), Ok(64.0_f64));
+    }
+
+    #[test]
+    fn test_text() {
+        let mut cfg = Config::new();
+
+        assert!(cfg.set("char", 'c').is_ok());
+        assert!(cfg.set("str", "str").is_ok());
+        assert!(cfg.set("string", String::from("string")).is_ok());
+
+        assert_eq!(cfg.get::<_, char>("char"), Ok('c'));
+        assert_eq!(cfg.get::<_, String>("str"), Ok(String::from("str")));
+        assert_eq!(cfg.get::<_, String>("string"), Ok(String::from("string")));
+    }
+
+    #[test]
+    fn test_text_borrowed() {
+        use std::borrow::Cow;
+
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct A<'a> {
+            name: &'a str,
+            #[serde(borrow)]
+            host: Cow<'a, str>,
+        }
+
+        let cfg = crate::config! {
+            "name" = "app",
+            "struct" = { "name" = "app", "host" = "localhost" },
+        };
+
+        assert_eq!(cfg.get::<_, &str>("name"), Ok("app"));
+        assert_eq!(
+            cfg.get::<_, HashMap<&str, &str>>("struct"),
+            Ok(vec![("name", "app"), ("host", "localhost")]
+                .into_iter()
+                .collect())
+        );
+
+        let a = cfg.get::<_, A>("struct").unwrap();
+
+        assert_eq!(a.name, "app");
+        assert!(matches!(a.host, Cow::Borrowed("localhost")));
+    }
+
+    #[test]
+    fn test_tuple() {
+        let mut cfg = Config::new();
+
+        assert!(cfg.set("tuple", ('a', "bee", 3, false)).is_ok());
+
+        assert_eq!(
+            cfg.get::<_, (String, String, String, String)>("tuple"),
+            Ok((
+                String::from("a"),
+                String::from("bee"),
+                String::from("3"),
+                String::from("false"),
+            ))
+        );
+        assert_eq!(
+            cfg.get::<_, (char, String, usize, bool)>("tuple"),
+            Ok(('a', String::from("bee"), 3, false))
+        );
+    }
+
+    #[test]
+    fn test_seq() {
+        let mut cfg = Config::new();
+
+        assert!(cfg.set("seq", vec!["hello", "world"]).is_ok());
+
+        assert_eq!(
+            cfg.get::<_, Vec<String>>("seq"),
+            Ok(vec![String::from("hello"), String::from("world")])
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let mut cfg = Config::new();
+        let mut map = HashMap::<String, Vec<String>>::new();
+
+        map.insert(
+            String::from("a"),
+            vec![String::from("hello"), String::from("world")],
+        );
+        map.insert(String::from("b"), Vec::new());
+
+        assert!(cfg.set("map", map.clone()).is_ok());
+
+        assert_eq!(cfg.get::<_, HashMap<String, Vec<String>>>("map"), Ok(map));
+    }
+
+    #[test]
+    fn test_struct() {
+        let mut cfg = Config::new();
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+        struct A {
+            one: String,
+            two: usize,
+        }
+
+        let a = A {
+            one: String::from("first"),
+            two: 42,
+        };
+
+        assert!(cfg.set("struct", a.clone()).is_ok());
+
+        assert_eq!(cfg.get::<_, A>("struct"), Ok(a));
+    }
+
+    #[test]
+    fn test_option() {
+        let mut cfg = Config::new();
+
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct A {
+            one: Option<String>,
+            two: Option<usize>,
+            three: Option<Vec<String>>,
+        }
+
+        assert!(cfg.set("struct.one", "first").is_ok());
+        assert!(cfg.set("struct.three", vec!["a", "b"]).is_ok());
+
+        assert_eq!(
+            cfg.get::<_, A>("struct"),
+            Ok(A {
+                one: Some(String::from("first")),
+                two: None,
+                three: Some(vec![String::from("a"), String::from("b")]),
+            })
+        );
+        assert_eq!(
+            cfg.get::<_, Option<String>>("struct.one"),
+            Ok(Some(String::from("first")))
+        );
+
+        assert!(cfg.set("struct.one", None::<String>).is_ok());
+
+        assert_eq!(cfg.lookup("struct.one"), Some(&Value::Entry(Entry::null())));
+        assert_eq!(cfg.get::<_, Option<String>>("struct.one"), Ok(None));
+        assert_eq!(
+            cfg.get::<_, A>("struct"),
+            Ok(A {
+                one: None,
+                two: None,
+                three: Some(vec![String::from("a"), String::from("b")]),
+            })
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_option_null() {
+        use crate::file::{from_str, to_string, Format};
+
+        let cfg = from_str(r#"{"one":null,"two":"2"}"#, Format::Json).unwrap();
+
+        assert_eq!(cfg.get::<_, Option<usize>>("one"), Ok(None));
+        assert_eq!(cfg.get::<_, Option<usize>>("two"), Ok(Some(2)));
+        assert_eq!(cfg.get::<_, ()>("one"), Ok(()));
+        assert_eq!(
+            to_string(&cfg, Format::Json)
+                .unwrap()
+                .replace(char::is_whitespace, ""),
+            r#"{"one":null,"two":"2"}"#
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        use std::fmt;
+
+        use serde::de::{Deserializer, Visitor};
+        use serde::ser::Serializer;
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct Bytes(Vec<u8>);
+
+        impl Serialize for Bytes {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Bytes {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct BytesVisitor;
+
+                impl<'de> Visitor<'de> for BytesVisitor {
+                    type Value = Bytes;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("bytes")
+                    }
+
+                    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+                        Ok(Bytes(value))
+                    }
+                }
+
+                deserializer.deserialize_byte_buf(BytesVisitor)
+            }
+        }
+
+        let mut cfg = Config::new();
+
+        assert!(cfg.set("key", Bytes(vec![0, 159, 146, 150])).is_ok());
+        assert!(cfg.set("text", "AJ+Slg==").is_ok());
+        assert!(cfg.set("invalid", "not base64").is_ok());
+
+        assert_eq!(
+            cfg.lookup("key"),
+            Some(&Value::Entry(Entry::bytes([0, 159, 146, 150])))
+        );
+        assert_eq!(cfg.get::<_, String>("key"), Ok(String::from("AJ+Slg==")));
+        assert_eq!(
+            cfg.get::<_, Bytes>("key"),
+            Ok(Bytes(vec![0, 159, 146, 150]))
+        );
+        assert_eq!(
+            cfg.get::<_, Bytes>("text"),
+            Ok(Bytes(vec![0, 159, 146, 150]))
+        );
+        assert!(cfg.get::<_, Bytes>("invalid").is_err());
+    }
+
+    #[test]
+    fn test_struct_flatten() {
+        use crate::{from_value, to_value};
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        struct Listen {
+            host: String,
+            port: u16,
+            tls: Option<bool>,
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        struct Server {
+            name: String,
+            #[serde(flatten)]
+            listen: Listen,
+            #[serde(flatten)]
+            extra: HashMap<String, Value>,
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        #[serde(tag = "driver", rename_all = "lowercase")]
+        enum Storage {
+            Memory,
+            Disk { path: String, ratio: f64 },
+        }
+
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        struct Backend {
+            name: String,
+            #[serde(flatten)]
+            storage: Storage,
+        }
+
+        let server = Server {
+            name: String::from("app"),
+            listen: Listen {
+                host: String::from("localhost"),
+                port: 8080,
+                tls: None,
+            },
+            extra: vec![(String::from("workers"), Value::from(4))]
+                .into_iter()
+                .collect(),
+        };
+        let backend = Backend {
+            name: String::from("cache"),
+            storage: Storage::Disk {
+                path: String::from("/var/lib/app"),
+                ratio: 0.5,
+            },
+        };
+
+        let value = to_value(&server).unwrap();
+
+        assert_eq!(value.get::<_, u16>("port"), Ok(8080));
+        assert_eq!(value.get::<_, usize>("workers"), Ok(4));
+        assert_eq!(from_value::<Server>(value), Ok(server.clone()));
+
+        let value = to_value(&backend).unwrap();
+
+        assert_eq!(value.get::<_, String>("driver"), Ok(String::from("disk")));
+        assert_eq!(from_value::<Backend>(value), Ok(backend));
+
+        let mut cfg = Config::new();
+
+        assert!(cfg.set("server", server.clone()).is_ok());
+        assert!(cfg.set("server.debug", true).is_ok());
+
+        let mut expected = server;
+
+        expected
+            .extra
+            .insert(String::from("debug"), Value::from(true));
+
+        assert_eq!(cfg.get::<_, Server>("server"), Ok(expected));
+    }
+
+    #[test]
+    fn test_struct_fields() {
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        #[serde(deny_unknown_fields)]
+        struct Listen {
+            #[serde(alias = "hostname")]
+            host: String,
+            port: u16,
+            #[serde(default)]
+            backlog: usize,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct Server {
+            listen: Listen,
+        }
+
+        let cfg = crate::config! {
+            "aliased" = { "hostname" = "localhost", "port" = 8080 },
+            "missing" = { "host" = "localhost" },
+            "unknown" = { "host" = "localhost", "port" = 8080, "extra" = 1 },
+            "nested" = { "listen" = { "host" = "localhost", "port" = "http" } },
+        };
+
+        assert_eq!(
+            cfg.get::<_, Listen>("aliased"),
+            Ok(Listen {
+                host: String::from("localhost"),
+                port: 8080,
+                backlog: 0,
+            })
+        );
+        assert_eq!(
+            cfg.get::<_, Listen>("missing").unwrap_err().to_string(),
+            "missing: Listen: missing field `port` (expected fields: host, hostname, port, backlog)"
+        );
+        assert!(cfg
+            .get::<_, Listen>("unknown")
+            .unwrap_err()
+            .to_string()
+            .starts_with("unknown: Listen: unknown field `extra`"));
+        assert_eq!(
+            cfg.get::<_, Server>("nested").unwrap_err().to_string(),
+            "nested.listen.port: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_ignored_any() {
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct Listen {
+            port: u16,
+        }
 
-        assert!(cfg.set::<_, f32>("f32", 32.0).is_ok());
-        assert!(cfg.set::<_, f64>("f64", 64.0).is_ok());
+        let mut cfg = crate::config! {
+            "listen" = {
+                "port" = 8080,
+                "extra" = "${missing}",
+                "nested" = { "list" = ["${missing}", { "deep" = "${missing}" }] },
+            },
+        };
 
-        assert_eq!(cfg.get::<_, f32>("f32"), Ok(32.0 as f32));
-        assert_eq!(cfg.get::<_, f64>("f64"), Ok(64.0 as f64));
+        cfg.interpolate_on_get(true);
+
+        assert_eq!(cfg.get("listen"), Ok(Listen { port: 8080 }));
+        assert!(cfg.get::<_, serde::de::IgnoredAny>("listen").is_ok());
+        assert!(cfg.get::<_, Value>("listen").is_err());
     }
 
     #[test]
-    fn test_text() {
-        let mut cfg = Config::new();
+    fn test_from_value() {
+        use std::collections::BTreeMap;
 
-        assert!(cfg.set("char", 'c').is_ok());
-        assert!(cfg.set("str", "str").is_ok());
-        assert!(cfg.set("string", String::from("string")).is_ok());
+        use serde::de::IntoDeserializer;
 
-        assert_eq!(cfg.get::<_, char>("char"), Ok('c'));
-        assert_eq!(cfg.get::<_, String>("str"), Ok(String::from("str")));
-        assert_eq!(cfg.get::<_, String>("string"), Ok(String::from("string")));
-    }
+        use crate::{from_value, to_value};
 
-    #[test]
-    fn test_tuple() {
-        let mut cfg = Config::new();
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        enum Mode {
+            Fast,
+            Custom { level: u8 },
+        }
 
-        assert!(cfg.set("tuple", ('a', "bee", 3, false)).is_ok());
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Worker {
+            name: String,
+            port: u16,
+            tags: Vec<String>,
+            limits: BTreeMap<u32, Option<f64>>,
+            modes: Vec<Mode>,
+        }
+
+        let worker = Worker {
+            name: String::from("primary"),
+            port: 8080,
+            tags: vec![String::from("a"), String::from("b")],
+            limits: BTreeMap::from([(1, Some(0.5)), (2, None)]),
+            modes: vec![Mode::Fast, Mode::Custom { level: 3 }],
+        };
 
+        let value = to_value(&worker).unwrap();
+
+        assert_eq!(Worker::deserialize(value.clone()), Ok(worker));
         assert_eq!(
-            cfg.get::<_, (String, String, String, String)>("tuple"),
-            Ok((
-                String::from("a"),
-                String::from("bee"),
-                String::from("3"),
-                String::from("false"),
-            ))
+            String::deserialize(value.get::<_, Value>("name").unwrap().into_deserializer()),
+            Ok(String::from("primary"))
         );
+
+        let value = to_value(vec![
+            HashMap::from([("port", "80")]),
+            HashMap::from([("port", "http")]),
+        ])
+        .unwrap();
+
         assert_eq!(
-            cfg.get::<_, (char, String, usize, bool)>("tuple"),
-            Ok(('a', String::from("bee"), 3, false))
+            from_value::<Vec<HashMap<String, u16>>>(value)
+                .unwrap_err()
+                .to_string(),
+            "1.port: invalid digit found in string"
         );
     }
 
     #[test]
-    fn test_seq() {
-        let mut cfg = Config::new();
+    fn test_error_path() {
+        #[derive(Deserialize, Debug)]
+        struct Worker {
+            port: u16,
+        }
 
-        assert!(cfg.set("seq", vec!["hello", "world"]).is_ok());
+        #[derive(Deserialize, Debug)]
+        struct Server {
+            workers: Vec<Worker>,
+        }
+
+        let cfg = crate::config! {
+            "server" = {
+                "workers" = [{ "port" = 80 }, { "port" = 81 }, { "port" = "http" }],
+            },
+        };
 
         assert_eq!(
-            cfg.get::<_, Vec<String>>("seq"),
-            Ok(vec![String::from("hello"), String::from("world")])
+            cfg.get::<_, Worker>("server.workers.1")
+                .map(|worker| worker.port),
+            Ok(81)
+        );
+        assert_eq!(
+            cfg.get::<_, Server>("server")
+                .map(|server| server.workers.len())
+                .unwrap_err()
+                .to_string(),
+            "server.workers.2.port: invalid digit found in string"
+        );
+        assert_eq!(
+            cfg.get::<_, Worker>("server.workers.2")
+                .unwrap_err()
+                .to_string(),
+            "server.workers.2.port: invalid digit found in string"
+        );
+        assert_eq!(
+            cfg.get::<_, Worker>("server.workers.2").unwrap_err().path(),
+            Some("server.workers.2.port")
         );
+        assert_eq!(
+            cfg.get::<_, u16>("server.missing").unwrap_err().path(),
+            None
+        );
+        assert_eq!(
+            cfg.get::<_, Vec<u16>>("server.workers")
+                .unwrap_err()
+                .to_string(),
+            "server.workers.0: cannot deserialize table variant as u16"
+        );
+
+        let mut map = HashMap::new();
+
+        map.insert("workers", vec![HashMap::from([((), 80)])]);
+
+        let err = crate::value::to_value(map).unwrap_err();
+
+        assert_eq!(err.to_string(), "workers.0: unsupported key type: unit");
     }
 
     #[test]
-    fn test_map() {
+    fn test_map_keys() {
+        use std::collections::BTreeMap;
+        use std::net::IpAddr;
+
         let mut cfg = Config::new();
-        let mut map = HashMap::<String, Vec<String>>::new();
 
-        map.insert(
-            String::from("a"),
-            vec![String::from("hello"), String::from("world")],
+        let ports = HashMap::from([(80_u16, String::from("http")), (443, String::from("https"))]);
+        let hosts = BTreeMap::from([
+            (IpAddr::from([127, 0, 0, 1]), 8080_u16),
+            (IpAddr::from([10, 0, 0, 1]), 9090),
+        ]);
+        let flags = HashMap::from([(true, 'y'), (false, 'n')]);
+        let grades = HashMap::from([('a', 1_i8), ('b', -1)]);
+
+        cfg.set("ports", &ports).unwrap();
+        cfg.set("hosts", &hosts).unwrap();
+        cfg.set("flags", &flags).unwrap();
+        cfg.set("grades", &grades).unwrap();
+
+        assert_eq!(cfg.get("ports.443"), Ok(String::from("https")));
+        assert_eq!(cfg.get("ports"), Ok(ports));
+        assert_eq!(cfg.get("hosts"), Ok(hosts));
+        assert_eq!(cfg.get("flags"), Ok(flags));
+        assert_eq!(cfg.get("grades"), Ok(grades));
+        assert_eq!(
+            cfg.get::<_, HashMap<u8, String>>("ports")
+                .unwrap_err()
+                .to_string(),
+            "ports: invalid key '443': number too large to fit in target type"
         );
-        map.insert(String::from("b"), Vec::new());
-
-        assert!(cfg.set("map", map.clone()).is_ok());
-
-        assert_eq!(cfg.get::<_, HashMap<String, Vec<String>>>("map"), Ok(map));
     }
 
     #[test]
-    fn test_struct() {
-        let mut cfg = Config::new();
+    fn test_newtype() {
+        use std::path::PathBuf;
 
         #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
-        struct A {
-            one: String,
-            two: usize,
-        }
+        struct Port(u16);
 
-        let a = A {
-            one: String::from("first"),
-            two: 42,
-        };
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+        struct Wrapper(PathBuf);
 
-        assert!(cfg.set("struct", a.clone()).is_ok());
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+        struct Ports(Vec<Port>);
 
-        assert_eq!(cfg.get::<_, A>("struct"), Ok(a));
+        let mut cfg = Config::new();
+
+        assert!(cfg.set("port", Port(8080)).is_ok());
+        assert!(cfg.set("path", Wrapper(PathBuf::from("/etc/app"))).is_ok());
+        assert!(cfg.set("ports", Ports(vec![Port(80), Port(443)])).is_ok());
+
+        assert_eq!(cfg.lookup("port"), Some(&Value::from(8080)));
+        assert_eq!(cfg.get::<_, u16>("port"), Ok(8080));
+        assert_eq!(cfg.get::<_, Port>("port"), Ok(Port(8080)));
+        assert_eq!(
+            cfg.get::<_, Wrapper>("path"),
+            Ok(Wrapper(PathBuf::from("/etc/app")))
+        );
+        assert_eq!(
+            cfg.get::<_, Ports>("ports"),
+            Ok(Ports(vec![Port(80), Port(443)]))
+        );
+        assert!(cfg.get::<_, Port>("path").is_err());
     }
 
     #[test]
     fn test_unit() {
         let mut cfg = Config::new();
 
-        #[derive(Serialize, Deserialize)]
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
         struct Unit;
 
-        assert!(cfg.set("unit", ()).is_err());
-        assert!(cfg.set("unit_struct", Unit).is_err());
+        assert!(cfg.set("unit", ()).is_ok());
+        assert!(cfg.set("unit_struct", Unit).is_ok());
+        assert!(cfg.set("empty", "").is_ok());
+        assert!(cfg.set("text", "text").is_ok());
+
+        assert_eq!(cfg.lookup("unit"), Some(&Value::Entry(Entry::null())));
+        assert_eq!(cfg.get::<_, ()>("unit"), Ok(()));
+        assert_eq!(cfg.get::<_, Unit>("unit_struct"), Ok(Unit));
+        assert_eq!(cfg.get::<_, Unit>("empty"), Ok(Unit));
+        assert!(cfg.get::<_, ()>("text").is_err());
     }
 
     #[test]
@@ -326,6 +1467,71 @@ mod tests {
         assert_eq!(cfg.get::<_, String>("e.E.b.a"), Ok(String::from("1")));
     }
 
+    #[test]
+    fn test_enum_tagged() {
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+        #[serde(tag = "type", rename_all = "lowercase")]
+        enum Internal {
+            Tcp { host: String, port: u16 },
+            Unix { path: String },
+        }
+
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+        #[serde(tag = "t", content = "c")]
+        enum Adjacent {
+            A(u16),
+            B { enabled: bool },
+        }
+
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+        #[serde(untagged)]
+        enum Untagged {
+            Port(u16),
+            Ratio(f64),
+            Enabled(bool),
+            Name(String),
+            Listen { host: String, port: u16 },
+        }
+
+        let mut cfg = crate::config! {
+            "internal" = { "type" = "tcp", "host" = "localhost", "port" = 8080 },
+            "untagged" = [8080, 0.5, true, "eth0", { "host" = "localhost", "port" = 8080 }],
+        };
+
+        assert_eq!(
+            cfg.get::<_, Internal>("internal"),
+            Ok(Internal::Tcp {
+                host: String::from("localhost"),
+                port: 8080,
+            })
+        );
+        assert_eq!(
+            cfg.get::<_, Vec<Untagged>>("untagged"),
+            Ok(vec![
+                Untagged::Port(8080),
+                Untagged::Ratio(0.5),
+                Untagged::Enabled(true),
+                Untagged::Name(String::from("eth0")),
+                Untagged::Listen {
+                    host: String::from("localhost"),
+                    port: 8080,
+                },
+            ])
+        );
+
+        let unix = Internal::Unix {
+            path: String::from("/run/app.sock"),
+        };
+        let adjacent = vec![Adjacent::A(1), Adjacent::B { enabled: false }];
+
+        assert!(cfg.set("unix", unix.clone()).is_ok());
+        assert!(cfg.set("adjacent", adjacent.clone()).is_ok());
+
+        assert_eq!(cfg.get::<_, String>("unix.type"), Ok(String::from("unix")));
+        assert_eq!(cfg.get::<_, Internal>("unix"), Ok(unix));
+        assert_eq!(cfg.get::<_, Vec<Adjacent>>("adjacent"), Ok(adjacent));
+    }
+
     #[test]
     fn test_ipv4() {
         let mut cfg = Config::new();
@@ -384,4 +1590,346 @@ mod tests {
         assert_eq!(cfg.get::<_, String>("0.0.0.a.0"), Ok(String::from("A")));
         assert_eq!(cfg.get::<_, String>("0.zero.0.a.0"), Ok(String::from("A")));
     }
+
+    #[test]
+    fn test_config_unused_keys() {
+        let cfg = crate::config! {
+            "name" = "app",
+            "server" = {
+                "host" = "localhost",
+                "port" = 8080,
+                "tls" = { "cert" = "a.pem", "key" = "a.key" },
+            },
+            "hosts" = ["a", "b"],
+            "empty" = {},
+        };
+
+        assert_eq!(cfg.get::<_, String>("name"), Ok(String::from("app")));
+        assert_eq!(cfg.get::<_, u16>("server.port"), Ok(8080));
+        assert!(cfg.get::<_, HashMap<String, String>>("server.tls").is_ok());
+        assert!(cfg.get::<_, String>("server.typo").is_err());
+
+        assert_eq!(
+            cfg.accessed_keys(),
+            vec!["name", "server.port", "server.tls", "server.typo"]
+        );
+        let mut unused = cfg.unused_keys();
+
+        unused.sort();
+
+        assert_eq!(unused, vec!["empty", "hosts", "server.host"]);
+
+        cfg.reset_access();
+
+        assert!(cfg.accessed_keys().is_empty());
+        assert_eq!(cfg.unused_keys().len(), 7);
+    }
+
+    #[test]
+    fn test_config_sensitive() {
+        let mut cfg = crate::config! {
+            "database" = {
+                "host" = "localhost",
+                "password" = "hunter2",
+            },
+            "tokens" = ["abc", "def"],
+        };
+
+        cfg.mark_sensitive("database.password")
+            .mark_sensitive("tokens.*");
+
+        let display = cfg.to_string();
+        let debug = format!("{:?}", cfg);
+        let pretty = format!("{:#?}", cfg);
+
+        assert!(display.contains("database.host = \"localhost\""));
+        assert!(display.contains("database.password = \"***\""));
+        assert!(display.contains("tokens = [\"***\", \"***\"]"));
+
+        for output in &[display, debug, pretty] {
+            assert!(!output.contains("hunter2"));
+            assert!(!output.contains("abc"));
+        }
+
+        assert!(cfg.is_sensitive("database.password"));
+        assert!(!cfg.is_sensitive("database.host"));
+        assert_eq!(
+            cfg.get::<_, String>("database.password"),
+            Ok(String::from("hunter2"))
+        );
+    }
+
+    #[test]
+    fn test_config_secret_resolver() {
+        #[derive(Deserialize)]
+        struct Database {
+            user: String,
+            password: String,
+            port: u16,
+        }
+
+        let mut cfg = crate::config! {
+            "database" = {
+                "user" = "app",
+                "password" = "${secret:vault/db#password}",
+                "port" = "${secret:vault/db#port}",
+            },
+            "missing" = "${secret:aws/db}",
+        };
+
+        assert_eq!(
+            cfg.get::<_, String>("database.password"),
+            Ok(String::from("${secret:vault/db#password}"))
+        );
+
+        cfg.add_secret_resolver("vault", |reference: &str| match reference {
+            "db#password" => Ok(String::from("hunter2")),
+            "db#port" => Ok(String::from("5432")),
+            _ => Err(crate::value::Error::custom("unknown secret")),
+        });
+
+        let database = cfg.get::<_, Database>("database").unwrap();
+
+        assert_eq!(database.user, "app");
+        assert_eq!(database.password, "hunter2");
+        assert_eq!(database.port, 5432);
+        assert_eq!(cfg.get::<_, u16>("database.port"), Ok(5432));
+        assert!(cfg.get::<_, String>("missing").is_err());
+        assert!(!format!("{:?}", cfg).contains("hunter2"));
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_config_sensitive_overwrite() {
+        let mut cfg = crate::config! {
+            "database" = { "password" = "hunter2" },
+            "tokens" = ["a"],
+        };
+
+        cfg.mark_sensitive("database.password")
+            .mark_sensitive("tokens");
+        cfg.set("database.password", "secret").unwrap();
+
+        assert_eq!(
+            cfg.get::<_, String>("database.password"),
+            Ok(String::from("secret"))
+        );
+
+        cfg.merge_with(
+            Config::from(crate::table! {
+                "database" = { "password" = "merged" },
+                "tokens" = ["b"],
+            }),
+            &crate::value::MergeOptions::new().arrays(crate::value::ArrayMerge::Append),
+        );
+
+        assert_eq!(
+            cfg.get::<_, String>("database.password"),
+            Ok(String::from("merged"))
+        );
+        assert_eq!(
+            cfg.get::<_, Vec<String>>("tokens"),
+            Ok(vec![String::from("a"), String::from("b")])
+        );
+    }
+
+    #[test]
+    fn test_config_secret_access() {
+        use std::sync::{Arc, Mutex};
+
+        let mut cfg = crate::config! {
+            "database" = { "user" = "app", "password" = "hunter2" },
+            "tokens" = ["a", "b"],
+        };
+        let accesses = Arc::new(Mutex::new(Vec::new()));
+        let recorded = accesses.clone();
+
+        cfg.mark_sensitive("database.password")
+            .mark_sensitive("tokens")
+            .on_secret_access(move |access| recorded.lock().unwrap().push(access.clone()));
+
+        assert!(cfg.get::<_, String>("database.user").is_ok());
+        assert!(accesses.lock().unwrap().is_empty());
+
+        assert!(cfg.get::<_, String>("database.password").is_ok());
+        assert!(cfg
+            .get_with_context::<_, HashMap<String, String>>("database", "billing")
+            .is_ok());
+        assert!(cfg.get::<_, String>("tokens.1").is_ok());
+
+        let accesses = accesses.lock().unwrap();
+        let keys = accesses
+            .iter()
+            .map(|access| (access.key.as_str(), access.context.as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keys,
+            vec![
+                ("database.password", None),
+                ("database.password", Some("billing")),
+                ("tokens.1", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_interpolate() {
+        let mut cfg = crate::config! {
+            "database" = { "host" = "localhost", "port" = 5432 },
+            "url" = "${database.host}:${database.port}",
+            "hosts" = ["${database.host}", "${url}"],
+            "missing" = "${database.user}",
+        };
+
+        assert_eq!(
+            cfg.get::<_, String>("url"),
+            Ok(String::from("${database.host}:${database.port}"))
+        );
+
+        cfg.interpolate_on_get(true);
+
+        assert_eq!(
+            cfg.get::<_, String>("url"),
+            Ok(String::from("localhost:5432"))
+        );
+        assert_eq!(
+            cfg.get::<_, Vec<String>>("hosts"),
+            Ok(vec![
+                String::from("localhost"),
+                String::from("localhost:5432")
+            ])
+        );
+        assert_eq!(
+            cfg.get::<_, String>("missing").unwrap_err().to_string(),
+            "missing: unknown reference 'database.user'"
+        );
+        assert!(cfg.interpolate().is_err());
+
+        cfg.set("missing", "${database.port}").unwrap();
+        cfg.interpolate_on_get(false);
+
+        assert!(cfg.interpolate().is_ok());
+        assert_eq!(cfg.lookup("url"), Some(&crate::value!("localhost:5432")));
+        assert_eq!(cfg.get::<_, u16>("missing"), Ok(5432));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_config_get_path() {
+        use std::path::PathBuf;
+
+        let home = PathBuf::from(std::env::var("HOME").unwrap());
+        let mut cfg = crate::config! {
+            "cache" = "~/.cache/myapp",
+            "data" = "${path:~/data}/myapp",
+            "absolute" = "/var/lib/myapp",
+        };
+
+        assert_eq!(cfg.get_path("cache"), Ok(home.join(".cache/myapp")));
+        assert_eq!(
+            cfg.get_path("absolute"),
+            Ok(PathBuf::from("/var/lib/myapp"))
+        );
+        assert_eq!(
+            cfg.get::<_, String>("data"),
+            Ok(String::from("${path:~/data}/myapp"))
+        );
+
+        cfg.interpolate_paths(true);
+
+        assert_eq!(cfg.get_path("data"), Ok(home.join("data/myapp")));
+    }
+
+    #[test]
+    fn test_config_interpolation_mode() {
+        use crate::InterpolationMode;
+
+        let mut lazy = crate::config! {
+            "port" = "${env:BRACE_CONFIG_MODE_PORT:-8080}",
+        };
+
+        std::env::remove_var("BRACE_CONFIG_MODE_PORT");
+
+        lazy.interpolate_env(true);
+
+        let mut eager = lazy.clone();
+
+        assert_eq!(lazy.interpolation_mode(), InterpolationMode::Lazy);
+        assert!(eager
+            .set_interpolation_mode(InterpolationMode::Eager)
+            .is_ok());
+        assert_eq!(eager.interpolation_mode(), InterpolationMode::Eager);
+        assert_eq!(eager.lookup("port"), Some(&crate::value!("8080")));
+
+        std::env::set_var("BRACE_CONFIG_MODE_PORT", "9090");
+
+        assert_eq!(lazy.get::<_, u16>("port"), Ok(9090));
+        assert_eq!(eager.get::<_, u16>("port"), Ok(8080));
+
+        std::env::remove_var("BRACE_CONFIG_MODE_PORT");
+    }
+
+    #[test]
+    fn test_config_get_cached() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static PARSED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone, Debug, PartialEq)]
+        struct Timeout(u64);
+
+        impl<'de> Deserialize<'de> for Timeout {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                PARSED.fetch_add(1, Ordering::SeqCst);
+
+                u64::deserialize(deserializer).map(Timeout)
+            }
+        }
+
+        let mut cfg = crate::config! {
+            "timeout" = "30",
+        };
+
+        assert_eq!(cfg.get_cached("timeout"), Ok(Timeout(30)));
+        assert_eq!(cfg.get_cached("timeout"), Ok(Timeout(30)));
+        assert_eq!(
+            cfg.get_cached::<_, String>("timeout"),
+            Ok(String::from("30"))
+        );
+        assert_eq!(PARSED.load(Ordering::SeqCst), 1);
+        assert_eq!(cfg.accessed_keys(), vec!["timeout"]);
+
+        cfg.set("timeout", 60).unwrap();
+
+        assert_eq!(cfg.get_cached("timeout"), Ok(Timeout(60)));
+        assert_eq!(PARSED.load(Ordering::SeqCst), 2);
+
+        cfg.merge(crate::config! { "timeout" = "90" });
+
+        assert_eq!(cfg.get_cached("timeout"), Ok(Timeout(90)));
+        assert_eq!(cfg.clone().get_cached("timeout"), Ok(Timeout(90)));
+        assert_eq!(PARSED.load(Ordering::SeqCst), 4);
+        assert!(cfg.get_cached::<_, Timeout>("missing").is_err());
+
+        let mut cfg = crate::config! {
+            "port" = "8080",
+            "url" = "http://localhost:${port}",
+        };
+
+        assert_eq!(
+            cfg.get_cached("url"),
+            Ok(String::from("http://localhost:${port}"))
+        );
+
+        cfg.interpolate_on_get(true);
+
+        assert_eq!(
+            cfg.get_cached("url"),
+            Ok(String::from("http://localhost:8080"))
+        );
+    }
 }
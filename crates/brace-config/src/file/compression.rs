@@ -0,0 +1,72 @@
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            #[cfg(feature = "gzip")]
+            "gz" => Some(Compression::Gzip),
+            #[cfg(feature = "zstd")]
+            "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    pub fn from_path<P>(path: P) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        path.as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    pub fn compress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                let mut encoder = zstd::Encoder::new(Vec::new(), 0)?;
+
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    pub fn decompress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        let mut vec = Vec::new();
+
+        self.decoder(bytes)?.read_to_end(&mut vec)?;
+
+        Ok(vec)
+    }
+
+    pub fn decoder<'a, R>(self, reader: R) -> io::Result<Box<dyn Read + 'a>>
+    where
+        R: Read + 'a,
+    {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Box::new(flate2::read::GzDecoder::new(reader))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Box::new(zstd::Decoder::new(reader)?)),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::env;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::Error;
+use crate::value::Value;
+use crate::Config;
+
+pub const WHEN_KEY: &str = "when";
+
+pub(crate) fn process(config: &mut Config) -> Result<(), Error> {
+    let sections = match config.table_mut().remove(WHEN_KEY) {
+        Some(Value::Table(sections)) => sections,
+        Some(_) => return Err(Error::condition("'when' must be a table of conditions")),
+        None => return Ok(()),
+    };
+
+    for (condition, section) in sections {
+        if &*condition == "env" {
+            if let Value::Table(vars) = section {
+                for (name, values) in vars {
+                    if let Value::Table(values) = values {
+                        for (expected, section) in values {
+                            if env::var(&*name).ok().as_deref() == Some(&*expected) {
+                                merge(config, &condition, section)?;
+                            }
+                        }
+                    }
+                }
+
+                continue;
+            }
+        }
+
+        if evaluate(&condition)? {
+            merge(config, &condition, section)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn merge(config: &mut Config, condition: &str, section: Value) -> Result<(), Error> {
+    match section {
+        Value::Table(table) => {
+            config.table_mut().merge(table);
+
+            Ok(())
+        }
+        _ => Err(Error::condition(format!(
+            "section for condition '{}' must be a table",
+            condition
+        ))),
+    }
+}
+
+pub(crate) fn evaluate(condition: &str) -> Result<bool, Error> {
+    let condition = condition.trim();
+
+    if let Some(rest) = condition.strip_prefix("env.") {
+        return Ok(match rest.split_once('=') {
+            Some((name, value)) => {
+                env::var(name.trim()).ok().as_deref() == Some(unquote(value.trim()))
+            }
+            None => env::var_os(rest.trim()).is_some(),
+        });
+    }
+
+    if condition.starts_with("cfg(") {
+        let mut parser = Parser {
+            chars: condition.chars().peekable(),
+        };
+        let result = parser.predicate()?;
+
+        parser.skip_whitespace();
+
+        return match parser.chars.next() {
+            None => Ok(result),
+            Some(_) => Err(invalid(condition)),
+        };
+    }
+
+    Err(invalid(condition))
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn predicate(&mut self) -> Result<bool, Error> {
+        let name = self.ident()?;
+
+        self.skip_whitespace();
+
+        match name.as_str() {
+            "cfg" | "all" | "any" | "not" => {
+                self.expect('(')?;
+
+                let mut results = Vec::new();
+
+                loop {
+                    self.skip_whitespace();
+
+                    if self.chars.peek() == Some(&')') {
+                        break;
+                    }
+
+                    results.push(self.predicate()?);
+                    self.skip_whitespace();
+
+                    if self.chars.peek() == Some(&',') {
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                self.expect(')')?;
+
+                match name.as_str() {
+                    "all" => Ok(results.iter().all(|result| *result)),
+                    "any" => Ok(results.iter().any(|result| *result)),
+                    _ if results.len() != 1 => Err(Error::condition(format!(
+                        "'{}' expects one predicate",
+                        name
+                    ))),
+                    "not" => Ok(!results[0]),
+                    _ => Ok(results[0]),
+                }
+            }
+            _ if self.chars.peek() == Some(&'=') => {
+                self.chars.next();
+                self.skip_whitespace();
+
+                let value = self.string()?;
+
+                Ok(matches(&name, Some(&value)))
+            }
+            _ => Ok(matches(&name, None)),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+
+        let mut ident = String::new();
+
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_alphanumeric() || ch == '_' {
+                ident.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if ident.is_empty() {
+            return Err(Error::condition("expected an identifier in condition"));
+        }
+
+        Ok(ident)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+
+        for ch in &mut self.chars {
+            if ch == '"' {
+                return Ok(string);
+            }
+
+            string.push(ch);
+        }
+
+        Err(Error::condition("unterminated string in condition"))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+
+        match self.chars.next() {
+            Some(ch) if ch == expected => Ok(()),
+            _ => Err(Error::condition(format!(
+                "expected '{}' in condition",
+                expected
+            ))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(ch) = self.chars.peek() {
+            if ch.is_whitespace() {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+fn matches(name: &str, value: Option<&str>) -> bool {
+    match (name, value) {
+        ("unix", None) => cfg!(unix),
+        ("windows", None) => cfg!(windows),
+        ("target_os", Some(value)) => env::consts::OS == value,
+        ("target_family", Some(value)) => env::consts::FAMILY == value,
+        ("target_arch", Some(value)) => env::consts::ARCH == value,
+        ("env", Some(value)) => env::var_os(value).is_some(),
+        _ => false,
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn invalid(condition: &str) -> Error {
+    Error::condition(format!("invalid condition '{}'", condition))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::consts::{ARCH, OS};
+
+    use super::evaluate;
+
+    #[test]
+    fn test_evaluate() {
+        let os = format!("cfg(target_os = \"{}\")", OS);
+        let arch = format!("cfg(all(target_arch = \"{}\", any(unix, windows)))", ARCH);
+
+        assert!(evaluate(&os).unwrap());
+        assert!(evaluate(&arch).unwrap());
+        assert!(evaluate("cfg(not(target_os = \"plan9\"))").unwrap());
+        assert!(!evaluate("cfg(target_os = \"plan9\")").unwrap());
+        assert!(!evaluate("cfg(any())").unwrap());
+        assert!(!evaluate("env.BRACE_CONFIG_UNSET_VARIABLE").unwrap());
+        assert!(evaluate("cfg(not(unix, windows))").is_err());
+        assert!(evaluate("cfg(target_os = \"linux\"").is_err());
+        assert!(evaluate("linux").is_err());
+    }
+}
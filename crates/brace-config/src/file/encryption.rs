@@ -0,0 +1,99 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use super::Error;
+
+pub const VALUE_PREFIX: &str = "enc:v1:";
+
+const HEADER: &[u8] = b"brace-config:aes-256-gcm:";
+const NONCE_LEN: usize = 12;
+
+pub fn encrypt(bytes: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let encrypted = cipher
+        .encrypt(&nonce, bytes)
+        .map_err(|_| Error::encryption("failed to encrypt data"))?;
+
+    let mut vec = Vec::with_capacity(HEADER.len() + NONCE_LEN + encrypted.len());
+
+    vec.extend_from_slice(HEADER);
+    vec.extend_from_slice(&nonce);
+    vec.extend_from_slice(&encrypted);
+
+    Ok(vec)
+}
+
+pub fn decrypt(bytes: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    if bytes.len() < HEADER.len() + NONCE_LEN || !bytes.starts_with(HEADER) {
+        return Err(Error::encryption("invalid encryption envelope"));
+    }
+
+    let (nonce, encrypted) = bytes[HEADER.len()..].split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), encrypted)
+        .map_err(|_| Error::encryption("failed to decrypt data"))
+}
+
+pub fn encrypt_value(value: &str, key: &[u8; 32]) -> Result<String, Error> {
+    let encrypted = encrypt(value.as_bytes(), key)?;
+
+    Ok(format!(
+        "{}{}",
+        VALUE_PREFIX,
+        STANDARD.encode(&encrypted[HEADER.len()..])
+    ))
+}
+
+pub fn decrypt_value(value: &str, key: &[u8; 32]) -> Result<String, Error> {
+    let encoded = value
+        .strip_prefix(VALUE_PREFIX)
+        .ok_or_else(|| Error::encryption("invalid encrypted value prefix"))?;
+    let mut bytes = HEADER.to_vec();
+
+    bytes.extend(
+        STANDARD
+            .decode(encoded)
+            .map_err(|_| Error::encryption("invalid encrypted value encoding"))?,
+    );
+
+    String::from_utf8(decrypt(&bytes, key)?)
+        .map_err(|_| Error::encryption("decrypted value is not valid utf-8"))
+}
+
+pub fn is_encrypted_value(value: &str) -> bool {
+    value.starts_with(VALUE_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, decrypt_value, encrypt, encrypt_value, is_encrypted_value};
+
+    #[test]
+    fn test_encryption() {
+        let key = [7; 32];
+        let encrypted = encrypt(b"hello world", &key).unwrap();
+
+        assert_ne!(&encrypted[..], &b"hello world"[..]);
+        assert_eq!(decrypt(&encrypted, &key).unwrap(), b"hello world");
+        assert!(decrypt(&encrypted, &[8; 32]).is_err());
+        assert!(decrypt(b"hello world", &key).is_err());
+    }
+
+    #[test]
+    fn test_encryption_value() {
+        let key = [7; 32];
+        let encrypted = encrypt_value("hunter2", &key).unwrap();
+
+        assert!(is_encrypted_value(&encrypted));
+        assert!(!encrypted.contains("hunter2"));
+        assert_eq!(decrypt_value(&encrypted, &key).unwrap(), "hunter2");
+        assert!(decrypt_value(&encrypted, &[8; 32]).is_err());
+        assert!(decrypt_value("hunter2", &key).is_err());
+        assert!(decrypt_value("enc:v1:!!", &key).is_err());
+    }
+}
@@ -1,11 +1,69 @@
 use std::fmt;
+use std::io;
 use std::path::{Path, PathBuf};
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: Option<String>,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self {
+            line,
+            column,
+            snippet: None,
+        }
+    }
+
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let offset = offset.min(source.len());
+        let before = &source.as_bytes()[..offset];
+        let line = before.iter().filter(|byte| **byte == b'\n').count() + 1;
+        let start = before
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let column = String::from_utf8_lossy(&before[start..]).chars().count() + 1;
+
+        Self::new(line, column).with_source(source)
+    }
+
+    pub(crate) fn with_source(mut self, source: &str) -> Self {
+        if self.snippet.is_none() && self.line > 0 {
+            self.snippet = source
+                .lines()
+                .nth(self.line - 1)
+                .map(|line| line.trim_end().to_owned());
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
-    ParseError(Box<dyn std::error::Error>),
-    IoError(std::io::Error),
+    ParseError(
+        Box<dyn std::error::Error>,
+        Option<Location>,
+        Option<PathBuf>,
+    ),
+    IoError(io::Error, Option<PathBuf>),
     InvalidFileType(Option<String>, PathBuf),
+    EncryptionError(String),
+    HttpError(String),
+    FileTooLarge(u64, Option<PathBuf>),
+    IncludeError(String, Option<PathBuf>),
+    ConditionError(String, Option<PathBuf>),
 }
 
 impl Error {
@@ -15,13 +73,101 @@ impl Error {
     {
         Self::InvalidFileType(extension, path.as_ref().into())
     }
+
+    pub fn encryption<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::EncryptionError(msg.to_string())
+    }
+
+    pub fn include<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::IncludeError(msg.to_string(), None)
+    }
+
+    pub fn condition<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::ConditionError(msg.to_string(), None)
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::ParseError(_, _, path) => path.as_deref(),
+            Self::IoError(_, path) => path.as_deref(),
+            Self::InvalidFileType(_, path) => Some(path),
+            Self::FileTooLarge(_, path) => path.as_deref(),
+            Self::IncludeError(_, path) => path.as_deref(),
+            Self::ConditionError(_, path) => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::ParseError(_, location, _) => location.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn io_error(&self) -> Option<&io::Error> {
+        match self {
+            Self::IoError(err, _) => Some(err),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Self::IoError(err, _) => err.kind() == io::ErrorKind::NotFound,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn with_path<P>(self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            Self::ParseError(err, location, None) => {
+                Self::ParseError(err, location, Some(path.as_ref().into()))
+            }
+            Self::IoError(err, None) => Self::IoError(err, Some(path.as_ref().into())),
+            Self::FileTooLarge(max, None) => Self::FileTooLarge(max, Some(path.as_ref().into())),
+            Self::IncludeError(msg, None) => Self::IncludeError(msg, Some(path.as_ref().into())),
+            Self::ConditionError(msg, None) => {
+                Self::ConditionError(msg, Some(path.as_ref().into()))
+            }
+            other => other,
+        }
+    }
+
+    #[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+    pub(crate) fn with_source(self, source: &str) -> Self {
+        match self {
+            Self::ParseError(err, Some(location), path) => {
+                Self::ParseError(err, Some(location.with_source(source)), path)
+            }
+            other => other,
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::ParseError(err) => write!(f, "{}", err),
-            Self::IoError(err) => write!(f, "{}", err),
+            Self::ParseError(err, Some(location), Some(path)) => {
+                write!(f, "{}:{}: {}", path.display(), location, err)
+            }
+            Self::ParseError(err, None, Some(path)) => write!(f, "{}: {}", path.display(), err),
+            Self::ParseError(err, Some(location), None) => write!(f, "{}: {}", location, err),
+            Self::ParseError(err, None, None) => write!(f, "{}", err),
+            Self::IoError(err, Some(path)) => write!(f, "{}: {}", path.display(), err),
+            Self::IoError(err, None) => write!(f, "{}", err),
             Self::InvalidFileType(ext, path) => match ext {
                 Some(ext) => write!(
                     f,
@@ -31,42 +177,102 @@ impl fmt::Display for Error {
                 ),
                 None => write!(f, "Invalid file type for path '{:?}'", path.display()),
             },
+            Self::EncryptionError(msg) => write!(f, "{}", msg),
+            Self::HttpError(msg) => write!(f, "{}", msg),
+            Self::FileTooLarge(max, Some(path)) => write!(
+                f,
+                "{}: file exceeds the maximum size of {} bytes",
+                path.display(),
+                max
+            ),
+            Self::FileTooLarge(max, None) => {
+                write!(f, "file exceeds the maximum size of {} bytes", max)
+            }
+            Self::IncludeError(msg, Some(path)) => write!(f, "{}: {}", path.display(), msg),
+            Self::IncludeError(msg, None) => write!(f, "{}", msg),
+            Self::ConditionError(msg, Some(path)) => write!(f, "{}: {}", path.display(), msg),
+            Self::ConditionError(msg, None) => write!(f, "{}", msg),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(err, _, _) => Some(err.as_ref()),
+            Self::IoError(err, _) => Some(err),
+            _ => None,
+        }
+    }
+}
 
-impl From<std::io::Error> for Error {
-    fn from(error: std::io::Error) -> Self {
-        Self::IoError(error)
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error, None)
+    }
+}
+
+impl From<crate::value::Error> for Error {
+    fn from(error: crate::value::Error) -> Self {
+        Self::ParseError(Box::new(error), None, None)
     }
 }
 
 #[cfg(feature = "json")]
 impl From<serde_json::Error> for Error {
     fn from(error: serde_json::Error) -> Self {
-        Self::ParseError(Box::new(error))
+        let location = match error.line() {
+            0 => None,
+            line => Some(Location::new(line, error.column())),
+        };
+
+        Self::ParseError(Box::new(error), location, None)
     }
 }
 
 #[cfg(feature = "toml")]
 impl From<toml::ser::Error> for Error {
     fn from(error: toml::ser::Error) -> Self {
-        Self::ParseError(Box::new(error))
+        Self::ParseError(Box::new(error), None, None)
     }
 }
 
 #[cfg(feature = "toml")]
 impl From<toml::de::Error> for Error {
     fn from(error: toml::de::Error) -> Self {
-        Self::ParseError(Box::new(error))
+        let location = error
+            .line_col()
+            .map(|(line, column)| Location::new(line + 1, column + 1));
+
+        Self::ParseError(Box::new(error), location, None)
+    }
+}
+
+#[cfg(feature = "toml-edit")]
+impl From<toml_edit::TomlError> for Error {
+    fn from(error: toml_edit::TomlError) -> Self {
+        Self::ParseError(Box::new(error), None, None)
+    }
+}
+
+#[cfg(feature = "toml-edit")]
+impl Error {
+    pub(crate) fn toml_edit(error: toml_edit::TomlError, source: &str) -> Self {
+        let location = error
+            .span()
+            .map(|span| Location::from_offset(source, span.start));
+
+        Self::ParseError(Box::new(error), location, None)
     }
 }
 
 #[cfg(feature = "yaml")]
 impl From<serde_yaml::Error> for Error {
     fn from(error: serde_yaml::Error) -> Self {
-        Self::ParseError(Box::new(error))
+        let location = error
+            .location()
+            .map(|location| Location::new(location.line(), location.column()));
+
+        Self::ParseError(Box::new(error), location, None)
     }
 }
@@ -0,0 +1,246 @@
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            #[cfg(feature = "json")]
+            "json" => Some(Format::Json),
+            #[cfg(feature = "toml")]
+            "toml" => Some(Format::Toml),
+            #[cfg(feature = "yaml")]
+            "yaml" => Some(Format::Yaml),
+            #[cfg(feature = "yaml")]
+            "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn from_path<P>(path: P) -> Option<Self>
+    where
+        P: AsRef<Path>,
+    {
+        path.as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            #[cfg(feature = "toml")]
+            Format::Toml,
+            #[cfg(feature = "yaml")]
+            Format::Yaml,
+            #[cfg(feature = "json")]
+            Format::Json,
+        ]
+    }
+
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            #[cfg(feature = "json")]
+            Format::Json => &["json"],
+            #[cfg(feature = "toml")]
+            Format::Toml => &["toml"],
+            #[cfg(feature = "yaml")]
+            Format::Yaml => &["yaml", "yml"],
+        }
+    }
+
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or("").trim();
+
+        match mime.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "json")]
+            "application/json" | "text/json" => Some(Format::Json),
+            #[cfg(feature = "toml")]
+            "application/toml" | "text/toml" | "text/x-toml" => Some(Format::Toml),
+            #[cfg(feature = "yaml")]
+            "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => {
+                Some(Format::Yaml)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn detect(content: &str) -> Option<Self> {
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+
+        if content.starts_with('{') {
+            return Self::json();
+        }
+
+        if content.starts_with("---") {
+            return Self::yaml();
+        }
+
+        for line in content.lines() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if is_toml_header(line) || is_toml_pair(line) {
+                return Self::toml();
+            }
+
+            if is_yaml_pair(line) || line == "-" || line.starts_with("- ") {
+                return Self::yaml();
+            }
+
+            break;
+        }
+
+        None
+    }
+
+    fn json() -> Option<Self> {
+        #[cfg(feature = "json")]
+        {
+            Some(Format::Json)
+        }
+
+        #[cfg(not(feature = "json"))]
+        {
+            None
+        }
+    }
+
+    fn toml() -> Option<Self> {
+        #[cfg(feature = "toml")]
+        {
+            Some(Format::Toml)
+        }
+
+        #[cfg(not(feature = "toml"))]
+        {
+            None
+        }
+    }
+
+    fn yaml() -> Option<Self> {
+        #[cfg(feature = "yaml")]
+        {
+            Some(Format::Yaml)
+        }
+
+        #[cfg(not(feature = "yaml"))]
+        {
+            None
+        }
+    }
+}
+
+fn is_key(key: &str) -> bool {
+    let key = key.trim();
+
+    if key.is_empty() {
+        return false;
+    }
+
+    key.chars().all(|c| {
+        c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '"' || c == '\''
+    })
+}
+
+fn is_toml_header(line: &str) -> bool {
+    let line = line.trim_start_matches('[');
+    let line = match line.find(']') {
+        Some(index) => &line[..index],
+        None => return false,
+    };
+
+    is_key(line)
+}
+
+fn is_toml_pair(line: &str) -> bool {
+    match line.find('=') {
+        Some(index) => is_key(&line[..index]),
+        None => false,
+    }
+}
+
+fn is_yaml_pair(line: &str) -> bool {
+    match line.find(':') {
+        Some(index) => {
+            let rest = &line[index + 1..];
+
+            is_key(&line[..index]) && (rest.is_empty() || rest.starts_with(' '))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(Format::from_path("a.json"), Some(Format::Json));
+        assert_eq!(Format::from_path("a.toml"), Some(Format::Toml));
+        assert_eq!(Format::from_path("a.yaml"), Some(Format::Yaml));
+        assert_eq!(Format::from_path("a.yml"), Some(Format::Yaml));
+        assert_eq!(Format::from_path("a.txt"), None);
+        assert_eq!(Format::from_path("a"), None);
+    }
+
+    #[test]
+    fn test_extensions() {
+        assert_eq!(
+            Format::all(),
+            vec![Format::Toml, Format::Yaml, Format::Json]
+        );
+        assert_eq!(Format::Yaml.extensions(), &["yaml", "yml"]);
+
+        for format in Format::all() {
+            for ext in format.extensions() {
+                assert_eq!(Format::from_extension(ext), Some(format));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_content_type() {
+        assert_eq!(
+            Format::from_content_type("application/json; charset=utf-8"),
+            Some(Format::Json)
+        );
+        assert_eq!(
+            Format::from_content_type("application/toml"),
+            Some(Format::Toml)
+        );
+        assert_eq!(
+            Format::from_content_type("Application/X-YAML"),
+            Some(Format::Yaml)
+        );
+        assert_eq!(Format::from_content_type("text/plain"), None);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Format::detect("{\"a\": 1}"), Some(Format::Json));
+        assert_eq!(Format::detect("  \n{}"), Some(Format::Json));
+        assert_eq!(Format::detect("---\na: 1"), Some(Format::Yaml));
+        assert_eq!(Format::detect("# comment\na: 1"), Some(Format::Yaml));
+        assert_eq!(Format::detect("a:\n  b: 1"), Some(Format::Yaml));
+        assert_eq!(Format::detect("- a\n- b"), Some(Format::Yaml));
+        assert_eq!(Format::detect("[section]\na = 1"), Some(Format::Toml));
+        assert_eq!(Format::detect("[[items]]\na = 1"), Some(Format::Toml));
+        assert_eq!(Format::detect("# comment\na = \"b\""), Some(Format::Toml));
+        assert_eq!(Format::detect("a.b = 1"), Some(Format::Toml));
+        assert_eq!(Format::detect(""), None);
+        assert_eq!(Format::detect("hello world"), None);
+    }
+}
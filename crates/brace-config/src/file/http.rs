@@ -0,0 +1,99 @@
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use super::{from_str, Error, Format};
+use crate::Config;
+
+pub fn load_url(url: &str) -> Result<Config, Error> {
+    let (body, content_type) = fetch(url).map_err(Error::HttpError)?;
+
+    parse(url, &body, content_type.as_deref())
+}
+
+pub async fn load_url_async(url: &str) -> Result<Config, Error> {
+    let (body, content_type) = Fetch::new(url).await.map_err(Error::HttpError)?;
+
+    parse(url, &body, content_type.as_deref())
+}
+
+fn fetch(url: &str) -> Result<(String, Option<String>), String> {
+    let response = ureq::get(url).call().map_err(|err| err.to_string())?;
+    let content_type = response.header("Content-Type").map(ToOwned::to_owned);
+    let body = response.into_string().map_err(|err| err.to_string())?;
+
+    Ok((body, content_type))
+}
+
+pub(crate) fn parse(url: &str, body: &str, content_type: Option<&str>) -> Result<Config, Error> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let format = content_type
+        .and_then(Format::from_content_type)
+        .or_else(|| Format::from_path(Path::new(path)))
+        .or_else(|| Format::detect(body));
+
+    match format {
+        Some(format) => from_str(body, format),
+        None => Err(Error::HttpError(format!(
+            "unable to determine the format of '{}'",
+            url
+        ))),
+    }
+}
+
+type FetchResult = Result<(String, Option<String>), String>;
+
+#[derive(Default)]
+struct FetchState {
+    result: Option<FetchResult>,
+    waker: Option<Waker>,
+}
+
+struct Fetch {
+    url: Option<String>,
+    state: Arc<Mutex<FetchState>>,
+}
+
+impl Fetch {
+    fn new(url: &str) -> Self {
+        Self {
+            url: Some(url.to_owned()),
+            state: Arc::new(Mutex::new(FetchState::default())),
+        }
+    }
+}
+
+impl Future for Fetch {
+    type Output = FetchResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(url) = self.url.take() {
+            let state = self.state.clone();
+
+            thread::spawn(move || {
+                let result = fetch(&url);
+                let mut state = state.lock().unwrap();
+
+                state.result = Some(result);
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+
+                Poll::Pending
+            }
+        }
+    }
+}
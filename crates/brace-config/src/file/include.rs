@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use super::{parse_with, Error, LoadOptions};
+use crate::value::Value;
+use crate::Config;
+
+pub const INCLUDE_KEY: &str = "$include";
+pub const EXTENDS_KEY: &str = "extends";
+
+pub(crate) fn process<P>(config: Config, path: P, options: &LoadOptions) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    let mut stack = Vec::new();
+
+    if let Ok(canonical) = path.as_ref().canonicalize() {
+        stack.push(canonical);
+    }
+
+    resolve(config, path.as_ref(), options, &mut stack)
+}
+
+fn resolve(
+    mut config: Config,
+    path: &Path,
+    options: &LoadOptions,
+    stack: &mut Vec<PathBuf>,
+) -> Result<Config, Error> {
+    let mut includes = Vec::new();
+
+    if options.extends {
+        if let Some(value) = config.table_mut().remove(EXTENDS_KEY) {
+            for parent in paths(value).map_err(|err| err.with_path(path))? {
+                includes.push(("extends", parent));
+            }
+        }
+    }
+
+    if options.includes {
+        if let Some(value) = config.table_mut().remove(INCLUDE_KEY) {
+            for include in paths(value).map_err(|err| err.with_path(path))? {
+                includes.push(("include", include));
+            }
+        }
+    }
+
+    if includes.is_empty() {
+        return Ok(config);
+    }
+
+    if stack.len() > options.include_depth {
+        return Err(Error::include(format!(
+            "maximum include depth of {} exceeded",
+            options.include_depth
+        ))
+        .with_path(path));
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut merged = Config::new();
+
+    for (kind, include) in includes {
+        let include = dir.join(include);
+        let canonical = include
+            .canonicalize()
+            .map_err(|err| Error::from(err).with_path(&include))?;
+
+        if stack.contains(&canonical) {
+            return Err(Error::include(format!(
+                "{} cycle detected for '{}'",
+                kind,
+                include.display()
+            ))
+            .with_path(path));
+        }
+
+        stack.push(canonical);
+
+        let included = parse_with(&include, options)?;
+        let included = resolve(included, &include, options, stack)?;
+
+        stack.pop();
+        merged.merge(included);
+    }
+
+    let origin = config.origin().map(Path::to_path_buf);
+
+    merged.merge(config);
+
+    if let Some(origin) = origin {
+        merged.set_origin(origin);
+    }
+
+    Ok(merged)
+}
+
+fn paths(value: Value) -> Result<Vec<String>, Error> {
+    match value {
+        Value::Entry(entry) => Ok(vec![entry.value().to_owned()]),
+        Value::Array(array) => array
+            .into_iter()
+            .map(|value| match value {
+                Value::Entry(entry) => Ok(entry.value().to_owned()),
+                _ => Err(Error::include("include paths must be strings")),
+            })
+            .collect(),
+        Value::Table(_) => Err(Error::include(
+            "include must be a path or an array of paths",
+        )),
+    }
+}
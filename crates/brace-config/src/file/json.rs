@@ -1,21 +1,28 @@
-use std::fs::{read_to_string, OpenOptions};
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 
+#[cfg(feature = "lazy")]
+use indexmap::IndexMap;
 use serde::ser::Serialize;
-use serde_json::{from_str, to_string_pretty};
+use serde_json::ser::{PrettyFormatter, Serializer};
+#[cfg(feature = "lazy")]
+use serde_json::value::RawValue;
 
-use super::Error;
+use super::{narrow, read, Error, LoadOptions, SaveOptions};
+#[cfg(feature = "lazy")]
+use crate::lazy::Sections;
+#[cfg(feature = "lazy")]
+use crate::value::{self, intern, Table, Value};
 use crate::Config;
 
 pub fn load<P>(path: P) -> Result<Config, Error>
 where
     P: AsRef<Path>,
 {
-    let string = read_to_string(path)?;
-    let config = from_str::<Config>(&string)?;
-
-    Ok(config)
+    read(&path, &LoadOptions::default())
+        .and_then(|string| from_str(&string))
+        .map_err(|err| err.with_path(&path))
 }
 
 pub fn save<T, P>(path: P, value: &T) -> Result<(), Error>
@@ -23,14 +30,82 @@ where
     T: Serialize,
     P: AsRef<Path>,
 {
-    let string = to_string_pretty(&value)?;
-    let mut file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)?;
+    let save = || -> Result<(), Error> {
+        let string = to_string(value)?;
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+
+        file.write_all(string.as_ref())?;
+
+        Ok(())
+    };
+
+    save().map_err(|err| err.with_path(&path))
+}
+
+pub fn from_str(string: &str) -> Result<Config, Error> {
+    serde_json::from_str(string).map_err(|err| Error::from(err).with_source(string))
+}
+
+#[cfg(feature = "lazy")]
+pub fn from_str_lazy(string: &str) -> Result<Config, Error> {
+    let map: IndexMap<String, &RawValue> =
+        serde_json::from_str(string).map_err(|err| Error::from(err).with_source(string))?;
+    let mut table = Table::with_capacity(map.len());
+    let mut sections = Sections::new(map.keys().map(|key| intern(key.as_str())).collect());
+
+    for (key, raw) in map {
+        if raw.get().starts_with('{') {
+            sections.defer(intern(key), raw.get(), section);
+        } else {
+            table
+                .0
+                .insert(intern(key), serde_json::from_str(raw.get())?);
+        }
+    }
+
+    let mut config = Config::from(table);
+
+    config.set_sections(sections);
+
+    Ok(config)
+}
+
+#[cfg(feature = "lazy")]
+fn section(source: &str) -> Result<Value, value::Error> {
+    serde_json::from_str(source).map_err(value::Error::custom)
+}
+
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    to_string_with(value, &SaveOptions::default())
+}
+
+pub fn to_string_with<T>(value: &T, options: &SaveOptions) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    let mut value = crate::to_value(value)?;
+
+    narrow(&mut value, |value| {
+        value.parse::<i64>().is_ok() || value.parse::<u64>().is_ok()
+    });
+
+    if !options.pretty {
+        return Ok(serde_json::to_string(&value)?);
+    }
+
+    let indent = " ".repeat(options.indent.unwrap_or(2));
+    let mut vec = Vec::new();
+    let mut serializer =
+        Serializer::with_formatter(&mut vec, PrettyFormatter::with_indent(indent.as_bytes()));
 
-    file.write_all(string.as_ref())?;
+    value.serialize(&mut serializer)?;
 
-    Ok(())
+    Ok(String::from_utf8(vec).expect("serde_json produced invalid utf-8"))
 }
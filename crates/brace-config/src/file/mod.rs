@@ -1,10 +1,38 @@
+use std::fs::{copy, File, OpenOptions};
+use std::io::{self, Read, Write};
 use std::path::Path;
 
 use self::error::Error;
+#[cfg(any(feature = "json", feature = "toml"))]
+use crate::value::{Kind, Value};
 use crate::Config;
 
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub use self::compression::Compression;
+pub use self::condition::WHEN_KEY;
+pub use self::format::Format;
+pub use self::include::{EXTENDS_KEY, INCLUDE_KEY};
+pub use self::options::{
+    Backup, LoadOptions, SaveOptions, DEFAULT_INCLUDE_DEPTH, DEFAULT_MAX_SIZE,
+};
+pub use self::standard::{expand_tilde, standard_dirs};
+
 pub mod error;
 
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compression;
+mod condition;
+mod format;
+mod include;
+mod options;
+mod standard;
+
+#[cfg(feature = "encryption")]
+pub mod encryption;
+
+#[cfg(feature = "http")]
+pub mod http;
+
 #[cfg(feature = "json")]
 pub mod json;
 
@@ -18,46 +46,533 @@ pub fn load<P>(path: P) -> Result<Config, Error>
 where
     P: AsRef<Path>,
 {
-    match path.as_ref().extension() {
-        Some(ext) => match ext.to_str() {
-            #[cfg(feature = "json")]
-            Some("json") => self::json::load(path),
-            #[cfg(feature = "toml")]
-            Some("toml") => self::toml::load(path),
-            #[cfg(feature = "yaml")]
-            Some("yaml") => self::yaml::load(path),
-            #[cfg(feature = "yaml")]
-            Some("yml") => self::yaml::load(path),
-            Some(ext) => Err(Error::invalid_file_type(
-                Some(ext.to_string()),
-                path.as_ref(),
-            )),
-            None => Err(Error::invalid_file_type(None, path.as_ref())),
-        },
-        None => Err(Error::invalid_file_type(None, path.as_ref())),
+    load_with(path, &LoadOptions::default())
+}
+
+pub fn load_with<P>(path: P, options: &LoadOptions) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    let config = parse_with(&path, options)?;
+
+    includes(config, path, options)
+}
+
+fn parse_with<P>(path: P, options: &LoadOptions) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    if let Some(format) = format(&path) {
+        return read(&path, options)
+            .and_then(|string| parse(&string, format, options))
+            .and_then(|config| locate(config, &path, options))
+            .map_err(|err| err.with_path(&path));
+    }
+
+    if !options.detect && !is_stdio(&path) {
+        return Err(Error::invalid_file_type(extension(&path), path.as_ref()));
+    }
+
+    let string = read(&path, options).map_err(|err| err.with_path(&path))?;
+
+    match Format::detect(&string) {
+        Some(format) => parse(&string, format, options)
+            .and_then(|config| locate(config, &path, options))
+            .map_err(|err| err.with_path(&path)),
+        None => Err(Error::invalid_file_type(extension(&path), path.as_ref())),
+    }
+}
+
+#[cfg_attr(not(feature = "lazy"), allow(unused_variables))]
+fn parse(string: &str, format: Format, options: &LoadOptions) -> Result<Config, Error> {
+    #[cfg(feature = "lazy")]
+    if options.lazy && format == Format::Json {
+        return self::json::from_str_lazy(string);
+    }
+
+    from_str(string, format)
+}
+
+pub fn load_stdin(format: Format) -> Result<Config, Error> {
+    from_reader(io::stdin().lock(), format)
+}
+
+pub fn load_as<P>(path: P, format: Format) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    load_as_with(path, format, &LoadOptions::default())
+}
+
+pub fn load_as_with<P>(path: P, format: Format, options: &LoadOptions) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    let config = read(&path, options)
+        .and_then(|string| parse(&string, format, options))
+        .and_then(|config| locate(config, &path, options))
+        .map_err(|err| err.with_path(&path))?;
+
+    includes(config, path, options)
+}
+
+fn locate<P>(mut config: Config, path: P, options: &LoadOptions) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    if options.conditions {
+        self::condition::process(&mut config)?;
+    }
+
+    #[cfg(feature = "encryption")]
+    if let Some(key) = &options.value_key {
+        config.set_value_key(key.0);
+    }
+
+    if is_stdio(&path) {
+        return Ok(config);
+    }
+
+    config.set_origin(&path);
+
+    for key in &options.resolve_paths {
+        if let Ok(resolved) = config.resolve_path(key.as_str()) {
+            config.set(key.as_str(), resolved)?;
+        }
+    }
+
+    Ok(config)
+}
+
+fn includes<P>(config: Config, path: P, options: &LoadOptions) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    if (options.includes || options.extends) && !is_stdio(&path) {
+        self::include::process(config, path, options)
+    } else {
+        Ok(config)
     }
 }
 
+pub fn load_auto<P>(path: P) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    load_with(path, &LoadOptions::new().detect(true))
+}
+
+pub fn load_any<P>(path: P) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    load_any_with(path, &Format::all())
+}
+
+pub fn load_any_with<P>(path: P, formats: &[Format]) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    for format in formats {
+        for ext in format.extensions() {
+            let mut name = path.as_ref().as_os_str().to_owned();
+
+            name.push(".");
+            name.push(ext);
+
+            let candidate = Path::new(&name);
+
+            if candidate.is_file() {
+                return load_as(candidate, *format);
+            }
+        }
+    }
+
+    Err(Error::from(io::Error::new(
+        io::ErrorKind::NotFound,
+        "no configuration file found for any known extension",
+    ))
+    .with_path(&path))
+}
+
+pub fn load_standard(app: &str) -> Result<Config, Error> {
+    let mut config = Config::new();
+
+    for dir in standard_dirs(app) {
+        match load_any(dir.join("config")) {
+            Ok(found) => {
+                config.merge(found);
+            }
+            Err(err) if err.is_not_found() => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(config)
+}
+
+pub fn load_with_profile<P>(path: P, profile: &str) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    let mut config = load(&path)?;
+
+    for overlay in &[profile, "local"] {
+        let overlay = overlay_path(&path, overlay);
+
+        match load(&overlay) {
+            Ok(found) => {
+                config.merge(found);
+            }
+            Err(err) if err.is_not_found() => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(config)
+}
+
+fn overlay_path<P>(path: P, name: &str) -> std::path::PathBuf
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let mut file = path.file_stem().unwrap_or_default().to_owned();
+
+    file.push(".");
+    file.push(name);
+
+    if let Some(ext) = path.extension() {
+        file.push(".");
+        file.push(ext);
+    }
+
+    path.with_file_name(file)
+}
+
+pub fn discover(name: &str) -> Result<Config, Error> {
+    discover_from(std::env::current_dir()?, name)
+}
+
+pub fn discover_from<P>(start: P, name: &str) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    let found = start
+        .as_ref()
+        .ancestors()
+        .map(|dir| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+
+    let mut config = Config::new();
+
+    for path in found.iter().rev() {
+        config.merge(load_auto(path)?);
+    }
+
+    Ok(config)
+}
+
 pub fn save<P>(path: P, config: &Config) -> Result<(), Error>
 where
     P: AsRef<Path>,
 {
-    match path.as_ref().extension() {
-        Some(ext) => match ext.to_str() {
-            #[cfg(feature = "json")]
-            Some("json") => self::json::save(path, config),
-            #[cfg(feature = "toml")]
-            Some("toml") => self::toml::save(path, config),
-            #[cfg(feature = "yaml")]
-            Some("yaml") => self::yaml::save(path, config),
-            #[cfg(feature = "yaml")]
-            Some("yml") => self::yaml::save(path, config),
-            Some(ext) => Err(Error::invalid_file_type(
-                Some(ext.to_string()),
-                path.as_ref(),
-            )),
-            None => Err(Error::invalid_file_type(None, path.as_ref())),
-        },
-        None => Err(Error::invalid_file_type(None, path.as_ref())),
+    save_with(path, config, &SaveOptions::default())
+}
+
+pub fn save_as<P>(path: P, config: &Config, format: Format) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    save_as_with(path, config, format, &SaveOptions::default())
+}
+
+pub fn save_with<P>(path: P, config: &Config, options: &SaveOptions) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    match format(&path) {
+        Some(format) => save_as_with(path, config, format, options),
+        None => Err(Error::invalid_file_type(extension(&path), path.as_ref())),
+    }
+}
+
+pub fn save_as_with<P>(
+    path: P,
+    config: &Config,
+    format: Format,
+    options: &SaveOptions,
+) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    let save = || {
+        let string = to_string_with(config, format, options)?;
+
+        #[cfg(feature = "toml-edit")]
+        let string = if options.preserve
+            && format == Format::Toml
+            && path.as_ref().is_file()
+            && !is_stdio(&path)
+        {
+            self::toml::preserve(&read(&path, &LoadOptions::default())?, config)?
+        } else {
+            string
+        };
+
+        if let Some(backup) = &options.backup {
+            if path.as_ref().is_file() && !is_stdio(&path) {
+                copy(&path, backup.path(&path))?;
+            }
+        }
+
+        write(&path, string.as_bytes())
+    };
+
+    save().map_err(|err| err.with_path(&path))
+}
+
+#[cfg(feature = "encryption")]
+pub fn load_encrypted<P>(path: P, key: &[u8; 32]) -> Result<Config, Error>
+where
+    P: AsRef<Path>,
+{
+    match format(encrypted_path(&path)) {
+        Some(format) => {
+            let load = || {
+                let bytes = read_bytes(&path, &LoadOptions::default())?;
+                let bytes = self::encryption::decrypt(&bytes, key)?;
+                let string = String::from_utf8(bytes)
+                    .map_err(|_| Error::encryption("decrypted data is not valid utf-8"))?;
+
+                from_str(&string, format)
+            };
+
+            load().map_err(|err| err.with_path(&path))
+        }
+        None => Err(Error::invalid_file_type(extension(&path), path.as_ref())),
+    }
+}
+
+#[cfg(feature = "encryption")]
+pub fn save_encrypted<P>(path: P, config: &Config, key: &[u8; 32]) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    match format(encrypted_path(&path)) {
+        Some(format) => {
+            let save = || {
+                let string = to_string(config, format)?;
+
+                write(&path, &self::encryption::encrypt(string.as_bytes(), key)?)
+            };
+
+            save().map_err(|err| err.with_path(&path))
+        }
+        None => Err(Error::invalid_file_type(extension(&path), path.as_ref())),
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "yaml")),
+    allow(unused_variables)
+)]
+pub fn from_str(string: &str, format: Format) -> Result<Config, Error> {
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => self::json::from_str(string),
+        #[cfg(feature = "toml")]
+        Format::Toml => self::toml::from_str(string),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => self::yaml::from_str(string),
+    }
+}
+
+pub fn from_reader<R>(mut reader: R, format: Format) -> Result<Config, Error>
+where
+    R: Read,
+{
+    let mut string = String::new();
+
+    reader.read_to_string(&mut string)?;
+
+    from_str(&string, format)
+}
+
+pub fn to_string(config: &Config, format: Format) -> Result<String, Error> {
+    to_string_with(config, format, &SaveOptions::default())
+}
+
+#[cfg_attr(
+    not(any(feature = "json", feature = "toml", feature = "yaml")),
+    allow(unused_variables)
+)]
+pub fn to_string_with(
+    config: &Config,
+    format: Format,
+    options: &SaveOptions,
+) -> Result<String, Error> {
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => self::json::to_string_with(config, options),
+        #[cfg(feature = "toml")]
+        Format::Toml => self::toml::to_string_with(config, options),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => self::yaml::to_string_with(config, options),
+    }
+}
+
+pub(crate) fn format<P>(path: P) -> Option<Format>
+where
+    P: AsRef<Path>,
+{
+    #[cfg(any(feature = "gzip", feature = "zstd"))]
+    {
+        if Compression::from_path(&path).is_some() {
+            return Format::from_path(path.as_ref().with_extension(""));
+        }
+    }
+
+    Format::from_path(path)
+}
+
+#[cfg(feature = "encryption")]
+fn encrypted_path<P>(path: P) -> std::path::PathBuf
+where
+    P: AsRef<Path>,
+{
+    match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+        Some("enc") => path.as_ref().with_extension(""),
+        _ => path.as_ref().to_path_buf(),
+    }
+}
+
+fn is_stdio<P>(path: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    path.as_ref() == Path::new("-")
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+pub(crate) fn narrow(value: &mut Value, fits: fn(&str) -> bool) {
+    match value {
+        Value::Entry(entry) => {
+            if entry.1 == Kind::Integer && !fits(&entry.0) {
+                entry.1 = Kind::String;
+            }
+        }
+        Value::Array(array) => {
+            for value in &mut array.0 {
+                narrow(value, fits);
+            }
+        }
+        Value::Table(table) => {
+            for value in table.0.values_mut() {
+                narrow(value, fits);
+            }
+        }
+    }
+}
+
+pub(crate) fn read<P>(path: P, options: &LoadOptions) -> Result<String, Error>
+where
+    P: AsRef<Path>,
+{
+    String::from_utf8(read_bytes(path, options)?)
+        .map_err(|err| Error::from(io::Error::new(io::ErrorKind::InvalidData, err)))
+}
+
+fn read_bytes<P>(path: P, options: &LoadOptions) -> Result<Vec<u8>, Error>
+where
+    P: AsRef<Path>,
+{
+    if is_stdio(&path) {
+        return read_limited(io::stdin().lock(), options);
+    }
+
+    let file = File::open(&path)?;
+
+    if let Some(max_size) = options.max_size {
+        if file.metadata()?.len() > max_size {
+            return Err(Error::FileTooLarge(max_size, None));
+        }
+    }
+
+    #[cfg(any(feature = "gzip", feature = "zstd"))]
+    {
+        if let Some(compression) = Compression::from_path(&path) {
+            return read_limited(compression.decoder(file)?, options);
+        }
     }
+
+    read_limited(file, options)
+}
+
+fn read_limited<R>(reader: R, options: &LoadOptions) -> Result<Vec<u8>, Error>
+where
+    R: Read,
+{
+    let mut vec = Vec::new();
+
+    match options.max_size {
+        Some(max_size) => {
+            reader.take(max_size + 1).read_to_end(&mut vec)?;
+
+            if vec.len() as u64 > max_size {
+                return Err(Error::FileTooLarge(max_size, None));
+            }
+        }
+        None => {
+            let mut reader = reader;
+
+            reader.read_to_end(&mut vec)?;
+        }
+    }
+
+    Ok(vec)
+}
+
+fn write<P>(path: P, bytes: &[u8]) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    if is_stdio(&path) {
+        let mut stdout = io::stdout().lock();
+
+        stdout.write_all(bytes)?;
+        stdout.flush()?;
+
+        return Ok(());
+    }
+
+    #[cfg(any(feature = "gzip", feature = "zstd"))]
+    let compressed = match Compression::from_path(&path) {
+        Some(compression) => Some(compression.compress(bytes)?),
+        None => None,
+    };
+
+    #[cfg(any(feature = "gzip", feature = "zstd"))]
+    let bytes = compressed.as_deref().unwrap_or(bytes);
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+
+    file.write_all(bytes)?;
+
+    Ok(())
+}
+
+fn extension<P>(path: P) -> Option<String>
+where
+    P: AsRef<Path>,
+{
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(ToOwned::to_owned)
 }
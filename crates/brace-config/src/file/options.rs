@@ -0,0 +1,222 @@
+use std::ffi::OsString;
+#[cfg(feature = "encryption")]
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_MAX_SIZE: u64 = 64 * 1024 * 1024;
+pub const DEFAULT_INCLUDE_DEPTH: usize = 16;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadOptions {
+    pub(crate) max_size: Option<u64>,
+    pub(crate) detect: bool,
+    pub(crate) includes: bool,
+    pub(crate) extends: bool,
+    pub(crate) conditions: bool,
+    pub(crate) include_depth: usize,
+    pub(crate) resolve_paths: Vec<String>,
+    #[cfg(feature = "lazy")]
+    pub(crate) lazy: bool,
+    #[cfg(feature = "encryption")]
+    pub(crate) value_key: Option<ValueKey>,
+}
+
+#[cfg(feature = "encryption")]
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct ValueKey(pub(crate) [u8; 32]);
+
+#[cfg(feature = "encryption")]
+impl fmt::Debug for ValueKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ValueKey(..)")
+    }
+}
+
+impl LoadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn unlimited(mut self) -> Self {
+        self.max_size = None;
+        self
+    }
+
+    pub fn detect(mut self, detect: bool) -> Self {
+        self.detect = detect;
+        self
+    }
+
+    pub fn includes(mut self, includes: bool) -> Self {
+        self.includes = includes;
+        self
+    }
+
+    pub fn extends(mut self, extends: bool) -> Self {
+        self.extends = extends;
+        self
+    }
+
+    pub fn conditions(mut self, conditions: bool) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    pub fn include_depth(mut self, include_depth: usize) -> Self {
+        self.include_depth = include_depth;
+        self
+    }
+
+    pub fn resolve_path<K>(mut self, key: K) -> Self
+    where
+        K: Into<String>,
+    {
+        self.resolve_paths.push(key.into());
+        self
+    }
+
+    #[cfg(feature = "lazy")]
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    #[cfg(feature = "encryption")]
+    pub fn value_key(mut self, key: [u8; 32]) -> Self {
+        self.value_key = Some(ValueKey(key));
+        self
+    }
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            max_size: Some(DEFAULT_MAX_SIZE),
+            detect: false,
+            includes: false,
+            extends: false,
+            conditions: false,
+            include_depth: DEFAULT_INCLUDE_DEPTH,
+            resolve_paths: Vec::new(),
+            #[cfg(feature = "lazy")]
+            lazy: false,
+            #[cfg(feature = "encryption")]
+            value_key: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaveOptions {
+    pub(crate) backup: Option<Backup>,
+    pub(crate) pretty: bool,
+    pub(crate) indent: Option<usize>,
+    pub(crate) flow: bool,
+    #[cfg(feature = "toml-edit")]
+    pub(crate) preserve: bool,
+}
+
+impl SaveOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn backup(mut self, backup: Backup) -> Self {
+        self.backup = Some(backup);
+        self
+    }
+
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    pub fn compact(self) -> Self {
+        self.pretty(false)
+    }
+
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    pub fn flow(mut self, flow: bool) -> Self {
+        self.flow = flow;
+        self
+    }
+
+    #[cfg(feature = "toml-edit")]
+    pub fn preserve(mut self, preserve: bool) -> Self {
+        self.preserve = preserve;
+        self
+    }
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            backup: None,
+            pretty: true,
+            indent: None,
+            flow: false,
+            #[cfg(feature = "toml-edit")]
+            preserve: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Backup {
+    Suffix,
+    Timestamp,
+}
+
+impl Backup {
+    pub fn path<P>(&self, path: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        let mut name = OsString::from(path.as_ref().as_os_str());
+
+        if let Backup::Timestamp = self {
+            let secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0);
+
+            name.push(format!(".{}", secs));
+        }
+
+        name.push(".bak");
+
+        PathBuf::from(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Backup;
+
+    #[test]
+    fn test_backup_path() {
+        assert_eq!(
+            Backup::Suffix.path("config.toml"),
+            PathBuf::from("config.toml.bak")
+        );
+
+        let path = Backup::Timestamp.path("config.toml");
+        let name = path.to_str().unwrap();
+
+        assert!(name.starts_with("config.toml."));
+        assert!(name.ends_with(".bak"));
+        assert!(name.len() > "config.toml..bak".len());
+    }
+}
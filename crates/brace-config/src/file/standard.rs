@@ -0,0 +1,176 @@
+use std::env;
+#[cfg(not(windows))]
+use std::fs;
+use std::path::{is_separator, PathBuf};
+
+#[cfg(windows)]
+const HOME_VAR: &str = "USERPROFILE";
+#[cfg(not(windows))]
+const HOME_VAR: &str = "HOME";
+
+pub fn standard_dirs(app: &str) -> Vec<PathBuf> {
+    dirs(app, |name| {
+        env::var(name).ok().filter(|value| !value.is_empty())
+    })
+}
+
+pub fn expand_tilde(path: &str) -> Option<PathBuf> {
+    expand(
+        path,
+        |name| env::var(name).ok().filter(|value| !value.is_empty()),
+        user_home,
+    )
+}
+
+fn expand<F, U>(path: &str, var: F, user: U) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+    U: Fn(&str) -> Option<String>,
+{
+    let rest = match path.strip_prefix('~') {
+        Some(rest) => rest,
+        None => return Some(PathBuf::from(path)),
+    };
+
+    let (name, tail) = match rest.find(is_separator) {
+        Some(index) => (&rest[..index], &rest[index + 1..]),
+        None => (rest, ""),
+    };
+
+    let home = match name {
+        "" => var(HOME_VAR)?,
+        name => user(name)?,
+    };
+
+    match tail {
+        "" => Some(PathBuf::from(home)),
+        tail => Some(PathBuf::from(home).join(tail)),
+    }
+}
+
+#[cfg(windows)]
+fn user_home(_: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(windows))]
+fn user_home(name: &str) -> Option<String> {
+    fs::read_to_string("/etc/passwd")
+        .ok()?
+        .lines()
+        .map(|line| line.split(':').collect::<Vec<_>>())
+        .find(|fields| fields.len() > 5 && fields[0] == name)
+        .map(|fields| fields[5].to_owned())
+}
+
+#[cfg(windows)]
+fn dirs<F>(app: &str, var: F) -> Vec<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut dirs = Vec::new();
+
+    if let Some(dir) = var("PROGRAMDATA") {
+        dirs.push(PathBuf::from(dir).join(app));
+    }
+
+    if let Some(dir) = var("APPDATA") {
+        dirs.push(PathBuf::from(dir).join(app));
+    }
+
+    dirs
+}
+
+#[cfg(not(windows))]
+fn dirs<F>(app: &str, var: F) -> Vec<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let home = var("HOME").map(PathBuf::from);
+    let mut dirs = vec![PathBuf::from("/etc").join(app)];
+
+    let system = var("XDG_CONFIG_DIRS").unwrap_or_else(|| String::from("/etc/xdg"));
+
+    for dir in system.split(':').rev().filter(|dir| !dir.is_empty()) {
+        dirs.push(PathBuf::from(dir).join(app));
+    }
+
+    if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/Library/Application Support").join(app));
+
+        if let Some(home) = &home {
+            dirs.push(home.join("Library/Application Support").join(app));
+        }
+    }
+
+    match var("XDG_CONFIG_HOME") {
+        Some(dir) => dirs.push(PathBuf::from(dir).join(app)),
+        None => {
+            if let Some(home) = &home {
+                dirs.push(home.join(".config").join(app));
+            }
+        }
+    }
+
+    dirs.dedup();
+    dirs
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{dirs, expand};
+
+    #[test]
+    fn test_standard_dirs() {
+        let found = dirs("myapp", |name| match name {
+            "HOME" => Some(String::from("/home/joe")),
+            "XDG_CONFIG_DIRS" => Some(String::from("/etc/xdg/a:/etc/xdg/b")),
+            _ => None,
+        });
+
+        assert_eq!(found.first(), Some(&PathBuf::from("/etc/myapp")));
+        assert_eq!(found[1], PathBuf::from("/etc/xdg/b/myapp"));
+        assert_eq!(found[2], PathBuf::from("/etc/xdg/a/myapp"));
+        assert_eq!(
+            found.last(),
+            Some(&PathBuf::from("/home/joe/.config/myapp"))
+        );
+
+        let found = dirs("myapp", |name| match name {
+            "XDG_CONFIG_HOME" => Some(String::from("/xdg")),
+            _ => None,
+        });
+
+        assert_eq!(found.last(), Some(&PathBuf::from("/xdg/myapp")));
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        let var = |name: &str| match name {
+            "HOME" => Some(String::from("/home/joe")),
+            _ => None,
+        };
+        let user = |name: &str| match name {
+            "ann" => Some(String::from("/home/ann")),
+            _ => None,
+        };
+
+        assert_eq!(expand("~", var, user), Some(PathBuf::from("/home/joe")));
+        assert_eq!(
+            expand("~/.cache/myapp", var, user),
+            Some(PathBuf::from("/home/joe/.cache/myapp"))
+        );
+        assert_eq!(
+            expand("~ann/data", var, user),
+            Some(PathBuf::from("/home/ann/data"))
+        );
+        assert_eq!(
+            expand("/var/lib/~joe", var, user),
+            Some(PathBuf::from("/var/lib/~joe"))
+        );
+        assert_eq!(expand("~bob/data", var, user), None);
+        assert_eq!(expand("~/data", |_| None, user), None);
+    }
+}
@@ -1,21 +1,22 @@
-use std::fs::{read_to_string, OpenOptions};
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 
 use serde::ser::Serialize;
-use toml::{from_str, to_string_pretty, Value};
+use toml::{Serializer, Value};
+#[cfg(feature = "toml-edit")]
+use toml_edit::{DocumentMut, InlineTable, Item, Table};
 
-use super::Error;
+use super::{narrow, read, Error, LoadOptions, SaveOptions};
 use crate::Config;
 
 pub fn load<P>(path: P) -> Result<Config, Error>
 where
     P: AsRef<Path>,
 {
-    let string = read_to_string(path)?;
-    let config = from_str::<Config>(&string)?;
-
-    Ok(config)
+    read(&path, &LoadOptions::default())
+        .and_then(|string| from_str(&string))
+        .map_err(|err| err.with_path(&path))
 }
 
 pub fn save<T, P>(path: P, value: &T) -> Result<(), Error>
@@ -23,15 +24,180 @@ where
     T: Serialize,
     P: AsRef<Path>,
 {
-    let value = Value::try_from(value)?;
-    let string = to_string_pretty(&value)?;
-    let mut file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)?;
+    let save = || -> Result<(), Error> {
+        let string = to_string(value)?;
+
+        #[cfg(feature = "toml-edit")]
+        let string = if path.as_ref().is_file() {
+            preserve(&read(&path, &LoadOptions::default())?, value)?
+        } else {
+            string
+        };
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+
+        file.write_all(string.as_ref())?;
+
+        Ok(())
+    };
+
+    save().map_err(|err| err.with_path(&path))
+}
+
+pub fn from_str(string: &str) -> Result<Config, Error> {
+    toml::from_str(string).map_err(|err| Error::from(err).with_source(string))
+}
+
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    to_string_with(value, &SaveOptions::default())
+}
+
+pub fn to_string_with<T>(value: &T, options: &SaveOptions) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    let mut tagged = crate::to_value(value)?;
+
+    narrow(&mut tagged, |value| value.parse::<i64>().is_ok());
+
+    let mut toml = Value::try_from(&tagged)?;
+    let mut string = String::new();
+
+    restore_datetimes(&mut toml, &tagged);
+
+    if options.pretty {
+        let mut serializer = Serializer::pretty(&mut string);
+
+        if let Some(indent) = options.indent {
+            serializer.pretty_array_indent(indent);
+        }
+
+        toml.serialize(&mut serializer)?;
+    } else {
+        toml.serialize(&mut Serializer::new(&mut string))?;
+    }
+
+    Ok(string)
+}
+
+fn restore_datetimes(value: &mut Value, tagged: &crate::Value) {
+    match (value, tagged) {
+        (Value::Table(table), crate::Value::Table(tagged)) => {
+            for (key, tagged) in tagged {
+                if let Some(value) = table.get_mut(key) {
+                    restore_datetimes(value, tagged);
+                }
+            }
+        }
+        (Value::Array(array), crate::Value::Array(tagged)) => {
+            for (value, tagged) in array.iter_mut().zip(tagged) {
+                restore_datetimes(value, tagged);
+            }
+        }
+        (value, crate::Value::Entry(entry)) if entry.is_datetime() => {
+            if let Some(datetime) = value.as_str().and_then(|string| string.parse().ok()) {
+                *value = Value::Datetime(datetime);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(feature = "toml-edit")]
+pub fn preserve<T>(original: &str, value: &T) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    let mut document = original
+        .parse::<DocumentMut>()
+        .map_err(|err| Error::toml_edit(err, original))?;
+    let updated = to_string_with(value, &SaveOptions::new().compact())?.parse::<DocumentMut>()?;
+
+    merge_table(document.as_table_mut(), updated.as_table());
+
+    Ok(document.to_string())
+}
+
+#[cfg(feature = "toml-edit")]
+fn merge_table(target: &mut Table, source: &Table) {
+    target.retain(|key, _| source.contains_key(key));
+
+    for (key, item) in source.iter() {
+        match target.get_mut(key) {
+            Some(existing) => merge_item(existing, item),
+            None => {
+                target.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "toml-edit")]
+fn merge_inline_table(target: &mut InlineTable, source: &InlineTable) {
+    target.retain(|key, _| source.contains_key(key));
+
+    for (key, value) in source.iter() {
+        match target.get_mut(key) {
+            Some(existing) => merge_value(existing, value),
+            None => {
+                target.insert(key, value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "toml-edit")]
+fn merge_item(target: &mut Item, source: &Item) {
+    match (target, source) {
+        (Item::Table(target), Item::Table(source)) => merge_table(target, source),
+        (Item::Table(target), Item::Value(toml_edit::Value::InlineTable(source))) => {
+            merge_table(target, &source.clone().into_table())
+        }
+        (Item::Value(toml_edit::Value::InlineTable(target)), Item::Table(source)) => {
+            merge_inline_table(target, &source.clone().into_inline_table())
+        }
+        (Item::Value(target), Item::Value(source)) => merge_value(target, source),
+        (Item::ArrayOfTables(target), Item::ArrayOfTables(source))
+            if target.len() == source.len() =>
+        {
+            for (target, source) in target.iter_mut().zip(source.iter()) {
+                merge_table(target, source);
+            }
+        }
+        (target, source) => *target = source.clone(),
+    }
+}
+
+#[cfg(feature = "toml-edit")]
+fn merge_value(target: &mut toml_edit::Value, source: &toml_edit::Value) {
+    use toml_edit::Value;
 
-    file.write_all(string.as_ref())?;
+    match (&mut *target, source) {
+        (Value::InlineTable(target), Value::InlineTable(source)) => {
+            merge_inline_table(target, source)
+        }
+        (Value::Array(target), Value::Array(source)) if target.len() == source.len() => {
+            for (target, source) in target.iter_mut().zip(source.iter()) {
+                merge_value(target, source);
+            }
+        }
+        (Value::String(a), Value::String(b)) if a.value() == b.value() => {}
+        (Value::Integer(a), Value::Integer(b)) if a.value() == b.value() => {}
+        (Value::Float(a), Value::Float(b)) if a.value() == b.value() => {}
+        (Value::Boolean(a), Value::Boolean(b)) if a.value() == b.value() => {}
+        (Value::Datetime(a), Value::Datetime(b)) if a.value() == b.value() => {}
+        (target, source) => {
+            let decor = target.decor().clone();
 
-    Ok(())
+            *target = source.clone();
+            *target.decor_mut() = decor;
+        }
+    }
 }
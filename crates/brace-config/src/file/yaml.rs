@@ -1,21 +1,20 @@
-use std::fs::{read_to_string, OpenOptions};
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 
 use serde::ser::Serialize;
-use serde_yaml::{from_str, to_string};
 
-use super::Error;
-use crate::Config;
+use super::{read, Error, LoadOptions, SaveOptions};
+use crate::value::{Kind, Value};
+use crate::{to_value, Config};
 
 pub fn load<P>(path: P) -> Result<Config, Error>
 where
     P: AsRef<Path>,
 {
-    let string = read_to_string(path)?;
-    let config = from_str::<Config>(&string)?;
-
-    Ok(config)
+    read(&path, &LoadOptions::default())
+        .and_then(|string| from_str(&string))
+        .map_err(|err| err.with_path(&path))
 }
 
 pub fn save<T, P>(path: P, value: &T) -> Result<(), Error>
@@ -23,14 +22,102 @@ where
     T: Serialize,
     P: AsRef<Path>,
 {
-    let string = to_string(&value)?;
-    let mut file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)?;
+    let save = || -> Result<(), Error> {
+        let string = to_string(value)?;
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+
+        file.write_all(string.as_ref())?;
+
+        Ok(())
+    };
+
+    save().map_err(|err| err.with_path(&path))
+}
+
+pub fn from_str(string: &str) -> Result<Config, Error> {
+    serde_yaml::from_str(string).map_err(|err| Error::from(err).with_source(string))
+}
+
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    to_string_with(value, &SaveOptions::default())
+}
+
+pub fn to_string_with<T>(value: &T, options: &SaveOptions) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    if !options.flow {
+        return Ok(serde_yaml::to_string(value)?);
+    }
+
+    let value = to_value(value)?;
+    let mut string = String::new();
+
+    write_flow(&mut string, &value);
+    string.push('\n');
+
+    Ok(string)
+}
+
+fn write_flow(string: &mut String, value: &Value) {
+    match value {
+        Value::Entry(entry) => match entry.kind() {
+            Kind::Null => string.push_str("null"),
+            Kind::Boolean | Kind::Integer | Kind::Float => string.push_str(entry.value()),
+            _ => write_quoted(string, entry.value()),
+        },
+        Value::Array(array) => {
+            string.push('[');
+
+            for (index, item) in array.into_iter().enumerate() {
+                if index > 0 {
+                    string.push_str(", ");
+                }
+
+                write_flow(string, item);
+            }
+
+            string.push(']');
+        }
+        Value::Table(table) => {
+            string.push('{');
+
+            for (index, (key, item)) in table.into_iter().enumerate() {
+                if index > 0 {
+                    string.push_str(", ");
+                }
+
+                write_quoted(string, key);
+                string.push_str(": ");
+                write_flow(string, item);
+            }
+
+            string.push('}');
+        }
+    }
+}
+
+fn write_quoted(string: &mut String, value: &str) {
+    string.push('"');
 
-    file.write_all(string.as_ref())?;
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if c.is_control() => string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => string.push(c),
+        }
+    }
 
-    Ok(())
+    string.push('"');
 }
@@ -0,0 +1,637 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io::ErrorKind;
+use std::process::Command;
+use std::sync::Arc;
+use std::{env, fs};
+
+#[cfg(feature = "encryption")]
+use crate::file::encryption::{decrypt_value, is_encrypted_value};
+use crate::file::expand_tilde;
+use crate::secret::SecretResolver;
+use crate::value::{Error, Table, Value};
+
+const SECRET_PREFIX: &str = "secret:";
+const FILE_PREFIX: &str = "file:";
+const ENV_PREFIX: &str = "env:";
+const CMD_PREFIX: &str = "cmd:";
+const PATH_PREFIX: &str = "path:";
+const DEFAULT_SEPARATOR: &str = ":-";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InterpolationMode {
+    #[default]
+    Lazy,
+    Eager,
+}
+
+pub trait Resolver: Send + Sync {
+    fn resolve(&self, argument: &str) -> Result<String, Error>;
+}
+
+impl<F> Resolver for F
+where
+    F: Fn(&str) -> Result<String, Error> + Send + Sync,
+{
+    fn resolve(&self, argument: &str) -> Result<String, Error> {
+        self(argument)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Interpolator {
+    secrets: Vec<(String, Arc<dyn SecretResolver>)>,
+    resolvers: Vec<(String, Arc<dyn Resolver>)>,
+    references: bool,
+    files: bool,
+    env: bool,
+    commands: bool,
+    paths: bool,
+    mode: InterpolationMode,
+    #[cfg(feature = "encryption")]
+    value_key: Option<[u8; 32]>,
+}
+
+impl Interpolator {
+    pub(crate) fn is_empty(&self) -> bool {
+        #[cfg(feature = "encryption")]
+        if self.value_key.is_some() {
+            return false;
+        }
+
+        self.secrets.is_empty()
+            && self.resolvers.is_empty()
+            && !self.references
+            && !self.files
+            && !self.env
+            && !self.commands
+            && !self.paths
+    }
+
+    pub(crate) fn mode(&self) -> InterpolationMode {
+        self.mode
+    }
+
+    pub(crate) fn set_mode(&mut self, mode: InterpolationMode) {
+        self.mode = mode;
+    }
+
+    pub(crate) fn set_references(&mut self, enabled: bool) {
+        self.references = enabled;
+    }
+
+    pub(crate) fn set_files(&mut self, enabled: bool) {
+        self.files = enabled;
+    }
+
+    pub(crate) fn set_env(&mut self, enabled: bool) {
+        self.env = enabled;
+    }
+
+    pub(crate) fn set_commands(&mut self, enabled: bool) {
+        self.commands = enabled;
+    }
+
+    pub(crate) fn set_paths(&mut self, enabled: bool) {
+        self.paths = enabled;
+    }
+
+    #[cfg(feature = "encryption")]
+    pub(crate) fn value_key(&self) -> Option<[u8; 32]> {
+        self.value_key
+    }
+
+    #[cfg(feature = "encryption")]
+    pub(crate) fn set_value_key(&mut self, key: Option<[u8; 32]>) {
+        self.value_key = key;
+    }
+
+    pub(crate) fn add_secret_resolver(&mut self, name: String, resolver: Arc<dyn SecretResolver>) {
+        self.secrets.retain(|(existing, _)| *existing != name);
+        self.secrets.push((name, resolver));
+    }
+
+    pub(crate) fn add_resolver(&mut self, scheme: String, resolver: Arc<dyn Resolver>) {
+        self.resolvers.retain(|(existing, _)| *existing != scheme);
+        self.resolvers.push((scheme, resolver));
+    }
+
+    pub(crate) fn resolve<'a>(&self, text: &'a str, table: &Table) -> Result<Cow<'a, str>, Error> {
+        self.expand(text, table, &mut Vec::new())
+    }
+
+    pub(crate) fn interpolate(&self, value: &mut Value, table: &Table) -> Result<(), Error> {
+        match value {
+            Value::Entry(entry) => {
+                if let Cow::Owned(text) = self.resolve(&entry.0, table)? {
+                    entry.0 = text;
+                }
+            }
+            Value::Array(array) => {
+                for value in &mut array.0 {
+                    self.interpolate(value, table)?;
+                }
+            }
+            Value::Table(inner) => {
+                for value in inner.0.values_mut() {
+                    self.interpolate(value, table)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expand<'a>(
+        &self,
+        text: &'a str,
+        table: &Table,
+        stack: &mut Vec<String>,
+    ) -> Result<Cow<'a, str>, Error> {
+        #[cfg(feature = "encryption")]
+        if let (Some(key), true) = (&self.value_key, is_encrypted_value(text)) {
+            return decrypt_value(text, key)
+                .map(Cow::Owned)
+                .map_err(Error::custom);
+        }
+
+        if self.is_empty() || !text.contains("${") {
+            return Ok(Cow::Borrowed(text));
+        }
+
+        let mut output = String::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find("${") {
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => break,
+            };
+            let (expression, default) = match rest[start + 2..end].split_once(DEFAULT_SEPARATOR) {
+                Some((expression, default)) => (expression, Some(default)),
+                None => (&rest[start + 2..end], None),
+            };
+
+            output.push_str(&rest[..start]);
+
+            let secret = expression.strip_prefix(SECRET_PREFIX);
+            let file = expression.strip_prefix(FILE_PREFIX);
+            let var = expression.strip_prefix(ENV_PREFIX);
+            let command = expression.strip_prefix(CMD_PREFIX);
+            let path = expression.strip_prefix(PATH_PREFIX);
+
+            if let (Some(reference), false) = (secret, self.secrets.is_empty()) {
+                output.push_str(&self.secret(reference, default)?);
+            } else if let (Some(path), true) = (file, self.files) {
+                output.push_str(&read_file(path, default)?);
+            } else if let (Some(name), true) = (var, self.env) {
+                output.push_str(&read_env(name, default)?);
+            } else if let (Some(command), true) = (command, self.commands) {
+                output.push_str(&run_command(command, default)?);
+            } else if let (Some(path), true) = (path, self.paths) {
+                output.push_str(&expand_path(path, default)?);
+            } else if let Some((resolver, argument)) = self.resolver(expression) {
+                output.push_str(&resolver.resolve(argument).or_else(|err| {
+                    fallback(
+                        default,
+                        Error::custom(format!("failed to resolve '{}': {}", expression, err)),
+                    )
+                })?);
+            } else if self.references && !expression.contains(':') {
+                output.push_str(&self.reference(expression, default, table, stack)?);
+            } else {
+                output.push_str(&rest[start..=end]);
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        output.push_str(rest);
+
+        Ok(Cow::Owned(output))
+    }
+
+    fn reference(
+        &self,
+        key: &str,
+        default: Option<&str>,
+        table: &Table,
+        stack: &mut Vec<String>,
+    ) -> Result<String, Error> {
+        let cyclic = stack.iter().any(|existing| existing == key);
+
+        stack.push(key.to_owned());
+
+        if cyclic {
+            return Err(Error::custom(format!(
+                "cyclic reference: {}",
+                stack.join(" -> ")
+            )));
+        }
+
+        let text = match table.lookup(key) {
+            Some(Value::Entry(entry)) => self.expand(entry.value(), table, stack)?.into_owned(),
+            Some(_) => {
+                return Err(Error::custom(format!(
+                    "reference '{}' does not point to a scalar value",
+                    key
+                )))
+            }
+            None => fallback(
+                default,
+                Error::custom(format!("unknown reference '{}'", key)),
+            )?,
+        };
+
+        stack.pop();
+
+        Ok(text)
+    }
+
+    fn resolver<'a>(&self, expression: &'a str) -> Option<(&dyn Resolver, &'a str)> {
+        let (scheme, argument) = expression.split_once(':')?;
+
+        self.resolvers
+            .iter()
+            .find(|(existing, _)| existing == scheme)
+            .map(|(_, resolver)| (resolver.as_ref(), argument))
+    }
+
+    fn secret(&self, reference: &str, default: Option<&str>) -> Result<String, Error> {
+        let (name, path) = match reference.split_once('/') {
+            Some((name, path)) => (name, path),
+            None => (reference, ""),
+        };
+
+        match self.secrets.iter().find(|(existing, _)| existing == name) {
+            Some((_, resolver)) => resolver.resolve(path).or_else(|err| {
+                fallback(
+                    default,
+                    Error::custom(format!("failed to resolve secret '{}': {}", reference, err)),
+                )
+            }),
+            None => Err(Error::custom(format!(
+                "no secret resolver registered for '{}'",
+                name
+            ))),
+        }
+    }
+}
+
+fn read_file(path: &str, default: Option<&str>) -> Result<String, Error> {
+    match (fs::read_to_string(path), default) {
+        (Ok(text), _) => Ok(text.trim().to_owned()),
+        (Err(err), Some(default)) if err.kind() == ErrorKind::NotFound => Ok(default.to_owned()),
+        (Err(err), _) => Err(Error::custom(format!(
+            "failed to read file '{}': {}",
+            path, err
+        ))),
+    }
+}
+
+fn read_env(name: &str, default: Option<&str>) -> Result<String, Error> {
+    match env::var(name) {
+        Ok(value) if !value.is_empty() => Ok(value),
+        _ => fallback(
+            default,
+            Error::custom(format!("environment variable '{}' is not set", name)),
+        ),
+    }
+}
+
+fn run_command(command: &str, default: Option<&str>) -> Result<String, Error> {
+    let mut args = command.split_whitespace();
+    let program = match args.next() {
+        Some(program) => program,
+        None => return fallback(default, Error::custom("empty command")),
+    };
+
+    let err = match Command::new(program).args(args).output() {
+        Ok(output) if output.status.success() => {
+            return Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        }
+        Ok(output) => Error::custom(format!(
+            "command '{}' failed with {}: {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(err) => Error::custom(format!("failed to run command '{}': {}", command, err)),
+    };
+
+    fallback(default, err)
+}
+
+fn expand_path(path: &str, default: Option<&str>) -> Result<String, Error> {
+    match expand_tilde(path) {
+        Some(path) => Ok(path.to_string_lossy().into_owned()),
+        None => fallback(
+            default,
+            Error::custom(format!("failed to expand home directory in '{}'", path)),
+        ),
+    }
+}
+
+fn fallback(default: Option<&str>, err: Error) -> Result<String, Error> {
+    match default {
+        Some(default) => Ok(default.to_owned()),
+        None => Err(err),
+    }
+}
+
+impl fmt::Debug for Interpolator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Interpolator")
+            .field(
+                "secrets",
+                &self
+                    .secrets
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "resolvers",
+                &self
+                    .resolvers
+                    .iter()
+                    .map(|(scheme, _)| scheme)
+                    .collect::<Vec<_>>(),
+            )
+            .field("references", &self.references)
+            .field("files", &self.files)
+            .field("env", &self.env)
+            .field("commands", &self.commands)
+            .field("paths", &self.paths)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Interpolator;
+    use crate::value::{Error, Table};
+
+    #[test]
+    fn test_interpolate_secret() {
+        let table = Table::new();
+        let mut interpolator = Interpolator::default();
+
+        interpolator.add_secret_resolver(
+            String::from("vault"),
+            Arc::new(|reference: &str| match reference {
+                "db#password" => Ok(String::from("hunter2")),
+                _ => Err(Error::custom("not found")),
+            }),
+        );
+
+        assert_eq!(
+            interpolator
+                .resolve("${secret:vault/db#password}", &table)
+                .unwrap(),
+            "hunter2"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("postgres://app:${secret:vault/db#password}@db", &table)
+                .unwrap(),
+            "postgres://app:hunter2@db"
+        );
+        assert_eq!(
+            interpolator.resolve("${other}", &table).unwrap(),
+            "${other}"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${secret:vault/missing}", &table)
+                .unwrap_err()
+                .to_string(),
+            "failed to resolve secret 'vault/missing': not found"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${secret:aws/db}", &table)
+                .unwrap_err()
+                .to_string(),
+            "no secret resolver registered for 'aws'"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_reference() {
+        let table = crate::table! {
+            "database" = { "host" = "localhost", "port" = 5432 },
+            "url" = "${database.host}:${database.port}",
+            "dsn" = "postgres://${url}/app",
+            "a" = "${b}",
+            "b" = "${a}",
+        };
+        let mut interpolator = Interpolator::default();
+
+        assert_eq!(interpolator.resolve("${url}", &table).unwrap(), "${url}");
+
+        interpolator.set_references(true);
+
+        assert_eq!(
+            interpolator.resolve("${url}", &table).unwrap(),
+            "localhost:5432"
+        );
+        assert_eq!(
+            interpolator.resolve("${dsn}", &table).unwrap(),
+            "postgres://localhost:5432/app"
+        );
+        assert_eq!(
+            interpolator.resolve("${secret:vault/db}", &table).unwrap(),
+            "${secret:vault/db}"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${database.user}", &table)
+                .unwrap_err()
+                .to_string(),
+            "unknown reference 'database.user'"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${database}", &table)
+                .unwrap_err()
+                .to_string(),
+            "reference 'database' does not point to a scalar value"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${a}", &table)
+                .unwrap_err()
+                .to_string(),
+            "cyclic reference: a -> b -> a"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_file() {
+        let table = Table::new();
+        let path = std::env::temp_dir().join("brace-config-interpolate-file");
+        let path = path.to_str().unwrap();
+        let mut interpolator = Interpolator::default();
+
+        std::fs::write(path, "hunter2\n").unwrap();
+
+        let reference = format!("${{file:{}}}", path);
+
+        assert_eq!(interpolator.resolve(&reference, &table).unwrap(), reference);
+
+        interpolator.set_files(true);
+
+        assert_eq!(interpolator.resolve(&reference, &table).unwrap(), "hunter2");
+        assert_eq!(
+            interpolator
+                .resolve(&format!("postgres://app:{}@db", reference), &table)
+                .unwrap(),
+            "postgres://app:hunter2@db"
+        );
+        assert!(interpolator
+            .resolve("${file:/nonexistent/brace-config}", &table)
+            .unwrap_err()
+            .to_string()
+            .starts_with("failed to read file '/nonexistent/brace-config'"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_interpolate_default() {
+        let table = crate::table! {
+            "database" = { "host" = "localhost" },
+        };
+        let mut interpolator = Interpolator::default();
+
+        std::env::set_var("BRACE_CONFIG_INTERPOLATE_PORT", "9090");
+        std::env::remove_var("BRACE_CONFIG_INTERPOLATE_MISSING");
+
+        interpolator.set_references(true);
+        interpolator.set_files(true);
+        interpolator.set_env(true);
+
+        assert_eq!(
+            interpolator
+                .resolve("${database.host:-127.0.0.1}", &table)
+                .unwrap(),
+            "localhost"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${database.user:-postgres}", &table)
+                .unwrap(),
+            "postgres"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${env:BRACE_CONFIG_INTERPOLATE_PORT:-8080}", &table)
+                .unwrap(),
+            "9090"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${env:BRACE_CONFIG_INTERPOLATE_MISSING:-8080}", &table)
+                .unwrap(),
+            "8080"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${env:BRACE_CONFIG_INTERPOLATE_MISSING:-}", &table)
+                .unwrap(),
+            ""
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${file:/nonexistent/brace-config:-none}", &table)
+                .unwrap(),
+            "none"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${env:BRACE_CONFIG_INTERPOLATE_MISSING}", &table)
+                .unwrap_err()
+                .to_string(),
+            "environment variable 'BRACE_CONFIG_INTERPOLATE_MISSING' is not set"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_interpolate_command() {
+        let table = Table::new();
+        let mut interpolator = Interpolator::default();
+
+        interpolator.set_references(true);
+
+        assert_eq!(
+            interpolator.resolve("${cmd:echo hello}", &table).unwrap(),
+            "${cmd:echo hello}"
+        );
+
+        interpolator.set_commands(true);
+
+        assert_eq!(
+            interpolator
+                .resolve("v1.0-${cmd:echo  hello   world}", &table)
+                .unwrap(),
+            "v1.0-hello world"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${cmd:false:-unknown}", &table)
+                .unwrap(),
+            "unknown"
+        );
+        assert!(interpolator
+            .resolve("${cmd:false}", &table)
+            .unwrap_err()
+            .to_string()
+            .starts_with("command 'false' failed with"));
+        assert!(interpolator
+            .resolve("${cmd:brace-config-nonexistent}", &table)
+            .unwrap_err()
+            .to_string()
+            .starts_with("failed to run command 'brace-config-nonexistent'"));
+    }
+
+    #[test]
+    fn test_interpolate_resolver() {
+        let table = Table::new();
+        let mut interpolator = Interpolator::default();
+
+        interpolator.add_resolver(
+            String::from("upper"),
+            Arc::new(|argument: &str| match argument {
+                "" => Err(Error::custom("empty")),
+                argument => Ok(argument.to_uppercase()),
+            }),
+        );
+
+        assert_eq!(
+            interpolator
+                .resolve("${upper:hello} world", &table)
+                .unwrap(),
+            "HELLO world"
+        );
+        assert_eq!(
+            interpolator.resolve("${upper::-none}", &table).unwrap(),
+            "none"
+        );
+        assert_eq!(
+            interpolator.resolve("${lower:hello}", &table).unwrap(),
+            "${lower:hello}"
+        );
+        assert_eq!(
+            interpolator
+                .resolve("${upper:}", &table)
+                .unwrap_err()
+                .to_string(),
+            "failed to resolve 'upper:': empty"
+        );
+    }
+}
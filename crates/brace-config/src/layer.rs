@@ -0,0 +1,85 @@
+use crate::source::Metadata;
+use crate::value::{Key, MergeOptions, Table};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    metadata: Metadata,
+    table: Table,
+}
+
+impl Layer {
+    pub fn new(metadata: Metadata, table: Table) -> Self {
+        Self { metadata, table }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.metadata.name
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    pub fn table_mut(&mut self) -> &mut Table {
+        &mut self.table
+    }
+
+    pub fn contains<K>(&self, key: K) -> bool
+    where
+        K: Into<Key>,
+    {
+        self.table.lookup(key).is_some()
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.metadata.name == name || self.metadata.origin.as_deref() == Some(name)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Layers {
+    layers: Vec<Layer>,
+    options: MergeOptions,
+}
+
+impl Layers {
+    pub(crate) fn new(options: MergeOptions) -> Self {
+        Self {
+            layers: Vec::new(),
+            options,
+        }
+    }
+
+    pub(crate) fn push(&mut self, layer: Layer) {
+        self.layers.push(layer);
+    }
+
+    pub(crate) fn all(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().rev().find(|layer| layer.matches(name))
+    }
+
+    pub(crate) fn get_mut(&mut self, name: &str) -> Option<&mut Layer> {
+        self.layers
+            .iter_mut()
+            .rev()
+            .find(|layer| layer.matches(name))
+    }
+
+    pub(crate) fn merge(&self) -> Table {
+        let mut table = Table::new();
+
+        for layer in &self.layers {
+            table.merge_with(layer.table.clone(), &self.options);
+        }
+
+        table
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::OnceLock;
+
+use indexmap::IndexMap;
+
+use crate::value::{Error, Table, TableKey, Value};
+
+pub(crate) type Parse = fn(&str) -> Result<Value, Error>;
+
+#[derive(Clone, Default)]
+pub(crate) struct Sections(Vec<TableKey>, IndexMap<TableKey, Section>);
+
+#[derive(Clone)]
+struct Section(Box<str>, Parse, OnceLock<Result<Value, Error>>);
+
+impl Sections {
+    #[cfg(feature = "lazy")]
+    pub(crate) fn new(keys: Vec<TableKey>) -> Self {
+        Self(keys, IndexMap::new())
+    }
+
+    #[cfg(feature = "lazy")]
+    pub(crate) fn defer(&mut self, key: TableKey, source: &str, parse: Parse) {
+        self.1
+            .insert(key, Section(source.into(), parse, OnceLock::new()));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.1.is_empty()
+    }
+
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        self.1.contains_key(key)
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<Result<&Value, Error>> {
+        self.1.get(key).map(Section::value)
+    }
+
+    pub(crate) fn materialized(&self, table: &Table) -> Table {
+        let mut map = Table::with_capacity(table.0.len() + self.1.len()).0;
+
+        for key in &self.0 {
+            if let Some(section) = self.1.get(key) {
+                if let Ok(value) = section.value() {
+                    map.insert(key.clone(), value.clone());
+                }
+            } else if let Some(value) = table.0.get(key) {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+
+        for (key, value) in &table.0 {
+            if !map.contains_key(key) {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+
+        Table(map)
+    }
+
+    pub(crate) fn materialize(&mut self, table: &mut Table) {
+        if self.1.is_empty() {
+            return;
+        }
+
+        let mut map = Table::with_capacity(table.0.len() + self.1.len()).0;
+
+        let mut failed = IndexMap::new();
+
+        for key in self.0.drain(..) {
+            if let Some(section) = self.1.swap_remove(&key) {
+                match section.into_value() {
+                    Ok(value) => {
+                        map.insert(key, value);
+                    }
+                    Err(section) => {
+                        failed.insert(key, section);
+                    }
+                }
+            } else if let Some(value) = table.0.swap_remove(&key) {
+                map.insert(key, value);
+            }
+        }
+
+        self.0 = failed.keys().cloned().collect();
+        self.1 = failed;
+
+        map.extend(table.0.drain(..));
+        table.0 = map;
+    }
+}
+
+impl Section {
+    fn value(&self) -> Result<&Value, Error> {
+        match self.2.get_or_init(|| (self.1)(&self.0)) {
+            Ok(value) => Ok(value),
+            Err(err) => Err(err.clone()),
+        }
+    }
+
+    fn into_value(self) -> Result<Value, Self> {
+        let Section(source, parse, value) = self;
+
+        match value.into_inner().unwrap_or_else(|| parse(&source)) {
+            Ok(value) => Ok(value),
+            Err(err) => Err(Section(source, parse, OnceLock::from(Err(err)))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "lazy"))]
+mod tests {
+    use super::{Sections, Table};
+    use crate::value::{intern, Error, Value};
+
+    #[test]
+    fn test_sections() {
+        let mut table = Table::new();
+        let mut sections = Sections::new(vec![intern("a"), intern("b"), intern("c")]);
+
+        table.0.insert(intern("b"), Value::from("second"));
+        sections.defer(intern("a"), "first", |source| Ok(Value::from(source)));
+        sections.defer(intern("c"), "third", |_| Err(Error::custom("invalid")));
+
+        assert!(sections.1["a"].2.get().is_none());
+        assert_eq!(sections.get("a"), Some(Ok(&Value::from("first"))));
+        assert!(sections.1["a"].2.get().is_some());
+        assert!(sections.1["c"].2.get().is_none());
+        assert!(sections.contains("c"));
+        assert!(!sections.contains("b"));
+        assert_eq!(sections.get("c"), Some(Err(Error::custom("invalid"))));
+        assert_eq!(
+            sections
+                .materialized(&table)
+                .0
+                .keys()
+                .map(|key| &**key)
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        sections.materialize(&mut table);
+
+        assert_eq!(
+            table.0.keys().map(|key| &**key).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert!(!sections.is_empty());
+        assert_eq!(sections.get("c"), Some(Err(Error::custom("invalid"))));
+    }
+}
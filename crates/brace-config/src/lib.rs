@@ -1,8 +1,24 @@
+pub use self::builder::{ConfigBuilder, Conflict};
 pub use self::config::Config;
+pub use self::interpolate::InterpolationMode;
+pub use self::layer::Layer;
 pub use self::value::{from_value, to_value, Array, Entry, Table, Value};
 
 pub mod file;
+pub mod interpolate;
+pub mod schema;
+pub mod secret;
+pub mod source;
 pub mod value;
 
+#[cfg(feature = "watch")]
+pub mod watch;
+
+mod access;
+mod builder;
+mod cache;
 mod config;
+mod layer;
+mod lazy;
 mod macros;
+mod sensitive;
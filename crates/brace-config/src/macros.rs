@@ -185,7 +185,7 @@ mod tests {
 
         assert_eq!(entry.as_entry().unwrap().value(), "entry");
         assert_eq!(array.get::<_, String>("0").unwrap(), "array");
-        assert_eq!(table.get::<_, bool>("table").unwrap(), true);
+        assert!(table.get::<_, bool>("table").unwrap());
     }
 
     #[test]
@@ -0,0 +1,619 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error as DeError, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use super::{ValidationError, ValidationReport};
+use crate::value::de::ValueDeserializer;
+use crate::value::{Table, Value};
+
+const MAX_ATTEMPTS: usize = 256;
+
+pub(crate) fn check<T>(table: &Table) -> ValidationReport
+where
+    T: DeserializeOwned,
+{
+    let root = Value::Table(table.clone());
+    let state = RefCell::new(State::default());
+    let mut report = ValidationReport::new();
+
+    for _ in 0..MAX_ATTEMPTS {
+        state.borrow_mut().failure = None;
+
+        let result = T::deserialize(Check {
+            value: &root,
+            path: String::new(),
+            state: &state,
+        });
+
+        let err = match result {
+            Ok(_) => break,
+            Err(err) => err,
+        };
+
+        let mut state = state.borrow_mut();
+        let (path, message) = state
+            .failure
+            .take()
+            .unwrap_or_else(|| (String::new(), err.message));
+
+        report.push(ValidationError::new(path.as_str(), message));
+
+        if path.is_empty() || !state.placeholders.insert(path) {
+            break;
+        }
+    }
+
+    report
+}
+
+#[derive(Default)]
+struct State {
+    placeholders: HashSet<String>,
+    failure: Option<(String, String)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Error {
+    message: String,
+    missing: Option<&'static str>,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DeError for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Self {
+            message: msg.to_string(),
+            missing: None,
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self {
+            message: format!("missing field `{}`", field),
+            missing: Some(field),
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+struct Check<'a> {
+    value: &'a Value,
+    path: String,
+    state: &'a RefCell<State>,
+}
+
+impl<'a> Check<'a> {
+    fn fail(&self, path: String, message: String) {
+        let mut state = self.state.borrow_mut();
+
+        if state.failure.is_none() {
+            state.failure = Some((path, message));
+        }
+    }
+
+    fn delegate<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce(ValueDeserializer<'a>) -> Result<T, crate::value::de::Error>,
+    {
+        func(ValueDeserializer::new(self.value)).map_err(|err| {
+            self.fail(self.path.clone(), err.to_string());
+
+            Error::custom(err)
+        })
+    }
+
+    fn child(&self, key: &str, value: &'a Value) -> Check<'a> {
+        Check {
+            value,
+            path: join(&self.path, key),
+            state: self.state,
+        }
+    }
+
+    fn finish<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        result.inspect_err(|err| match err.missing {
+            Some(field) => self.fail(
+                join(&self.path, field),
+                String::from("missing required key"),
+            ),
+            None => self.fail(self.path.clone(), err.message.clone()),
+        })
+    }
+}
+
+macro_rules! delegate {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'a>,
+            {
+                self.delegate(|de| de.$method(visitor))
+            }
+        )*
+    };
+}
+
+impl<'a> Deserializer<'a> for Check<'a> {
+    type Error = Error;
+
+    delegate! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.delegate(|de| de.deserialize_unit_struct(name, visitor))
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.delegate(|de| de.deserialize_newtype_struct(name, visitor))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.delegate(|de| de.deserialize_tuple(len, visitor))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.delegate(|de| de.deserialize_tuple_struct(name, len, visitor))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.delegate(|de| de.deserialize_enum(name, variants, visitor))
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        match self.value {
+            Value::Array(array) => {
+                let items = array
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| self.child(&index.to_string(), value))
+                    .collect::<Vec<_>>();
+                let result = visitor.visit_seq(Items(items.into_iter()));
+
+                self.finish(result)
+            }
+            _ => self.delegate(|de| de.deserialize_seq(visitor)),
+        }
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        self.deserialize_struct("", &[], visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'a>,
+    {
+        let table = match self.value {
+            Value::Table(table) => table,
+            _ => return self.delegate(|de| de.deserialize_map(visitor)),
+        };
+
+        let mut entries = table
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), Some(self.child(key, value))))
+            .collect::<Vec<_>>();
+
+        for field in fields {
+            let path = join(&self.path, field);
+
+            if table.lookup(*field).is_none() && self.state.borrow().placeholders.contains(&path) {
+                entries.push((field.to_string(), None));
+            }
+        }
+
+        let result = visitor.visit_map(Entries {
+            entries: entries.into_iter(),
+            value: None,
+            state: self.state,
+            path: self.path.clone(),
+        });
+
+        self.finish(result)
+    }
+}
+
+struct Items<'a>(std::vec::IntoIter<Check<'a>>);
+
+impl<'a> SeqAccess<'a> for Items<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'a>,
+    {
+        match self.0.next() {
+            Some(check) => {
+                if check.state.borrow().placeholders.contains(&check.path) {
+                    return seed.deserialize(Placeholder).map(Some);
+                }
+
+                seed.deserialize(check).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+struct Entries<'a> {
+    entries: std::vec::IntoIter<(String, Option<Check<'a>>)>,
+    value: Option<Option<Check<'a>>>,
+    state: &'a RefCell<State>,
+    path: String,
+}
+
+impl<'a> MapAccess<'a> for Entries<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'a>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+
+                seed.deserialize(Key(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'a>,
+    {
+        match self.value.take() {
+            Some(Some(check)) => {
+                if self.state.borrow().placeholders.contains(&check.path) {
+                    return seed.deserialize(Placeholder);
+                }
+
+                seed.deserialize(check)
+            }
+            Some(None) => seed.deserialize(Placeholder),
+            None => Err(Error::custom(format!(
+                "value requested before key at '{}'",
+                self.path
+            ))),
+        }
+    }
+}
+
+struct Key(String);
+
+impl<'de> Deserializer<'de> for Key {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.0)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+macro_rules! placeholder {
+    ($($method:ident => $visit:ident($value:expr))*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit($value)
+            }
+        )*
+    };
+}
+
+struct Placeholder;
+
+impl<'de> Deserializer<'de> for Placeholder {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(false)
+    }
+
+    placeholder! {
+        deserialize_i8 => visit_i8(0)
+        deserialize_i16 => visit_i16(0)
+        deserialize_i32 => visit_i32(0)
+        deserialize_i64 => visit_i64(0)
+        deserialize_i128 => visit_i128(0)
+        deserialize_u8 => visit_u8(0)
+        deserialize_u16 => visit_u16(0)
+        deserialize_u32 => visit_u32(0)
+        deserialize_u64 => visit_u64(0)
+        deserialize_u128 => visit_u128(0)
+        deserialize_f32 => visit_f32(0.0)
+        deserialize_f64 => visit_f64(0.0)
+        deserialize_string => visit_string(String::new())
+        deserialize_byte_buf => visit_byte_buf(Vec::new())
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_char(' ')
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str("")
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bytes(b"")
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Placeholders(0))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Placeholders(len))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Placeholders(len))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Fields(&[]))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Fields(fields))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match variants.first() {
+            Some(variant) => visitor.visit_enum(Variant(variant)),
+            None => Err(Error::custom("enum has no variants")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        unit unit_struct identifier ignored_any
+    }
+}
+
+struct Placeholders(usize);
+
+impl<'de> SeqAccess<'de> for Placeholders {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.0 == 0 {
+            return Ok(None);
+        }
+
+        self.0 -= 1;
+
+        seed.deserialize(Placeholder).map(Some)
+    }
+}
+
+struct Fields(&'static [&'static str]);
+
+impl<'de> MapAccess<'de> for Fields {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.0.split_first() {
+            Some((field, rest)) => {
+                self.0 = rest;
+
+                seed.deserialize(Key(field.to_string())).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Placeholder)
+    }
+}
+
+struct Variant(&'static str);
+
+impl<'de> EnumAccess<'de> for Variant {
+    type Error = Error;
+    type Variant = Placeholder;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Key(self.0.to_owned()))
+            .map(|value| (value, Placeholder))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Placeholder {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Placeholder)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Placeholders(len))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Fields(fields))
+    }
+}
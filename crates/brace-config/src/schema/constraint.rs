@@ -0,0 +1,284 @@
+use std::fs::File;
+use std::net::IpAddr;
+use std::path::Path;
+
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use crate::value::Value;
+
+#[derive(Clone, Debug)]
+pub enum Constraint {
+    Min(f64),
+    Max(f64),
+    OneOf(Vec<String>),
+    MinLength(usize),
+    MaxLength(usize),
+    MinItems(usize),
+    MaxItems(usize),
+    #[cfg(feature = "regex")]
+    Pattern(Regex),
+    Url,
+    Email,
+    Ip,
+    Port,
+    PathExists,
+    ReadableFile,
+}
+
+impl Constraint {
+    pub fn check(&self, value: &Value) -> Result<(), String> {
+        match (self, value) {
+            (Constraint::Min(min), Value::Entry(entry)) => match entry.value().parse::<f64>() {
+                Ok(number) if number < *min => Err(format!(
+                    "value '{}' is less than the minimum of {}",
+                    entry.value(),
+                    min
+                )),
+                Ok(_) => Ok(()),
+                Err(_) => Err(format!("value '{}' is not a number", entry.value())),
+            },
+            (Constraint::Max(max), Value::Entry(entry)) => match entry.value().parse::<f64>() {
+                Ok(number) if number > *max => Err(format!(
+                    "value '{}' is greater than the maximum of {}",
+                    entry.value(),
+                    max
+                )),
+                Ok(_) => Ok(()),
+                Err(_) => Err(format!("value '{}' is not a number", entry.value())),
+            },
+            (Constraint::OneOf(values), Value::Entry(entry)) => {
+                if values.iter().any(|value| value == entry.value()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "value '{}' is not one of '{}'",
+                        entry.value(),
+                        values.join("', '")
+                    ))
+                }
+            }
+            (Constraint::MinLength(min), Value::Entry(entry)) => {
+                let length = entry.value().chars().count();
+
+                if length < *min {
+                    Err(format!(
+                        "value '{}' is shorter than {} characters",
+                        entry.value(),
+                        min
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            (Constraint::MaxLength(max), Value::Entry(entry)) => {
+                let length = entry.value().chars().count();
+
+                if length > *max {
+                    Err(format!(
+                        "value '{}' is longer than {} characters",
+                        entry.value(),
+                        max
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            (Constraint::MinItems(min), Value::Array(array)) => {
+                if array.len() < *min {
+                    Err(format!(
+                        "array has {} items, expected at least {}",
+                        array.len(),
+                        min
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            (Constraint::MaxItems(max), Value::Array(array)) => {
+                if array.len() > *max {
+                    Err(format!(
+                        "array has {} items, expected at most {}",
+                        array.len(),
+                        max
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            #[cfg(feature = "regex")]
+            (Constraint::Pattern(regex), Value::Entry(entry)) => {
+                if regex.is_match(entry.value()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "value '{}' does not match pattern '{}'",
+                        entry.value(),
+                        regex.as_str()
+                    ))
+                }
+            }
+            (Constraint::Url, Value::Entry(entry)) => {
+                if is_url(entry.value()) {
+                    Ok(())
+                } else {
+                    Err(format!("value '{}' is not a valid url", entry.value()))
+                }
+            }
+            (Constraint::Email, Value::Entry(entry)) => {
+                if is_email(entry.value()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "value '{}' is not a valid email address",
+                        entry.value()
+                    ))
+                }
+            }
+            (Constraint::Ip, Value::Entry(entry)) => match entry.value().parse::<IpAddr>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err(format!(
+                    "value '{}' is not a valid ip address",
+                    entry.value()
+                )),
+            },
+            (Constraint::Port, Value::Entry(entry)) => match entry.value().parse::<u16>() {
+                Ok(port) if port > 0 => Ok(()),
+                _ => Err(format!("value '{}' is not a valid port", entry.value())),
+            },
+            (Constraint::PathExists, Value::Entry(entry)) => {
+                if Path::new(entry.value()).exists() {
+                    Ok(())
+                } else {
+                    Err(format!("path '{}' does not exist", entry.value()))
+                }
+            }
+            (Constraint::ReadableFile, Value::Entry(entry)) => {
+                let path = Path::new(entry.value());
+
+                if path.is_file() && File::open(path).is_ok() {
+                    Ok(())
+                } else {
+                    Err(format!("path '{}' is not a readable file", entry.value()))
+                }
+            }
+            (Constraint::MinItems(_), _) | (Constraint::MaxItems(_), _) => {
+                Err(String::from("expected an array"))
+            }
+            _ => Err(String::from("expected a value")),
+        }
+    }
+}
+
+fn is_url(value: &str) -> bool {
+    let (scheme, rest) = match value.split_once("://") {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = host.rsplit('@').next().unwrap_or_default();
+
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        && !host.is_empty()
+        && !rest.chars().any(char::is_whitespace)
+}
+
+fn is_email(value: &str) -> bool {
+    let (local, domain) = match value.rsplit_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    !local.is_empty()
+        && !local.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !value.chars().any(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Constraint;
+    use crate::value;
+
+    #[test]
+    fn test_constraint_check() {
+        assert!(Constraint::Min(1.0).check(&value!(1)).is_ok());
+        assert_eq!(
+            Constraint::Max(65535.0).check(&value!(70000)),
+            Err(String::from(
+                "value '70000' is greater than the maximum of 65535"
+            ))
+        );
+        assert!(Constraint::Min(1.0).check(&value!("abc")).is_err());
+        assert!(
+            Constraint::OneOf(vec![String::from("a"), String::from("b")])
+                .check(&value!("b"))
+                .is_ok()
+        );
+        assert_eq!(
+            Constraint::OneOf(vec![String::from("a"), String::from("b")]).check(&value!("c")),
+            Err(String::from("value 'c' is not one of 'a', 'b'"))
+        );
+        assert!(Constraint::MinLength(3).check(&value!("ab")).is_err());
+        assert!(Constraint::MaxLength(3).check(&value!("abc")).is_ok());
+        assert!(Constraint::MinItems(1).check(&value!([])).is_err());
+        assert!(Constraint::MaxItems(1).check(&value!(["a", "b"])).is_err());
+        assert!(Constraint::MaxItems(1).check(&value!("a")).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_constraint_pattern() {
+        let pattern = Constraint::Pattern(regex::Regex::new("^[a-z]+$").unwrap());
+
+        assert!(pattern.check(&value!("abc")).is_ok());
+        assert_eq!(
+            pattern.check(&value!("ABC")),
+            Err(String::from(
+                "value 'ABC' does not match pattern '^[a-z]+$'"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_constraint_semantic() {
+        assert!(Constraint::Url
+            .check(&value!("https://example.com/a?b"))
+            .is_ok());
+        assert!(Constraint::Url
+            .check(&value!("postgres://user@db:5432"))
+            .is_ok());
+        assert_eq!(
+            Constraint::Url.check(&value!("example.com")),
+            Err(String::from("value 'example.com' is not a valid url"))
+        );
+        assert!(Constraint::Url.check(&value!("http://")).is_err());
+        assert!(Constraint::Email.check(&value!("user@example.com")).is_ok());
+        assert!(Constraint::Email.check(&value!("user@localhost")).is_err());
+        assert!(Constraint::Email.check(&value!("@example.com")).is_err());
+        assert!(Constraint::Ip.check(&value!("127.0.0.1")).is_ok());
+        assert!(Constraint::Ip.check(&value!("::1")).is_ok());
+        assert!(Constraint::Ip.check(&value!("256.0.0.1")).is_err());
+        assert!(Constraint::Port.check(&value!(8080)).is_ok());
+        assert!(Constraint::Port.check(&value!(0)).is_err());
+        assert_eq!(
+            Constraint::Port.check(&value!(70000)),
+            Err(String::from("value '70000' is not a valid port"))
+        );
+        assert!(Constraint::PathExists.check(&value!("src")).is_ok());
+        assert!(Constraint::PathExists.check(&value!("missing")).is_err());
+        assert!(Constraint::ReadableFile
+            .check(&value!("Cargo.toml"))
+            .is_ok());
+        assert_eq!(
+            Constraint::ReadableFile.check(&value!("src")),
+            Err(String::from("path 'src' is not a readable file"))
+        );
+    }
+}
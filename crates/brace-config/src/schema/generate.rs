@@ -0,0 +1,215 @@
+use serde_json::{json, Map, Value as Json};
+
+use super::{Constraint, KeySchema, Schema, Type};
+use crate::value::Value;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl Schema {
+    pub fn to_json_schema(&self) -> Json {
+        let mut root = object();
+
+        for key in self.keys() {
+            insert(&mut root, &key.path.split('.').collect::<Vec<_>>(), key);
+        }
+
+        if let Json::Object(map) = &mut root {
+            map.insert(String::from("$schema"), Json::from(DRAFT));
+        }
+
+        root
+    }
+}
+
+#[cfg(feature = "schemars")]
+pub fn struct_schema<T>() -> Json
+where
+    T: schemars::JsonSchema,
+{
+    schemars::schema_for!(T).to_value()
+}
+
+fn object() -> Json {
+    json!({ "type": "object", "properties": {} })
+}
+
+fn insert(node: &mut Json, parts: &[&str], key: &KeySchema) {
+    let (name, rest) = match parts.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+
+    if key.required && rest.is_empty() {
+        let required = node
+            .as_object_mut()
+            .map(|map| map.entry("required").or_insert_with(|| json!([])));
+
+        if let Some(Json::Array(required)) = required {
+            required.push(Json::from(*name));
+        }
+    }
+
+    let properties = match node.get_mut("properties").and_then(Json::as_object_mut) {
+        Some(properties) => properties,
+        None => return,
+    };
+
+    if rest.is_empty() {
+        properties.insert(name.to_string(), leaf(key));
+    } else {
+        let child = properties.entry(name.to_string()).or_insert_with(object);
+
+        if child.get("properties").is_none() {
+            if let Json::Object(map) = child {
+                map.insert(String::from("properties"), Json::Object(Map::new()));
+            }
+        }
+
+        insert(child, rest, key);
+    }
+}
+
+fn leaf(key: &KeySchema) -> Json {
+    let mut schema = match key.kind {
+        Type::Any => json!({}),
+        Type::Bool => json!({ "type": "boolean" }),
+        Type::String => json!({ "type": "string" }),
+        Type::Datetime => json!({ "type": "string", "format": "date-time" }),
+        Type::I8 => integer(i8::MIN as f64, i8::MAX as f64),
+        Type::I16 => integer(i16::MIN as f64, i16::MAX as f64),
+        Type::I32 => integer(i32::MIN as f64, i32::MAX as f64),
+        Type::I64 => json!({ "type": "integer" }),
+        Type::U8 => integer(0.0, u8::MAX as f64),
+        Type::U16 => integer(0.0, u16::MAX as f64),
+        Type::U32 => integer(0.0, u32::MAX as f64),
+        Type::U64 => json!({ "type": "integer", "minimum": 0 }),
+        Type::Float => json!({ "type": "number" }),
+        Type::Array => json!({ "type": "array" }),
+        Type::Table => json!({ "type": "object" }),
+    };
+
+    if let Json::Object(map) = &mut schema {
+        for constraint in &key.constraints {
+            let (name, value) = match constraint {
+                Constraint::Min(min) => ("minimum", number(*min)),
+                Constraint::Max(max) => ("maximum", number(*max)),
+                Constraint::OneOf(values) => ("enum", json!(values)),
+                Constraint::MinLength(min) => ("minLength", json!(min)),
+                Constraint::MaxLength(max) => ("maxLength", json!(max)),
+                Constraint::MinItems(min) => ("minItems", json!(min)),
+                Constraint::MaxItems(max) => ("maxItems", json!(max)),
+                #[cfg(feature = "regex")]
+                Constraint::Pattern(regex) => ("pattern", json!(regex.as_str())),
+                Constraint::Url => ("format", json!("uri")),
+                Constraint::Email => ("format", json!("email")),
+                Constraint::Port => {
+                    map.insert(String::from("minimum"), json!(1));
+                    ("maximum", json!(u16::MAX))
+                }
+                Constraint::Ip | Constraint::PathExists | Constraint::ReadableFile => continue,
+            };
+
+            map.insert(String::from(name), value);
+        }
+
+        if let Some(default) = &key.default {
+            map.insert(String::from("default"), to_json(default, &key.kind));
+        }
+    }
+
+    schema
+}
+
+fn integer(min: f64, max: f64) -> Json {
+    json!({ "type": "integer", "minimum": number(min), "maximum": number(max) })
+}
+
+fn number(value: f64) -> Json {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+fn to_json(value: &Value, kind: &Type) -> Json {
+    match value {
+        Value::Entry(entry) => {
+            let string = entry.value();
+
+            match kind {
+                Type::Bool => string.parse::<bool>().map(Json::from).ok(),
+                Type::I8 | Type::I16 | Type::I32 | Type::I64 => {
+                    string.parse::<i64>().map(Json::from).ok()
+                }
+                Type::U8 | Type::U16 | Type::U32 | Type::U64 => {
+                    string.parse::<u64>().map(Json::from).ok()
+                }
+                Type::Float => string.parse::<f64>().ok().map(number),
+                _ => None,
+            }
+            .unwrap_or_else(|| Json::from(string))
+        }
+        Value::Array(array) => array
+            .into_iter()
+            .map(|value| to_json(value, &Type::Any))
+            .collect(),
+        Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), to_json(value, &Type::Any)))
+            .collect::<Map<_, _>>()
+            .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::schema::{Schema, Type};
+
+    #[test]
+    fn test_to_json_schema() {
+        let schema = Schema::new()
+            .key("name", Type::String)
+            .required()
+            .length(1, 16)
+            .key("server.port", Type::U16)
+            .required()
+            .default_value(8080)
+            .key("server.tls", Type::Bool)
+            .key("log.level", Type::String)
+            .one_of(vec!["info", "debug"]);
+
+        assert_eq!(
+            schema.to_json_schema(),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": { "type": "string", "minLength": 1, "maxLength": 16 },
+                    "server": {
+                        "type": "object",
+                        "required": ["port"],
+                        "properties": {
+                            "port": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "maximum": 65535,
+                                "default": 8080
+                            },
+                            "tls": { "type": "boolean" }
+                        }
+                    },
+                    "log": {
+                        "type": "object",
+                        "properties": {
+                            "level": { "type": "string", "enum": ["info", "debug"] }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use jsonschema::Validator;
+use serde_json::{Map, Number, Value as Json};
+
+use super::{ValidationError, ValidationReport};
+use crate::value::{Error, Table, Value};
+
+const MAX_DEPTH: usize = 32;
+
+pub struct JsonSchema {
+    schema: Json,
+    validator: Validator,
+}
+
+impl JsonSchema {
+    pub fn new(schema: Json) -> Result<Self, Error> {
+        let validator = jsonschema::draft202012::new(&schema).map_err(Error::custom)?;
+
+        Ok(Self { schema, validator })
+    }
+
+    pub fn parse(string: &str) -> Result<Self, Error> {
+        Self::new(serde_json::from_str(string).map_err(Error::custom)?)
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn for_type<T>() -> Result<Self, Error>
+    where
+        T: schemars::JsonSchema,
+    {
+        Self::new(super::struct_schema::<T>())
+    }
+
+    pub fn schema(&self) -> &Json {
+        &self.schema
+    }
+
+    pub fn validate(&self, table: &Table) -> ValidationReport {
+        let instance = typed(&Value::Table(table.clone()), &self.schema, &self.schema);
+        let mut report = ValidationReport::new();
+
+        for error in self.validator.iter_errors(&instance) {
+            report.push(ValidationError::new(
+                path(error.instance_path().as_str()),
+                error.to_string(),
+            ));
+        }
+
+        report
+    }
+}
+
+fn path(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|part| part.replace("~1", "/").replace("~0", "~"))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn typed(value: &Value, schema: &Json, root: &Json) -> Json {
+    let branches = branches(schema, root, 0);
+
+    match value {
+        Value::Table(table) => {
+            let mut map = Map::new();
+
+            for (key, value) in table {
+                let schema = find(&branches, |branch| branch["properties"].get(key))
+                    .or_else(|| find(&branches, |branch| branch.get("additionalProperties")))
+                    .unwrap_or(&Json::Null);
+
+                map.insert(key.to_string(), typed(value, schema, root));
+            }
+
+            Json::Object(map)
+        }
+        Value::Array(array) => Json::Array(
+            array
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let schema = find(&branches, |branch| branch["prefixItems"].get(index))
+                        .or_else(|| find(&branches, |branch| branch.get("items")))
+                        .unwrap_or(&Json::Null);
+
+                    typed(value, schema, root)
+                })
+                .collect(),
+        ),
+        Value::Entry(entry) => {
+            let types = branches
+                .iter()
+                .flat_map(|branch| match &branch["type"] {
+                    Json::String(kind) => vec![Json::String(kind.clone())],
+                    Json::Array(kinds) => kinds.clone(),
+                    _ => Vec::new(),
+                })
+                .collect::<Vec<_>>();
+
+            if types.is_empty() {
+                scalar(entry.value(), &Json::Null)
+            } else {
+                scalar(entry.value(), &Json::Array(types))
+            }
+        }
+    }
+}
+
+fn branches<'a>(schema: &'a Json, root: &'a Json, depth: usize) -> Vec<&'a Json> {
+    if depth > MAX_DEPTH {
+        return Vec::new();
+    }
+
+    let schema = match schema.get("$ref").and_then(Json::as_str) {
+        Some(reference) => match reference
+            .strip_prefix('#')
+            .and_then(|path| root.pointer(path))
+        {
+            Some(target) => return branches(target, root, depth + 1),
+            None => return Vec::new(),
+        },
+        None => schema,
+    };
+    let mut found = vec![schema];
+
+    for combinator in &["allOf", "anyOf", "oneOf"] {
+        if let Some(Json::Array(schemas)) = schema.get(*combinator) {
+            for schema in schemas {
+                found.extend(branches(schema, root, depth + 1));
+            }
+        }
+    }
+
+    found
+}
+
+fn find<'a, F>(branches: &[&'a Json], func: F) -> Option<&'a Json>
+where
+    F: Fn(&'a Json) -> Option<&'a Json>,
+{
+    branches.iter().find_map(|branch| func(branch))
+}
+
+fn scalar(string: &str, types: &Json) -> Json {
+    let allows = |name: &str| match types {
+        Json::String(kind) => kind == name,
+        Json::Array(kinds) => kinds.iter().any(|kind| kind == name),
+        _ => true,
+    };
+
+    if (types.is_string() || types.is_array()) && allows("string") {
+        return Json::String(string.to_owned());
+    }
+
+    if allows("boolean") {
+        if let Ok(value) = string.parse::<bool>() {
+            return Json::Bool(value);
+        }
+    }
+
+    if allows("integer") || allows("number") {
+        if let Ok(value) = string.parse::<i64>() {
+            return Json::Number(Number::from(value));
+        }
+
+        if let Ok(value) = string.parse::<u64>() {
+            return Json::Number(Number::from(value));
+        }
+    }
+
+    if allows("number") {
+        if let Some(value) = string.parse::<f64>().ok().and_then(Number::from_f64) {
+            return Json::Number(value);
+        }
+    }
+
+    Json::String(string.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{path, scalar, typed};
+    use crate::table;
+    use crate::value::Value;
+
+    #[test]
+    fn test_json_schema_scalar() {
+        assert_eq!(scalar("8080", &json!("integer")), json!(8080));
+        assert_eq!(scalar("8080", &json!("string")), json!("8080"));
+        assert_eq!(scalar("8080", &json!(["string", "integer"])), json!("8080"));
+        assert_eq!(scalar("true", &json!(null)), json!(true));
+        assert_eq!(scalar("1.5", &json!("number")), json!(1.5));
+        assert_eq!(scalar("abc", &json!("integer")), json!("abc"));
+        assert_eq!(path("/server/ports/0"), "server.ports.0");
+        assert_eq!(path("/a~1b"), "a/b");
+        assert_eq!(path(""), "");
+    }
+
+    #[test]
+    fn test_json_schema_typed() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "inner": { "$ref": "#/$defs/Inner" },
+                "either": { "anyOf": [{ "type": "integer" }, { "type": "null" }] },
+                "both": { "allOf": [{ "$ref": "#/$defs/Inner" }] },
+                "cycle": { "$ref": "#/$defs/Cycle" },
+            },
+            "$defs": {
+                "Inner": {
+                    "type": "object",
+                    "properties": { "version": { "type": "string" }, "port": { "type": "integer" } },
+                },
+                "Cycle": { "$ref": "#/$defs/Cycle" },
+            },
+        });
+        let table = table! {
+            "inner" = { "version" = "1", "port" = "80" },
+            "either" = "5",
+            "both" = { "version" = "2" },
+            "cycle" = "3",
+        };
+
+        assert_eq!(
+            typed(&Value::Table(table), &schema, &schema),
+            json!({
+                "inner": { "version": "1", "port": 80 },
+                "either": 5,
+                "both": { "version": "2" },
+                "cycle": 3,
+            })
+        );
+    }
+}
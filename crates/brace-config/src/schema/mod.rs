@@ -0,0 +1,462 @@
+use std::fmt;
+
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use crate::value::{Error, Table, Value};
+use crate::Config;
+
+pub use self::constraint::Constraint;
+#[cfg(feature = "schemars")]
+pub use self::generate::struct_schema;
+#[cfg(feature = "json-schema")]
+pub use self::json::JsonSchema;
+pub use self::report::{Severity, ValidationError, ValidationReport};
+pub use self::rule::Rule;
+pub use self::validator::Validator;
+
+pub(crate) use self::check::check;
+pub(crate) use self::validator::matches;
+
+mod check;
+mod constraint;
+mod report;
+mod rule;
+mod validator;
+
+#[cfg(feature = "json")]
+mod generate;
+
+#[cfg(feature = "json-schema")]
+mod json;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Any,
+    Bool,
+    String,
+    Datetime,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Float,
+    Array,
+    Table,
+}
+
+impl Type {
+    pub fn check(&self, value: &Value) -> Result<(), String> {
+        let valid = match (self, value) {
+            (Type::Any, _) => true,
+            (Type::Array, Value::Array(_)) => true,
+            (Type::Table, Value::Table(_)) => true,
+            (_, Value::Entry(entry)) => {
+                let string = entry.value();
+
+                match self {
+                    Type::Bool => string.parse::<bool>().is_ok(),
+                    Type::String => true,
+                    Type::Datetime => entry.is_datetime(),
+                    Type::I8 => string.parse::<i8>().is_ok(),
+                    Type::I16 => string.parse::<i16>().is_ok(),
+                    Type::I32 => string.parse::<i32>().is_ok(),
+                    Type::I64 => string.parse::<i64>().is_ok(),
+                    Type::U8 => string.parse::<u8>().is_ok(),
+                    Type::U16 => string.parse::<u16>().is_ok(),
+                    Type::U32 => string.parse::<u32>().is_ok(),
+                    Type::U64 => string.parse::<u64>().is_ok(),
+                    Type::Float => string.parse::<f64>().is_ok(),
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("expected {}, found {}", self, describe(value)))
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Type::Any => "any",
+            Type::Bool => "bool",
+            Type::String => "string",
+            Type::Datetime => "datetime",
+            Type::I8 => "i8",
+            Type::I16 => "i16",
+            Type::I32 => "i32",
+            Type::I64 => "i64",
+            Type::U8 => "u8",
+            Type::U16 => "u16",
+            Type::U32 => "u32",
+            Type::U64 => "u64",
+            Type::Float => "float",
+            Type::Array => "array",
+            Type::Table => "table",
+        };
+
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeySchema {
+    path: String,
+    kind: Type,
+    required: bool,
+    default: Option<Value>,
+    constraints: Vec<Constraint>,
+    severity: Severity,
+}
+
+impl KeySchema {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn kind(&self) -> &Type {
+        &self.kind
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn default_value(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check(&self, table: &Table, report: &mut ValidationReport) {
+        match table.lookup(self.path.as_str()) {
+            Some(value) => {
+                if let Err(message) = self.kind.check(value) {
+                    report.push(self.error(message));
+                    return;
+                }
+
+                for constraint in &self.constraints {
+                    if let Err(message) = constraint.check(value) {
+                        report.push(self.error(message));
+                    }
+                }
+            }
+            None if self.required && self.default.is_none() => {
+                report.push(self.error("missing required key"));
+            }
+            None => {}
+        }
+    }
+
+    fn error<M>(&self, message: M) -> ValidationError
+    where
+        M: Into<String>,
+    {
+        ValidationError::new(&self.path, message).with_severity(self.severity)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    keys: Vec<KeySchema>,
+    validators: Vec<Validator>,
+    rules: Vec<Rule>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key<K>(mut self, path: K, kind: Type) -> Self
+    where
+        K: Into<String>,
+    {
+        self.keys.push(KeySchema {
+            path: path.into(),
+            kind,
+            required: false,
+            default: None,
+            constraints: Vec::new(),
+            severity: Severity::Error,
+        });
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        if let Some(key) = self.keys.last_mut() {
+            key.required = true;
+        }
+
+        self
+    }
+
+    pub fn default_value<V>(mut self, value: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        if let Some(key) = self.keys.last_mut() {
+            key.default = Some(value.into());
+        }
+
+        self
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        if let Some(key) = self.keys.last_mut() {
+            key.severity = severity;
+        }
+
+        self
+    }
+
+    pub fn warning(self) -> Self {
+        self.severity(Severity::Warning)
+    }
+
+    pub fn constraint(mut self, constraint: Constraint) -> Self {
+        if let Some(key) = self.keys.last_mut() {
+            key.constraints.push(constraint);
+        }
+
+        self
+    }
+
+    pub fn min<N>(self, min: N) -> Self
+    where
+        N: Into<f64>,
+    {
+        self.constraint(Constraint::Min(min.into()))
+    }
+
+    pub fn max<N>(self, max: N) -> Self
+    where
+        N: Into<f64>,
+    {
+        self.constraint(Constraint::Max(max.into()))
+    }
+
+    pub fn range<N>(self, min: N, max: N) -> Self
+    where
+        N: Into<f64>,
+    {
+        self.min(min).max(max)
+    }
+
+    pub fn one_of<I, T>(self, values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.constraint(Constraint::OneOf(
+            values.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    pub fn length(self, min: usize, max: usize) -> Self {
+        self.constraint(Constraint::MinLength(min))
+            .constraint(Constraint::MaxLength(max))
+    }
+
+    pub fn items(self, min: usize, max: usize) -> Self {
+        self.constraint(Constraint::MinItems(min))
+            .constraint(Constraint::MaxItems(max))
+    }
+
+    #[cfg(feature = "regex")]
+    pub fn pattern(self, pattern: Regex) -> Self {
+        self.constraint(Constraint::Pattern(pattern))
+    }
+
+    pub fn url(self) -> Self {
+        self.constraint(Constraint::Url)
+    }
+
+    pub fn email(self) -> Self {
+        self.constraint(Constraint::Email)
+    }
+
+    pub fn ip(self) -> Self {
+        self.constraint(Constraint::Ip)
+    }
+
+    pub fn port(self) -> Self {
+        self.constraint(Constraint::Port)
+    }
+
+    pub fn path_exists(self) -> Self {
+        self.constraint(Constraint::PathExists)
+    }
+
+    pub fn readable_file(self) -> Self {
+        self.constraint(Constraint::ReadableFile)
+    }
+
+    pub fn validator<P, F>(mut self, pattern: P, func: F) -> Self
+    where
+        P: Into<String>,
+        F: FnMut(&Value) -> Result<(), String> + Send + 'static,
+    {
+        self.validators.push(Validator::new(pattern, func));
+        self
+    }
+
+    pub fn validators(&self) -> &[Validator] {
+        &self.validators
+    }
+
+    pub fn rule<P, F>(self, path: P, func: F) -> Self
+    where
+        P: Into<String>,
+        F: Fn(&Table) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.add_rule(Rule::new(path, func))
+    }
+
+    pub fn add_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn required_if<K, W, V>(self, key: K, when: W, value: V) -> Self
+    where
+        K: Into<String>,
+        W: Into<String>,
+        V: Into<Value>,
+    {
+        self.add_rule(Rule::required_if(key, when, value))
+    }
+
+    pub fn less_or_equal<A, B>(self, lower: A, upper: B) -> Self
+    where
+        A: Into<String>,
+        B: Into<String>,
+    {
+        self.add_rule(Rule::less_or_equal(lower, upper))
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn keys(&self) -> &[KeySchema] {
+        &self.keys
+    }
+
+    pub fn get(&self, path: &str) -> Option<&KeySchema> {
+        self.keys.iter().find(|key| key.path == path)
+    }
+
+    pub fn validate(&self, table: &Table) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
+        for key in &self.keys {
+            key.check(table, &mut report);
+        }
+
+        for validator in &self.validators {
+            for (path, message) in validator.run(table) {
+                report.push(ValidationError::new(path, message));
+            }
+        }
+
+        for rule in &self.rules {
+            if let Err(message) = rule.check(table) {
+                report.push(ValidationError::new(rule.path(), message));
+            }
+        }
+
+        report
+    }
+
+    pub(crate) fn validate_key(&self, table: &Table, path: &str) -> ValidationReport {
+        let prefix = format!("{}.", path);
+        let mut report = ValidationReport::new();
+
+        for key in &self.keys {
+            if key.path == path || key.path.starts_with(&prefix) {
+                key.check(table, &mut report);
+            }
+        }
+
+        report
+    }
+
+    pub fn apply_defaults(&self, config: &mut Config) -> Result<(), Error> {
+        for key in &self.keys {
+            if let Some(value) = &key.default {
+                if config.lookup(key.path.as_str()).is_none() {
+                    config.set(key.path.as_str(), value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Entry(entry) => format!("'{}'", entry.value()),
+        Value::Array(_) => String::from("an array"),
+        Value::Table(_) => String::from("a table"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schema, Type};
+    use crate::table;
+
+    #[test]
+    fn test_schema_validate() {
+        let schema = Schema::new()
+            .key("name", Type::String)
+            .required()
+            .key("server.port", Type::U16)
+            .required()
+            .key("server.host", Type::String)
+            .default_value("localhost")
+            .key("debug", Type::Bool)
+            .key("tags", Type::Array);
+
+        let report = schema.validate(&table! {
+            "name" = "app",
+            "server" = { "port" = 8080 },
+            "tags" = ["a"],
+        });
+
+        assert!(report.is_valid());
+
+        let report = schema.validate(&table! {
+            "server" = { "port" = 70000 },
+            "debug" = "maybe",
+            "tags" = "a",
+        });
+        let paths = report
+            .errors()
+            .iter()
+            .map(|error| error.path())
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, vec!["name", "server.port", "debug", "tags"]);
+        assert_eq!(report.errors()[1].message(), "expected u16, found '70000'");
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    path: String,
+    message: String,
+    severity: Severity,
+}
+
+impl ValidationError {
+    pub fn new<P, M>(path: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            severity: Severity::Error,
+        }
+    }
+
+    pub fn warning<P, M>(path: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        Self::new(path, message).with_severity(Severity::Warning)
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, error: ValidationError) {
+        self.errors.push(error);
+    }
+
+    pub fn extend(&mut self, other: ValidationReport) {
+        self.errors.extend(other.errors);
+    }
+
+    pub fn issues(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    pub fn errors(&self) -> Vec<&ValidationError> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> Vec<&ValidationError> {
+        self.with_severity(Severity::Warning)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors
+            .iter()
+            .all(|error| error.severity < Severity::Error)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<ValidationReport, ValidationReport> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(self)
+        }
+    }
+
+    fn with_severity(&self, severity: Severity) -> Vec<&ValidationError> {
+        self.errors
+            .iter()
+            .filter(|error| error.severity == severity)
+            .collect()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.errors.is_empty() {
+            return write!(f, "configuration is valid");
+        }
+
+        let errors = self.errors().len();
+        let warnings = self.warnings().len();
+
+        write!(
+            f,
+            "configuration has {} {} and {} {}:",
+            errors,
+            if errors == 1 { "error" } else { "errors" },
+            warnings,
+            if warnings == 1 { "warning" } else { "warnings" }
+        )?;
+
+        for error in &self.errors {
+            write!(f, "\n  {}: {}", error.severity, error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ValidationError, ValidationReport};
+
+    #[test]
+    fn test_report_display() {
+        let mut report = ValidationReport::new();
+
+        assert_eq!(report.to_string(), "configuration is valid");
+
+        report.push(ValidationError::new("server.port", "missing required key"));
+        report.push(ValidationError::warning("legacy", "key is deprecated"));
+
+        assert!(!report.is_valid());
+        assert_eq!(report.errors().len(), 1);
+        assert_eq!(report.warnings().len(), 1);
+        assert_eq!(
+            report.to_string(),
+            "configuration has 1 error and 1 warning:\n  error: server.port: missing required key\n  warning: legacy: key is deprecated"
+        );
+
+        let mut report = ValidationReport::new();
+
+        report.push(ValidationError::warning("legacy", "key is deprecated"));
+
+        assert!(report.is_valid());
+        assert!(report.into_result().is_ok());
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::value::{Table, Value};
+
+type RuleFn = dyn Fn(&Table) -> Result<(), String> + Send + Sync;
+
+#[derive(Clone)]
+pub struct Rule {
+    path: String,
+    func: Arc<RuleFn>,
+}
+
+impl Rule {
+    pub fn new<P, F>(path: P, func: F) -> Self
+    where
+        P: Into<String>,
+        F: Fn(&Table) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self {
+            path: path.into(),
+            func: Arc::new(func),
+        }
+    }
+
+    pub fn required_if<K, W, V>(key: K, when: W, value: V) -> Self
+    where
+        K: Into<String>,
+        W: Into<String>,
+        V: Into<Value>,
+    {
+        let key = key.into();
+        let when = when.into();
+        let value = value.into();
+        let path = key.clone();
+
+        Self::new(path, move |table| {
+            if matches(table.lookup(when.as_str()), &value) && table.lookup(key.as_str()).is_none()
+            {
+                Err(format!("required when '{}' is {}", when, describe(&value)))
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    pub fn less_or_equal<A, B>(lower: A, upper: B) -> Self
+    where
+        A: Into<String>,
+        B: Into<String>,
+    {
+        let lower = lower.into();
+        let upper = upper.into();
+        let path = lower.clone();
+
+        Self::new(path, move |table| {
+            let (min, max) = match (number(table, &lower), number(table, &upper)) {
+                (Some(min), Some(max)) => (min, max),
+                _ => return Ok(()),
+            };
+
+            if min <= max {
+                Ok(())
+            } else {
+                Err(format!(
+                    "value {} is greater than '{}' ({})",
+                    min, upper, max
+                ))
+            }
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub(crate) fn check(&self, table: &Table) -> Result<(), String> {
+        (self.func)(table)
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rule").field("path", &self.path).finish()
+    }
+}
+
+fn number(table: &Table, key: &str) -> Option<f64> {
+    match table.lookup(key) {
+        Some(Value::Entry(entry)) => entry.value().parse().ok(),
+        _ => None,
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Entry(entry) => format!("'{}'", entry.value()),
+        Value::Array(_) => String::from("an array"),
+        Value::Table(_) => String::from("a table"),
+    }
+}
+
+fn matches(found: Option<&Value>, value: &Value) -> bool {
+    match (found, value) {
+        (Some(Value::Entry(found)), Value::Entry(value)) => found.value() == value.value(),
+        (Some(found), value) => found == value,
+        (None, _) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rule;
+    use crate::table;
+
+    #[test]
+    fn test_rule_required_if() {
+        let rule = Rule::required_if("tls.cert", "tls.enabled", true);
+
+        assert!(rule
+            .check(&table! { "tls" = { "enabled" = false } })
+            .is_ok());
+        assert_eq!(
+            rule.check(&table! { "tls" = { "enabled" = true } }),
+            Err(String::from("required when 'tls.enabled' is 'true'"))
+        );
+        assert!(rule
+            .check(&table! { "tls" = { "enabled" = true, "cert" = "a.pem" } })
+            .is_ok());
+        assert!(rule
+            .check(&table! { "tls" = { "enabled" = "true" } })
+            .is_err());
+    }
+
+    #[test]
+    fn test_rule_less_or_equal() {
+        let rule = Rule::less_or_equal("pool.min", "pool.max");
+
+        assert!(rule
+            .check(&table! { "pool" = { "min" = 1, "max" = 4 } })
+            .is_ok());
+        assert!(rule.check(&table! { "pool" = { "min" = 1 } }).is_ok());
+        assert_eq!(
+            rule.check(&table! { "pool" = { "min" = 8, "max" = 4 } }),
+            Err(String::from("value 8 is greater than 'pool.max' (4)"))
+        );
+    }
+}
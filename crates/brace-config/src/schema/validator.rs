@@ -0,0 +1,119 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::value::{Table, Value};
+
+type ValidatorFn = dyn FnMut(&Value) -> Result<(), String> + Send;
+
+#[derive(Clone)]
+pub struct Validator {
+    pattern: String,
+    func: Arc<Mutex<Box<ValidatorFn>>>,
+}
+
+impl Validator {
+    pub fn new<P, F>(pattern: P, func: F) -> Self
+    where
+        P: Into<String>,
+        F: FnMut(&Value) -> Result<(), String> + Send + 'static,
+    {
+        Self {
+            pattern: pattern.into(),
+            func: Arc::new(Mutex::new(Box::new(func))),
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub(crate) fn run(&self, table: &Table) -> Vec<(String, String)> {
+        let pattern = self.pattern.split('.').collect::<Vec<_>>();
+        let mut func = match self.func.lock() {
+            Ok(func) => func,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut failures = Vec::new();
+
+        walk(
+            &Value::Table(table.clone()),
+            &mut Vec::new(),
+            &mut |path, value| {
+                if matches(&pattern, path) {
+                    if let Err(message) = func(value) {
+                        failures.push((path.join("."), message));
+                    }
+                }
+            },
+        );
+
+        failures
+    }
+}
+
+impl fmt::Debug for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Validator")
+            .field("pattern", &self.pattern)
+            .finish()
+    }
+}
+
+fn walk<F>(value: &Value, path: &mut Vec<String>, visit: &mut F)
+where
+    F: FnMut(&[String], &Value),
+{
+    let children: Box<dyn Iterator<Item = (String, &Value)>> = match value {
+        Value::Table(table) => Box::new(
+            table
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value)),
+        ),
+        Value::Array(array) => Box::new(
+            array
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| (index.to_string(), value)),
+        ),
+        Value::Entry(_) => return,
+    };
+
+    for (key, value) in children {
+        path.push(key);
+        visit(path, value);
+        walk(value, path, visit);
+        path.pop();
+    }
+}
+
+pub(crate) fn matches(pattern: &[&str], path: &[String]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (Some((&"**", rest)), _) => {
+            matches(rest, path) || (!path.is_empty() && matches(pattern, &path[1..]))
+        }
+        (Some((head, rest)), Some((part, tail))) => {
+            (*head == "*" || head == part) && matches(rest, tail)
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches;
+
+    fn path(path: &str) -> Vec<String> {
+        path.split('.').map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn test_validator_matches() {
+        assert!(matches(&["server", "port"], &path("server.port")));
+        assert!(matches(&["servers", "*", "port"], &path("servers.0.port")));
+        assert!(!matches(&["servers", "*", "port"], &path("servers.0.host")));
+        assert!(matches(&["**", "port"], &path("a.b.port")));
+        assert!(matches(&["**", "port"], &path("port")));
+        assert!(!matches(&["*"], &path("a.b")));
+    }
+}
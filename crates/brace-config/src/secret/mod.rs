@@ -0,0 +1,20 @@
+use crate::value::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecretAccess {
+    pub key: String,
+    pub context: Option<String>,
+}
+
+pub trait SecretResolver: Send + Sync {
+    fn resolve(&self, reference: &str) -> Result<String, Error>;
+}
+
+impl<F> SecretResolver for F
+where
+    F: Fn(&str) -> Result<String, Error> + Send + Sync,
+{
+    fn resolve(&self, reference: &str) -> Result<String, Error> {
+        self(reference)
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::schema::matches;
+use crate::secret::SecretAccess;
+use crate::value::{leaves, Array, Table, Value};
+
+pub(crate) const REDACTED: &str = "***";
+
+type AuditFn = dyn Fn(&SecretAccess) + Send + Sync;
+
+#[derive(Clone, Default)]
+pub(crate) struct Sensitive(Vec<Vec<String>>, Vec<Arc<AuditFn>>);
+
+impl Sensitive {
+    pub(crate) fn mark(&mut self, pattern: &str) {
+        let pattern = pattern
+            .split('.')
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+
+        if !self.0.contains(&pattern) {
+            self.0.push(pattern);
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn contains(&self, path: &str) -> bool {
+        let path = path.split('.').map(ToOwned::to_owned).collect::<Vec<_>>();
+
+        self.matches(&path)
+    }
+
+    pub(crate) fn add_hook(&mut self, hook: Arc<AuditFn>) {
+        self.1.push(hook);
+    }
+
+    pub(crate) fn audit(&self, value: Option<&Value>, key: &str, context: Option<&str>) {
+        if self.is_empty() || self.1.is_empty() {
+            return;
+        }
+
+        let mut paths = Vec::new();
+
+        if let Some(value) = value {
+            leaves(value, key, &mut paths);
+        }
+
+        for path in paths {
+            let segments = path.split('.').map(ToOwned::to_owned).collect::<Vec<_>>();
+
+            if (1..=segments.len()).any(|len| self.matches(&segments[..len])) {
+                let access = SecretAccess {
+                    key: path,
+                    context: context.map(ToOwned::to_owned),
+                };
+
+                for hook in &self.1 {
+                    hook(&access);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn redact(&self, table: &Table) -> Table {
+        if self.is_empty() {
+            return table.clone();
+        }
+
+        let mut path = Vec::new();
+
+        self.redact_table(table, &mut path)
+    }
+
+    #[cfg(feature = "zeroize")]
+    pub(crate) fn wipe(&self, table: &mut Table, target: &str) {
+        if self.is_empty() {
+            return;
+        }
+
+        let target = match target {
+            "" => Vec::new(),
+            target => target.split('.').map(ToOwned::to_owned).collect(),
+        };
+        let mut path = Vec::new();
+
+        for (key, value) in table {
+            path.push(key.to_string());
+            self.wipe_value(value, &mut path, &target, false);
+            path.pop();
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn wipe_value(&self, value: &mut Value, path: &mut Vec<String>, target: &[String], wipe: bool) {
+        let targeted = path.starts_with(target) || target.starts_with(path);
+        let wipe = wipe || (self.matches(path) && path.starts_with(target));
+
+        if !targeted {
+            return;
+        }
+
+        match value {
+            Value::Entry(entry) if wipe => entry.0.zeroize(),
+            Value::Entry(_) => {}
+            Value::Array(array) => {
+                for (index, item) in array.0.iter_mut().enumerate() {
+                    path.push(index.to_string());
+                    self.wipe_value(item, path, target, wipe);
+                    path.pop();
+                }
+            }
+            Value::Table(table) => {
+                for (key, item) in table.0.iter_mut() {
+                    path.push(key.to_string());
+                    self.wipe_value(item, path, target, wipe);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    fn matches(&self, path: &[String]) -> bool {
+        self.0.iter().any(|pattern| {
+            let pattern = pattern.iter().map(String::as_str).collect::<Vec<_>>();
+
+            matches(&pattern, path)
+        })
+    }
+
+    fn redact_table(&self, table: &Table, path: &mut Vec<String>) -> Table {
+        let mut redacted = Table::new();
+
+        for (key, value) in &table.0 {
+            path.push(key.to_string());
+            redacted
+                .0
+                .insert(key.clone(), self.redact_value(value, path));
+            path.pop();
+        }
+
+        redacted
+    }
+
+    fn redact_value(&self, value: &Value, path: &mut Vec<String>) -> Value {
+        if self.matches(path) {
+            return Value::from(REDACTED);
+        }
+
+        match value {
+            Value::Entry(_) => value.clone(),
+            Value::Array(array) => {
+                let mut items = Vec::new();
+
+                for (index, item) in array.into_iter().enumerate() {
+                    path.push(index.to_string());
+                    items.push(self.redact_value(item, path));
+                    path.pop();
+                }
+
+                Value::Array(Array::from(items))
+            }
+            Value::Table(table) => Value::Table(self.redact_table(table, path)),
+        }
+    }
+}
+
+impl fmt::Debug for Sensitive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Sensitive")
+            .field(&self.0)
+            .field(&self.1.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sensitive;
+    use crate::table;
+
+    #[test]
+    fn test_sensitive_redact() {
+        let mut sensitive = Sensitive::default();
+
+        sensitive.mark("database.password");
+        sensitive.mark("tokens.*");
+        sensitive.mark("**.secret");
+
+        let table = table! {
+            "database" = { "user" = "app", "password" = "hunter2" },
+            "tokens" = ["a", "b"],
+            "nested" = { "deep" = { "secret" = { "key" = "x" } } },
+        };
+
+        assert_eq!(
+            sensitive.redact(&table),
+            table! {
+                "database" = { "user" = "app", "password" = "***" },
+                "tokens" = ["***", "***"],
+                "nested" = { "deep" = { "secret" = "***" } },
+            }
+        );
+        assert!(sensitive.contains("database.password"));
+        assert!(!sensitive.contains("database.user"));
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_sensitive_wipe() {
+        let mut sensitive = Sensitive::default();
+
+        sensitive.mark("database.password");
+        sensitive.mark("tokens");
+
+        let mut table = table! {
+            "database" = { "user" = "app", "password" = "hunter2" },
+            "tokens" = ["a", "b"],
+            "other" = "x",
+        };
+
+        sensitive.wipe(&mut table, "database.user");
+
+        assert_eq!(table.get("database.password"), Ok(String::from("hunter2")));
+
+        sensitive.wipe(&mut table, "database");
+
+        assert_eq!(table.get("database.user"), Ok(String::from("app")));
+        assert_eq!(table.get("database.password"), Ok(String::new()));
+        assert_eq!(table.get("tokens.0"), Ok(String::from("a")));
+
+        sensitive.wipe(&mut table, "");
+
+        assert_eq!(table.get("tokens.0"), Ok(String::new()));
+        assert_eq!(table.get("other"), Ok(String::from("x")));
+    }
+}
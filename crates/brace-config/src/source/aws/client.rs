@@ -0,0 +1,251 @@
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use serde_json::Value as Json;
+use sha2::{Digest, Sha256};
+
+use super::Credentials;
+use crate::value::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Client {
+    pub(crate) service: &'static str,
+    pub(crate) region: String,
+    pub(crate) endpoint: Option<String>,
+    pub(crate) credentials: Option<Credentials>,
+}
+
+impl Client {
+    pub(crate) fn new(service: &'static str) -> Self {
+        Self {
+            service,
+            region: env::var("AWS_REGION")
+                .or_else(|_| env::var("AWS_DEFAULT_REGION"))
+                .unwrap_or_else(|_| String::from("us-east-1")),
+            endpoint: None,
+            credentials: None,
+        }
+    }
+
+    pub(crate) fn endpoint(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_owned(),
+            None => format!("https://{}.{}.amazonaws.com", self.service, self.region),
+        }
+    }
+
+    pub(crate) fn call(&self, target: &str, body: &Json) -> Result<Json, Error> {
+        let credentials = match &self.credentials {
+            Some(credentials) => credentials.clone(),
+            None => Credentials::from_env()?,
+        };
+        let endpoint = self.endpoint();
+        let host = endpoint
+            .split("://")
+            .nth(1)
+            .unwrap_or(&endpoint)
+            .split('/')
+            .next()
+            .unwrap_or("");
+        let body = body.to_string();
+        let content_type = "application/x-amz-json-1.1";
+        let (date, time) = timestamp(SystemTime::now());
+        let amz_date = format!("{}T{}Z", date, time);
+        let mut headers = vec![
+            ("content-type", content_type.to_owned()),
+            ("host", host.to_owned()),
+            ("x-amz-date", amz_date.clone()),
+            ("x-amz-target", target.to_owned()),
+        ];
+
+        if let Some(token) = &credentials.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+
+        let (signed, canonical) = canonical_request(&mut headers, &body);
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical.as_bytes()))
+        );
+        let key = signing_key(
+            &credentials.secret_access_key,
+            &date,
+            &self.region,
+            self.service,
+        );
+        let signature = hex(&hmac(&key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, signed, signature
+        );
+        let mut request =
+            ureq::post(&format!("{}/", endpoint)).set("Authorization", &authorization);
+
+        for (name, value) in &headers {
+            if *name != "host" {
+                request = request.set(name, value);
+            }
+        }
+
+        match request.send_string(&body) {
+            Ok(response) => response.into_json().map_err(Error::custom),
+            Err(ureq::Error::Status(status, response)) => {
+                let message = response
+                    .into_json::<Json>()
+                    .ok()
+                    .and_then(|json| {
+                        json["message"]
+                            .as_str()
+                            .or_else(|| json["Message"].as_str())
+                            .map(ToOwned::to_owned)
+                    })
+                    .unwrap_or_default();
+
+                Err(Error::custom(format!(
+                    "{} request failed with status {}: {}",
+                    target, status, message
+                )))
+            }
+            Err(err) => Err(Error::custom(err)),
+        }
+    }
+}
+
+fn canonical_request(headers: &mut [(&str, String)], body: &str) -> (String, String) {
+    headers.sort_by_key(|(name, _)| name.to_ascii_lowercase());
+
+    let signed = headers
+        .iter()
+        .map(|(name, _)| name.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical = format!(
+        "POST\n/\n\n{}\n{}\n{}",
+        headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name.to_ascii_lowercase(), value.trim()))
+            .collect::<String>(),
+        signed,
+        hex(&Sha256::digest(body.as_bytes()))
+    );
+
+    (signed, canonical)
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts keys of any size");
+
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let key = hmac(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+    let key = hmac(&key, region.as_bytes());
+    let key = hmac(&key, service.as_bytes());
+
+    hmac(&key, b"aws4_request")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn timestamp(time: SystemTime) -> (String, String) {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (
+        format!("{:04}{:02}{:02}", year, month, day),
+        format!("{:02}{:02}{:02}", rem / 3600, rem % 3600 / 60, rem % 60),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{canonical_request, hex, signing_key, timestamp};
+
+    #[test]
+    fn test_aws_canonical_request() {
+        let mut headers = vec![
+            ("content-type", String::from("application/x-amz-json-1.1")),
+            (
+                "host",
+                String::from("secretsmanager.us-east-1.amazonaws.com"),
+            ),
+            ("x-amz-date", String::from("20120215T113213Z")),
+            (
+                "x-amz-target",
+                String::from("secretsmanager.GetSecretValue"),
+            ),
+            ("x-amz-security-token", String::from(" session ")),
+        ];
+        let (signed, canonical) = canonical_request(&mut headers, "{}");
+
+        assert_eq!(
+            signed,
+            "content-type;host;x-amz-date;x-amz-security-token;x-amz-target"
+        );
+        assert_eq!(
+            canonical,
+            "POST\n/\n\n\
+             content-type:application/x-amz-json-1.1\n\
+             host:secretsmanager.us-east-1.amazonaws.com\n\
+             x-amz-date:20120215T113213Z\n\
+             x-amz-security-token:session\n\
+             x-amz-target:secretsmanager.GetSecretValue\n\n\
+             content-type;host;x-amz-date;x-amz-security-token;x-amz-target\n\
+             44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        );
+    }
+
+    #[test]
+    fn test_aws_signing_key() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_aws_timestamp() {
+        assert_eq!(
+            timestamp(UNIX_EPOCH),
+            (String::from("19700101"), String::from("000000"))
+        );
+        assert_eq!(
+            timestamp(UNIX_EPOCH + Duration::from_secs(1_329_305_533)),
+            (String::from("20120215"), String::from("113213"))
+        );
+        assert_eq!(
+            timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            (String::from("20000229"), String::from("000000"))
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::env;
+
+use crate::value::Error;
+
+#[cfg(any(feature = "ssm", feature = "secrets-manager"))]
+pub(crate) use self::client::Client;
+
+#[cfg(any(feature = "ssm", feature = "secrets-manager"))]
+mod client;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    pub fn new<A, S>(access_key_id: A, secret_access_key: S) -> Self
+    where
+        A: Into<String>,
+        S: Into<String>,
+    {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+
+    pub fn session_token<T>(mut self, session_token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    pub fn from_env() -> Result<Self, Error> {
+        let access_key_id = env::var("AWS_ACCESS_KEY_ID")
+            .map_err(|_| Error::custom("missing AWS_ACCESS_KEY_ID"))?;
+        let secret_access_key = env::var("AWS_SECRET_ACCESS_KEY")
+            .map_err(|_| Error::custom("missing AWS_SECRET_ACCESS_KEY"))?;
+
+        Ok(Self {
+            access_key_id,
+            secret_access_key,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish()
+    }
+}
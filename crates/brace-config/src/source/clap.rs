@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory};
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table, Value};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClapSource {
+    args: Vec<(String, Vec<String>, bool)>,
+    keys: HashMap<String, String>,
+    defaults: bool,
+}
+
+impl ClapSource {
+    pub fn from_matches(matches: &ArgMatches) -> Self {
+        let args = matches
+            .ids()
+            .filter_map(|id| {
+                let values = matches
+                    .try_get_raw(id.as_str())
+                    .ok()??
+                    .map(|value| value.to_string_lossy().into_owned())
+                    .collect();
+                let explicit = matches.value_source(id.as_str()) == Some(ValueSource::CommandLine);
+
+                Some((id.as_str().to_owned(), values, explicit))
+            })
+            .collect();
+
+        Self {
+            args,
+            keys: HashMap::new(),
+            defaults: false,
+        }
+    }
+
+    pub fn from_parser<T>() -> Self
+    where
+        T: CommandFactory,
+    {
+        Self::from_matches(&T::command().get_matches())
+    }
+
+    pub fn key<I, K>(mut self, id: I, key: K) -> Self
+    where
+        I: Into<String>,
+        K: Into<String>,
+    {
+        self.keys.insert(id.into(), key.into());
+        self
+    }
+
+    pub fn defaults(mut self, defaults: bool) -> Self {
+        self.defaults = defaults;
+        self
+    }
+}
+
+impl Source for ClapSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("clap")
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut table = Table::new();
+
+        for (id, values, explicit) in &self.args {
+            if !explicit && !self.defaults {
+                continue;
+            }
+
+            let key = self.keys.get(id).unwrap_or(id);
+            let value = match values.as_slice() {
+                [value] => Value::from(value.as_str()),
+                values => Value::from(
+                    values
+                        .iter()
+                        .map(String::as_str)
+                        .map(Value::from)
+                        .collect::<Vec<_>>(),
+                ),
+            };
+
+            table.set(key.as_str(), value)?;
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{Arg, ArgAction, Command};
+
+    use super::ClapSource;
+    use crate::source::Source;
+
+    fn command() -> Command {
+        Command::new("app")
+            .arg(Arg::new("port").long("port").default_value("80"))
+            .arg(Arg::new("host").long("host").default_value("localhost"))
+            .arg(
+                Arg::new("verbose")
+                    .long("verbose")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(Arg::new("tag").long("tag").action(ArgAction::Append))
+    }
+
+    #[test]
+    fn test_clap_source() {
+        let matches = command().get_matches_from([
+            "app",
+            "--port",
+            "8080",
+            "--verbose",
+            "--tag",
+            "a",
+            "--tag",
+            "b",
+        ]);
+        let source = ClapSource::from_matches(&matches).key("port", "server.port");
+        let table = source.collect().unwrap();
+
+        assert_eq!(table.get::<_, u16>("server.port"), Ok(8080));
+        assert_eq!(table.get::<_, bool>("verbose"), Ok(true));
+        assert_eq!(
+            table.get::<_, Vec<String>>("tag"),
+            Ok(vec![String::from("a"), String::from("b")])
+        );
+        assert!(table.get::<_, String>("host").is_err());
+
+        let table = source.defaults(true).collect().unwrap();
+
+        assert_eq!(
+            table.get::<_, String>("host"),
+            Ok(String::from("localhost"))
+        );
+        assert_eq!(table.get::<_, u16>("server.port"), Ok(8080));
+    }
+}
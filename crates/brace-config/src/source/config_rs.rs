@@ -0,0 +1,86 @@
+use std::convert::TryFrom;
+
+use config_rs::{ConfigError, Map, ValueKind};
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table, Value};
+use crate::Config;
+
+impl Source for config_rs::Config {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("config-rs")
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        self.clone()
+            .try_deserialize::<Table>()
+            .map_err(Error::custom)
+    }
+}
+
+impl config_rs::Source for Config {
+    fn clone_into_box(&self) -> Box<dyn config_rs::Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, config_rs::Value>, ConfigError> {
+        let origin = self.origin().map(|path| path.display().to_string());
+
+        Ok(table(Table::from(self.clone()), origin.as_ref()))
+    }
+}
+
+impl TryFrom<config_rs::Config> for Config {
+    type Error = ConfigError;
+
+    fn try_from(config: config_rs::Config) -> Result<Self, Self::Error> {
+        config.try_deserialize::<Table>().map(Config::from)
+    }
+}
+
+impl TryFrom<Config> for config_rs::Config {
+    type Error = ConfigError;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        config_rs::Config::builder().add_source(config).build()
+    }
+}
+
+impl TryFrom<config_rs::Value> for Value {
+    type Error = ConfigError;
+
+    fn try_from(value: config_rs::Value) -> Result<Self, Self::Error> {
+        value.try_deserialize()
+    }
+}
+
+impl From<Value> for config_rs::Value {
+    fn from(value: Value) -> Self {
+        convert(value, None)
+    }
+}
+
+fn convert(value: Value, origin: Option<&String>) -> config_rs::Value {
+    match value {
+        Value::Entry(entry) => config_rs::Value::new(origin, ValueKind::String(entry.0)),
+        Value::Array(array) => config_rs::Value::new(
+            origin,
+            ValueKind::Array(
+                array
+                    .into_iter()
+                    .map(|value| convert(value, origin))
+                    .collect(),
+            ),
+        ),
+        Value::Table(inner) => {
+            config_rs::Value::new(origin, ValueKind::Table(table(inner, origin)))
+        }
+    }
+}
+
+fn table(table: Table, origin: Option<&String>) -> Map<String, config_rs::Value> {
+    table
+        .into_iter()
+        .map(|(key, value)| (key, convert(value, origin)))
+        .collect()
+}
@@ -0,0 +1,192 @@
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use super::{Metadata, Source};
+use crate::file::{format, load_with, LoadOptions};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirSource {
+    path: PathBuf,
+    pattern: Option<String>,
+    options: LoadOptions,
+    required: bool,
+}
+
+impl DirSource {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            pattern: None,
+            options: LoadOptions::default(),
+            required: true,
+        }
+    }
+
+    pub fn glob<P>(pattern: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let pattern = pattern.as_ref();
+        let dir = match pattern.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        Self {
+            pattern: pattern
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            ..Self::new(dir)
+        }
+    }
+
+    pub fn options(mut self, options: LoadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn optional(self) -> Self {
+        self.required(false)
+    }
+
+    fn paths(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = Vec::new();
+
+        for entry in fs::read_dir(&self.path).map_err(Error::custom)? {
+            let path = entry.map_err(Error::custom)?.path();
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let matched = match &self.pattern {
+                Some(pattern) => glob(
+                    &pattern.chars().collect::<Vec<_>>(),
+                    &name.chars().collect::<Vec<_>>(),
+                ),
+                None => true,
+            };
+
+            if matched && !name.starts_with('.') && path.is_file() && format(&path).is_some() {
+                paths.push(path);
+            }
+        }
+
+        paths.sort();
+
+        Ok(paths)
+    }
+
+    fn load(&self) -> Result<Vec<(PathBuf, Table)>, Error> {
+        if !self.required && !self.path.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let paths = self.paths()?;
+        let threads = thread::available_parallelism().map_or(1, usize::from);
+        let size = paths.len().div_ceil(threads).max(1);
+        let options = &self.options;
+
+        thread::scope(|scope| {
+            paths
+                .chunks(size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|path| {
+                                load_with(path, options)
+                                    .map(|config| (path.clone(), Table::from(config)))
+                                    .map_err(Error::custom)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| panic::resume_unwind(err))
+                })
+                .collect()
+        })
+    }
+}
+
+impl Source for DirSource {
+    fn metadata(&self) -> Metadata {
+        let origin = match &self.pattern {
+            Some(pattern) => self.path.join(pattern),
+            None => self.path.clone(),
+        };
+
+        Metadata::new("dir").with_origin(origin.display().to_string())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut table = Table::new();
+
+        for (_, other) in self.load()? {
+            table.merge(other);
+        }
+
+        Ok(table)
+    }
+
+    fn fragments(&self) -> Result<Vec<(Metadata, Table)>, Error> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .map(|(path, table)| {
+                (
+                    Metadata::new("dir").with_origin(path.display().to_string()),
+                    table,
+                )
+            })
+            .collect())
+    }
+}
+
+fn glob(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (Some(('*', rest)), _) => {
+            glob(rest, name) || (!name.is_empty() && glob(pattern, &name[1..]))
+        }
+        (Some(('?', rest)), Some((_, tail))) => glob(rest, tail),
+        (Some((head, rest)), Some((part, tail))) => head == part && glob(rest, tail),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        glob(
+            &pattern.chars().collect::<Vec<_>>(),
+            &name.chars().collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_dir_glob() {
+        assert!(matches("*.toml", "10-server.toml"));
+        assert!(matches("??-*.json", "10-server.json"));
+        assert!(matches("app.toml", "app.toml"));
+        assert!(!matches("*.toml", "10-server.json"));
+        assert!(!matches("?-*.toml", "10-server.toml"));
+    }
+}
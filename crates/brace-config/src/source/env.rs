@@ -0,0 +1,285 @@
+use std::env;
+use std::ffi::OsString;
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table, Value};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvSource {
+    prefix: Option<String>,
+    separator: String,
+    try_parse_numbers: bool,
+    list_separator: Option<String>,
+    list_keys: Vec<String>,
+    vars: Option<Vec<(String, String)>>,
+}
+
+impl EnvSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prefix<T>(mut self, prefix: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn separator<T>(mut self, separator: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn try_parse_numbers(mut self, try_parse_numbers: bool) -> Self {
+        self.try_parse_numbers = try_parse_numbers;
+        self
+    }
+
+    pub fn list_separator<T>(mut self, list_separator: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.list_separator = Some(list_separator.into());
+        self
+    }
+
+    pub fn list_key<T>(mut self, key: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.list_keys.push(key.into());
+        self
+    }
+
+    pub fn vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.vars = Some(
+            vars.into_iter()
+                .map(|(key, val)| (key.into(), val.into()))
+                .collect(),
+        );
+        self
+    }
+
+    fn key(&self, name: &str) -> Option<String> {
+        let name = match &self.prefix {
+            Some(prefix) => {
+                let head = name.get(..prefix.len())?;
+                let rest = name.get(prefix.len()..)?;
+
+                if !head.eq_ignore_ascii_case(prefix) || !rest.starts_with('_') {
+                    return None;
+                }
+
+                &rest[1..]
+            }
+            None => name,
+        };
+
+        if name.is_empty() {
+            return None;
+        }
+
+        let key = name
+            .split(self.separator.as_str())
+            .map(|part| part.to_lowercase())
+            .collect::<Vec<_>>();
+
+        if key.iter().any(String::is_empty) {
+            return None;
+        }
+
+        Some(key.join("."))
+    }
+
+    fn value(&self, key: &str, val: &str) -> Value {
+        if let Some(separator) = &self.list_separator {
+            let listed = self.list_keys.is_empty() || self.list_keys.iter().any(|k| k == key);
+
+            if listed && val.contains(separator.as_str()) {
+                return Value::from(
+                    val.split(separator.as_str())
+                        .map(|item| self.scalar(item.trim()))
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        self.scalar(val)
+    }
+
+    fn scalar(&self, val: &str) -> Value {
+        if self.try_parse_numbers {
+            if let Ok(number) = val.parse::<i64>() {
+                return Value::from(number);
+            }
+
+            if let Ok(number) = val.parse::<f64>() {
+                if number.is_finite() {
+                    return Value::from(number);
+                }
+            }
+        }
+
+        Value::from(val)
+    }
+
+    fn os_vars<I>(&self, vars: I) -> Result<Vec<(String, String)>, Error>
+    where
+        I: IntoIterator<Item = (OsString, OsString)>,
+    {
+        let mut found = Vec::new();
+
+        for (name, val) in vars {
+            let name = match name.into_string() {
+                Ok(name) if self.key(&name).is_some() => name,
+                _ => continue,
+            };
+            let val = val.into_string().map_err(|_| {
+                Error::custom(format!(
+                    "environment variable '{}' is not valid unicode",
+                    name
+                ))
+            })?;
+
+            found.push((name, val));
+        }
+
+        Ok(found)
+    }
+}
+
+impl Default for EnvSource {
+    fn default() -> Self {
+        Self {
+            prefix: None,
+            separator: String::from("_"),
+            try_parse_numbers: false,
+            list_separator: None,
+            list_keys: Vec::new(),
+            vars: None,
+        }
+    }
+}
+
+impl Source for EnvSource {
+    fn metadata(&self) -> Metadata {
+        match &self.prefix {
+            Some(prefix) => Metadata::new("env").with_origin(prefix.clone()),
+            None => Metadata::new("env"),
+        }
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut vars = match &self.vars {
+            Some(vars) => vars.clone(),
+            None => self.os_vars(env::vars_os())?,
+        };
+        let mut table = Table::new();
+
+        vars.sort();
+
+        for (name, val) in vars {
+            if let Some(key) = self.key(&name) {
+                let val = self.value(&key, &val);
+
+                table.set(key.as_str(), val)?;
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EnvSource;
+    use crate::source::Source;
+
+    #[test]
+    fn test_env_source() {
+        let source = EnvSource::new()
+            .prefix("APP")
+            .separator("__")
+            .try_parse_numbers(true)
+            .list_separator(",")
+            .list_key("hosts")
+            .vars(vec![
+                ("APP_NAME", "demo"),
+                ("APP_SERVER__PORT", "8080"),
+                ("APP_SERVER__RATIO", "0.5"),
+                ("APP_HOSTS", "a, b,c"),
+                ("APP_GREETING", "hello, world"),
+                ("APPLICATION", "ignored"),
+                ("OTHER_NAME", "ignored"),
+            ]);
+        let table = source.collect().unwrap();
+
+        assert_eq!(table.get::<_, String>("name"), Ok(String::from("demo")));
+        assert_eq!(table.get::<_, u16>("server.port"), Ok(8080));
+        assert_eq!(table.get::<_, f64>("server.ratio"), Ok(0.5));
+        assert_eq!(
+            table.get::<_, Vec<String>>("hosts"),
+            Ok(vec![
+                String::from("a"),
+                String::from("b"),
+                String::from("c")
+            ])
+        );
+        assert_eq!(
+            table.get::<_, String>("greeting"),
+            Ok(String::from("hello, world"))
+        );
+        assert!(table.get::<_, String>("ication").is_err());
+        assert!(table.get::<_, String>("other").is_err());
+    }
+
+    #[test]
+    fn test_env_source_key() {
+        let source = EnvSource::new().prefix("app");
+
+        assert_eq!(
+            source.key("APP_SERVER_PORT"),
+            Some(String::from("server.port"))
+        );
+        assert_eq!(source.key("app_name"), Some(String::from("name")));
+        assert_eq!(source.key("APP_"), None);
+        assert_eq!(source.key("APP__NAME"), None);
+        assert_eq!(EnvSource::new().key("HOME"), Some(String::from("home")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_source_os_vars() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let source = EnvSource::new().prefix("APP");
+        let invalid = || OsString::from_vec(vec![0x66, 0x80]);
+        let vars = source
+            .os_vars(vec![
+                (OsString::from("APP_NAME"), OsString::from("demo")),
+                (invalid(), OsString::from("ignored")),
+                (OsString::from("OTHER"), invalid()),
+            ])
+            .unwrap();
+
+        assert_eq!(vars, vec![(String::from("APP_NAME"), String::from("demo"))]);
+        assert_eq!(
+            source
+                .os_vars(vec![(OsString::from("APP_NAME"), invalid())])
+                .unwrap_err()
+                .to_string(),
+            "environment variable 'APP_NAME' is not valid unicode"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::io::{BufRead, BufReader};
+use std::thread::{self, JoinHandle};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Value as Json};
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EtcdSource {
+    endpoint: String,
+    prefix: String,
+    auth: Option<(String, String)>,
+}
+
+impl EtcdSource {
+    pub fn new<E, P>(endpoint: E, prefix: P) -> Self
+    where
+        E: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_owned(),
+            prefix: prefix.into(),
+            auth: None,
+        }
+    }
+
+    pub fn auth<U, P>(mut self, username: U, password: P) -> Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.auth = Some((username.into(), password.into()));
+        self
+    }
+
+    pub fn watch<F>(&self, mut callback: F) -> JoinHandle<Result<(), Error>>
+    where
+        F: FnMut(Result<Table, Error>) + Send + 'static,
+    {
+        let source = self.clone();
+
+        thread::spawn(move || {
+            let (_, revision) = source.range()?;
+            let token = source.token()?;
+            let body = json!({
+                "create_request": {
+                    "key": STANDARD.encode(&source.prefix),
+                    "range_end": STANDARD.encode(range_end(&source.prefix)),
+                    "start_revision": (revision + 1).to_string(),
+                }
+            });
+            let response = source
+                .request("/v3/watch", token.as_deref())
+                .send_json(body)
+                .map_err(Error::custom)?;
+
+            for line in BufReader::new(response.into_reader()).lines() {
+                let line = line.map_err(Error::custom)?;
+                let message = serde_json::from_str::<Json>(&line).map_err(Error::custom)?;
+                let changed = message["result"]["events"]
+                    .as_array()
+                    .map(|events| !events.is_empty())
+                    .unwrap_or(false);
+
+                if changed {
+                    callback(source.collect());
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn range(&self) -> Result<(Table, i64), Error> {
+        let token = self.token()?;
+        let body = json!({
+            "key": STANDARD.encode(&self.prefix),
+            "range_end": STANDARD.encode(range_end(&self.prefix)),
+        });
+        let response = self
+            .request("/v3/kv/range", token.as_deref())
+            .send_json(body)
+            .map_err(Error::custom)?
+            .into_json::<Json>()
+            .map_err(Error::custom)?;
+        let revision = response["header"]["revision"]
+            .as_str()
+            .and_then(|revision| revision.parse().ok())
+            .unwrap_or(0);
+        let mut table = Table::new();
+
+        for kv in response["kvs"].as_array().into_iter().flatten() {
+            let key = decode(&kv["key"])?;
+            let value = decode(&kv["value"])?;
+
+            if let Some(key) = self.key(&key) {
+                table.set(key.as_str(), value)?;
+            }
+        }
+
+        Ok((table, revision))
+    }
+
+    fn token(&self) -> Result<Option<String>, Error> {
+        let (username, password) = match &self.auth {
+            Some(auth) => auth,
+            None => return Ok(None),
+        };
+        let response = self
+            .request("/v3/auth/authenticate", None)
+            .send_json(json!({ "name": username, "password": password }))
+            .map_err(Error::custom)?
+            .into_json::<Json>()
+            .map_err(Error::custom)?;
+
+        match response["token"].as_str() {
+            Some(token) => Ok(Some(token.to_owned())),
+            None => Err(Error::custom("etcd authentication returned no token")),
+        }
+    }
+
+    fn request(&self, path: &str, token: Option<&str>) -> ureq::Request {
+        let request = ureq::post(&format!("{}{}", self.endpoint, path));
+
+        match token {
+            Some(token) => request.set("Authorization", token),
+            None => request,
+        }
+    }
+
+    fn key(&self, key: &str) -> Option<String> {
+        let key = key[self.prefix.len().min(key.len())..]
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if key.is_empty() {
+            None
+        } else {
+            Some(key)
+        }
+    }
+}
+
+impl Source for EtcdSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("etcd").with_origin(format!("{}/{}", self.endpoint, self.prefix))
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        self.range().map(|(table, _)| table)
+    }
+}
+
+fn range_end(prefix: &str) -> Vec<u8> {
+    let mut end = prefix.as_bytes().to_vec();
+
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+
+            return end;
+        }
+    }
+
+    vec![0]
+}
+
+fn decode(value: &Json) -> Result<String, Error> {
+    let bytes = STANDARD
+        .decode(value.as_str().unwrap_or(""))
+        .map_err(Error::custom)?;
+
+    String::from_utf8(bytes).map_err(Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{range_end, EtcdSource};
+
+    #[test]
+    fn test_etcd_range_end() {
+        assert_eq!(range_end("/app/"), b"/app0".to_vec());
+        assert_eq!(range_end(""), vec![0]);
+    }
+
+    #[test]
+    fn test_etcd_key() {
+        let source = EtcdSource::new("http://localhost:2379", "/app/");
+
+        assert_eq!(source.key("/app/db/url"), Some(String::from("db.url")));
+        assert_eq!(source.key("/app/"), None);
+    }
+}
@@ -0,0 +1,57 @@
+use figment::value::{Dict, Map, Value as FigmentValue};
+use figment::{Figment, Profile, Provider};
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table};
+use crate::Config;
+
+#[derive(Clone, Debug)]
+pub struct FigmentSource {
+    figment: Figment,
+}
+
+impl FigmentSource {
+    pub fn new(figment: Figment) -> Self {
+        Self { figment }
+    }
+
+    pub fn provider<P>(provider: P) -> Self
+    where
+        P: Provider,
+    {
+        Self::new(Figment::from(provider))
+    }
+}
+
+impl From<Figment> for FigmentSource {
+    fn from(figment: Figment) -> Self {
+        Self::new(figment)
+    }
+}
+
+impl Source for FigmentSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("figment").with_origin(self.figment.profile().as_str().to_string())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        self.figment.extract::<Table>().map_err(Error::custom)
+    }
+}
+
+impl Provider for Config {
+    fn metadata(&self) -> figment::Metadata {
+        match self.origin() {
+            Some(path) => figment::Metadata::from("brace-config", path),
+            None => figment::Metadata::named("brace-config"),
+        }
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
+        let dict = FigmentValue::serialize(self)?
+            .into_dict()
+            .ok_or_else(|| figment::Error::from(String::from("config is not a table")))?;
+
+        Ok(Profile::Default.collect(dict))
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use super::{Metadata, Source};
+use crate::file::{load_as_with, load_with, Format, LoadOptions};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSource {
+    path: PathBuf,
+    format: Option<Format>,
+    options: LoadOptions,
+    required: bool,
+}
+
+impl FileSource {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            format: None,
+            options: LoadOptions::default(),
+            required: true,
+        }
+    }
+
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn options(mut self, options: LoadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn optional(self) -> Self {
+        self.required(false)
+    }
+}
+
+impl Source for FileSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("file").with_origin(self.path.display().to_string())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let config = match self.format {
+            Some(format) => load_as_with(&self.path, format, &self.options),
+            None => load_with(&self.path, &self.options),
+        };
+
+        match config {
+            Ok(config) => Ok(Table::from(config)),
+            Err(err) if err.is_not_found() && !self.required => Ok(Table::new()),
+            Err(err) => Err(Error::custom(err)),
+        }
+    }
+}
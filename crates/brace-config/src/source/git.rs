@@ -0,0 +1,143 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use super::{Metadata, Source};
+use crate::file::{from_str, Format};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitSource {
+    url: String,
+    checkout: PathBuf,
+    reference: String,
+    files: Vec<String>,
+    fetch: bool,
+}
+
+impl GitSource {
+    pub fn new<U, P>(url: U, checkout: P) -> Self
+    where
+        U: Into<String>,
+        P: AsRef<Path>,
+    {
+        Self {
+            url: url.into(),
+            checkout: checkout.as_ref().to_path_buf(),
+            reference: String::from("HEAD"),
+            files: Vec::new(),
+            fetch: true,
+        }
+    }
+
+    pub fn reference<T>(mut self, reference: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.reference = reference.into();
+        self
+    }
+
+    pub fn file<T>(mut self, file: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.files.push(file.into());
+        self
+    }
+
+    pub fn fetch(mut self, fetch: bool) -> Self {
+        self.fetch = fetch;
+        self
+    }
+
+    pub fn sync(&self) -> Result<(), Error> {
+        if self.checkout.join("HEAD").is_file() {
+            if self.fetch {
+                self.git(&["fetch", "--quiet", "--prune", "--", "origin"])?;
+            }
+
+            return Ok(());
+        }
+
+        let output = Command::new("git")
+            .arg("clone")
+            .arg("--quiet")
+            .arg("--mirror")
+            .arg("--")
+            .arg(&self.url)
+            .arg(&self.checkout)
+            .output();
+
+        check(output).map(|_| ())
+    }
+
+    pub fn revision(&self) -> Result<String, Error> {
+        let commit = format!("{}^{{commit}}", self.reference);
+
+        self.git(&["rev-parse", "--verify", "--end-of-options", &commit])
+            .map(|output| output.trim().to_owned())
+    }
+
+    fn show(&self, file: &str) -> Result<Table, Error> {
+        let object = format!("{}:{}", self.reference, file);
+        let string = self.git(&["show", "--end-of-options", &object])?;
+        let format = Format::from_path(file)
+            .or_else(|| Format::detect(&string))
+            .ok_or_else(|| Error::custom(format!("unknown format for '{}'", file)))?;
+
+        from_str(&string, format)
+            .map(Table::from)
+            .map_err(|err| Error::custom(format!("{}: {}", file, err)))
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String, Error> {
+        let output = Command::new("git")
+            .arg("--git-dir")
+            .arg(&self.checkout)
+            .args(args)
+            .output();
+
+        check(output)
+    }
+}
+
+impl Source for GitSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("git").with_origin(format!("{}#{}", self.url, self.reference))
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        if self.files.is_empty() {
+            return Err(Error::custom("git error: no files configured"));
+        }
+
+        self.sync()?;
+
+        let mut table = Table::new();
+
+        for file in &self.files {
+            table.merge(self.show(file)?);
+        }
+
+        Ok(table)
+    }
+}
+
+fn check(output: io::Result<Output>) -> Result<String, Error> {
+    let output = output.map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => {
+            Error::custom("git error: the git source requires a `git` executable on the PATH")
+        }
+        _ => Error::custom(format!("git error: {}", err)),
+    })?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+
+    Err(Error::custom(format!(
+        "git error: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
@@ -0,0 +1,27 @@
+use super::{Metadata, Source};
+use crate::file::http::load_url;
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlSource {
+    url: String,
+}
+
+impl UrlSource {
+    pub fn new<T>(url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self { url: url.into() }
+    }
+}
+
+impl Source for UrlSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("url").with_origin(self.url.clone())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        load_url(&self.url).map(Table::from).map_err(Error::custom)
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::Value as Json;
+
+use super::{Metadata, Source, Watch};
+use crate::value::{Error, Table};
+
+pub type K8sWatch = Watch;
+
+const TOKEN_PATH: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    ConfigMap,
+    Secret,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Location {
+    Mount(PathBuf),
+    Api {
+        endpoint: String,
+        namespace: String,
+        name: String,
+        token: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct K8sSource {
+    kind: Kind,
+    location: Location,
+    key: Option<String>,
+    trim: bool,
+}
+
+impl K8sSource {
+    pub fn mount<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            kind: Kind::ConfigMap,
+            location: Location::Mount(path.as_ref().to_path_buf()),
+            key: None,
+            trim: true,
+        }
+    }
+
+    pub fn config_map<E, N, M>(endpoint: E, namespace: N, name: M) -> Self
+    where
+        E: Into<String>,
+        N: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            kind: Kind::ConfigMap,
+            location: Location::Api {
+                endpoint: endpoint.into().trim_end_matches('/').to_owned(),
+                namespace: namespace.into(),
+                name: name.into(),
+                token: None,
+            },
+            key: None,
+            trim: true,
+        }
+    }
+
+    pub fn secret<E, N, M>(endpoint: E, namespace: N, name: M) -> Self
+    where
+        E: Into<String>,
+        N: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            kind: Kind::Secret,
+            ..Self::config_map(endpoint, namespace, name)
+        }
+    }
+
+    pub fn token<T>(mut self, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        if let Location::Api { token, .. } = &mut self.location {
+            *token = Some(value.into());
+        }
+
+        self
+    }
+
+    pub fn key<T>(mut self, key: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    pub fn watch<F>(&self, interval: Duration, mut callback: F) -> K8sWatch
+    where
+        F: FnMut(Result<Table, Error>) + Send + 'static,
+    {
+        let source = self.clone();
+        let mut version = source.version();
+
+        Watch::spawn(interval, move || {
+            let current = source.version();
+
+            if current != version {
+                version = current;
+                callback(source.collect());
+            }
+        })
+    }
+
+    fn version(&self) -> Option<String> {
+        match &self.location {
+            Location::Mount(path) => match fs::read_link(path.join("..data")) {
+                Ok(target) => Some(target.display().to_string()),
+                Err(_) => self.collect().ok().map(|table| format!("{:?}", table)),
+            },
+            Location::Api { .. } => self.fetch().ok().and_then(|json| {
+                json["metadata"]["resourceVersion"]
+                    .as_str()
+                    .map(ToOwned::to_owned)
+            }),
+        }
+    }
+
+    fn fetch(&self) -> Result<Json, Error> {
+        let (endpoint, namespace, name, token) = match &self.location {
+            Location::Api {
+                endpoint,
+                namespace,
+                name,
+                token,
+            } => (endpoint, namespace, name, token),
+            Location::Mount(_) => return Err(Error::custom("not an api source")),
+        };
+        let resource = match self.kind {
+            Kind::ConfigMap => "configmaps",
+            Kind::Secret => "secrets",
+        };
+        let token = match token {
+            Some(token) => Some(token.clone()),
+            None => fs::read_to_string(TOKEN_PATH)
+                .ok()
+                .map(|token| token.trim().to_owned()),
+        };
+        let mut request = ureq::get(&format!(
+            "{}/api/v1/namespaces/{}/{}/{}",
+            endpoint, namespace, resource, name
+        ));
+
+        if let Some(token) = token {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+
+        request
+            .call()
+            .map_err(Error::custom)?
+            .into_json()
+            .map_err(Error::custom)
+    }
+
+    fn entries(&self) -> Result<Vec<(String, String)>, Error> {
+        match &self.location {
+            Location::Mount(path) => {
+                let mut entries = Vec::new();
+
+                for entry in fs::read_dir(path).map_err(Error::custom)? {
+                    let entry = entry.map_err(Error::custom)?;
+                    let name = entry.file_name().to_string_lossy().into_owned();
+
+                    if name.starts_with('.') || !entry.path().is_file() {
+                        continue;
+                    }
+
+                    entries.push((
+                        name,
+                        fs::read_to_string(entry.path()).map_err(Error::custom)?,
+                    ));
+                }
+
+                entries.sort();
+
+                Ok(entries)
+            }
+            Location::Api { .. } => {
+                let json = self.fetch()?;
+                let mut entries = Vec::new();
+
+                for (name, value) in json["data"].as_object().into_iter().flatten() {
+                    let value = value.as_str().unwrap_or("");
+                    let value = match self.kind {
+                        Kind::ConfigMap => value.to_owned(),
+                        Kind::Secret => {
+                            String::from_utf8(STANDARD.decode(value).map_err(Error::custom)?)
+                                .map_err(Error::custom)?
+                        }
+                    };
+
+                    entries.push((name.clone(), value));
+                }
+
+                Ok(entries)
+            }
+        }
+    }
+}
+
+impl Source for K8sSource {
+    fn metadata(&self) -> Metadata {
+        match &self.location {
+            Location::Mount(path) => Metadata::new("k8s").with_origin(path.display().to_string()),
+            Location::Api {
+                namespace, name, ..
+            } => Metadata::new("k8s").with_origin(format!("{}/{}", namespace, name)),
+        }
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut table = Table::new();
+
+        for (name, value) in self.entries()? {
+            let value = if self.trim {
+                value.trim_end_matches(['\n', '\r']).to_owned()
+            } else {
+                value
+            };
+            let key = match &self.key {
+                Some(key) => format!("{}.{}", key, name),
+                None => name,
+            };
+
+            table.set(key.as_str(), value)?;
+        }
+
+        Ok(table)
+    }
+}
@@ -0,0 +1,48 @@
+use super::{Metadata, Source};
+use crate::value::{Error, Table};
+use crate::Config;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemorySource {
+    table: Table,
+    name: Option<String>,
+}
+
+impl MemorySource {
+    pub fn new(table: Table) -> Self {
+        Self { table, name: None }
+    }
+
+    pub fn name<T>(mut self, name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl From<Table> for MemorySource {
+    fn from(table: Table) -> Self {
+        Self::new(table)
+    }
+}
+
+impl From<Config> for MemorySource {
+    fn from(config: Config) -> Self {
+        Self::new(Table::from(config))
+    }
+}
+
+impl Source for MemorySource {
+    fn metadata(&self) -> Metadata {
+        match &self.name {
+            Some(name) => Metadata::new("memory").with_origin(name.as_str()),
+            None => Metadata::new("memory"),
+        }
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        Ok(self.table.clone())
+    }
+}
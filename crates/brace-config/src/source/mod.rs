@@ -0,0 +1,139 @@
+use crate::value::{Error, Table};
+
+#[cfg(feature = "aws")]
+pub use self::aws::Credentials;
+#[cfg(feature = "clap")]
+pub use self::clap::ClapSource;
+pub use self::dir::DirSource;
+pub use self::env::EnvSource;
+#[cfg(feature = "etcd")]
+pub use self::etcd::EtcdSource;
+#[cfg(feature = "figment")]
+pub use self::figment::FigmentSource;
+pub use self::file::FileSource;
+#[cfg(feature = "git")]
+pub use self::git::GitSource;
+#[cfg(feature = "http")]
+pub use self::http::UrlSource;
+#[cfg(feature = "k8s")]
+pub use self::k8s::{K8sSource, K8sWatch};
+pub use self::memory::MemorySource;
+#[cfg(feature = "http")]
+pub use self::poll::PollingSource;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisSource;
+#[cfg(feature = "secrets-manager")]
+pub use self::secrets_manager::SecretsManagerSource;
+#[cfg(feature = "sql")]
+pub use self::sql::SqlSource;
+#[cfg(feature = "ssm")]
+pub use self::ssm::SsmSource;
+pub use self::systemd::SystemdSource;
+#[cfg(feature = "vault")]
+pub use self::vault::{Lease, VaultAuth, VaultSource};
+#[cfg(any(feature = "http", feature = "redis", feature = "watch"))]
+pub use self::watch::Watch;
+
+mod dir;
+mod env;
+mod file;
+mod memory;
+mod systemd;
+
+#[cfg(feature = "aws")]
+mod aws;
+
+#[cfg(feature = "clap")]
+mod clap;
+
+#[cfg(feature = "config-rs")]
+mod config_rs;
+
+#[cfg(feature = "etcd")]
+mod etcd;
+
+#[cfg(feature = "figment")]
+mod figment;
+
+#[cfg(feature = "git")]
+mod git;
+
+#[cfg(feature = "k8s")]
+mod k8s;
+
+#[cfg(feature = "http")]
+mod poll;
+
+#[cfg(feature = "redis")]
+mod redis;
+
+#[cfg(feature = "secrets-manager")]
+mod secrets_manager;
+
+#[cfg(feature = "sql")]
+mod sql;
+
+#[cfg(feature = "ssm")]
+mod ssm;
+
+#[cfg(feature = "vault")]
+mod vault;
+
+#[cfg(any(feature = "http", feature = "redis", feature = "watch"))]
+mod watch;
+
+#[cfg(feature = "http")]
+mod http;
+
+pub trait Source: Send + Sync {
+    fn metadata(&self) -> Metadata;
+
+    fn collect(&self) -> Result<Table, Error>;
+
+    fn fragments(&self) -> Result<Vec<(Metadata, Table)>, Error> {
+        Ok(vec![(self.metadata(), self.collect()?)])
+    }
+}
+
+impl<S> Source for Box<S>
+where
+    S: Source + ?Sized,
+{
+    fn metadata(&self) -> Metadata {
+        (**self).metadata()
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        (**self).collect()
+    }
+
+    fn fragments(&self) -> Result<Vec<(Metadata, Table)>, Error> {
+        (**self).fragments()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    pub name: String,
+    pub origin: Option<String>,
+}
+
+impl Metadata {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            origin: None,
+        }
+    }
+
+    pub fn with_origin<T>(mut self, origin: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.origin = Some(origin.into());
+        self
+    }
+}
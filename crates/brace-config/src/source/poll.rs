@@ -0,0 +1,99 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::{Metadata, Source, Watch};
+use crate::file::http::parse;
+use crate::value::{Error, Table};
+
+#[derive(Debug, Default)]
+struct State {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    table: Option<Table>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PollingSource {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl PollingSource {
+    pub fn new<T>(url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            url: url.into(),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    pub fn refresh(&self) -> Result<Option<Table>, Error> {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let mut request = ureq::get(&self.url);
+
+        if state.table.is_some() {
+            if let Some(etag) = &state.etag {
+                request = request.set("If-None-Match", etag);
+            }
+
+            if let Some(last_modified) = &state.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        let response = request.call().map_err(Error::custom)?;
+
+        if response.status() == 304 {
+            return Ok(None);
+        }
+
+        let etag = response.header("ETag").map(ToOwned::to_owned);
+        let last_modified = response.header("Last-Modified").map(ToOwned::to_owned);
+        let content_type = response.header("Content-Type").map(ToOwned::to_owned);
+        let body = response.into_string().map_err(Error::custom)?;
+        let table =
+            Table::from(parse(&self.url, &body, content_type.as_deref()).map_err(Error::custom)?);
+
+        state.etag = etag;
+        state.last_modified = last_modified;
+
+        if state.table.as_ref() == Some(&table) {
+            return Ok(None);
+        }
+
+        state.table = Some(table.clone());
+
+        Ok(Some(table))
+    }
+
+    pub fn poll<F>(&self, interval: Duration, mut callback: F) -> Watch
+    where
+        F: FnMut(Result<Table, Error>) + Send + 'static,
+    {
+        let source = self.clone();
+
+        Watch::spawn(interval, move || match source.refresh() {
+            Ok(Some(table)) => callback(Ok(table)),
+            Ok(None) => {}
+            Err(err) => callback(Err(err)),
+        })
+    }
+}
+
+impl Source for PollingSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("poll").with_origin(self.url.clone())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        if let Some(table) = self.refresh()? {
+            return Ok(table);
+        }
+
+        let state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+
+        Ok(state.table.clone().unwrap_or_default())
+    }
+}
@@ -0,0 +1,410 @@
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use super::{Metadata, Source, Watch};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Target {
+    Hash(String),
+    Prefix(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedisSource {
+    address: String,
+    target: Target,
+    password: Option<String>,
+    database: u32,
+}
+
+impl RedisSource {
+    pub fn hash<A, K>(address: A, key: K) -> Self
+    where
+        A: Into<String>,
+        K: Into<String>,
+    {
+        Self {
+            address: address.into(),
+            target: Target::Hash(key.into()),
+            password: None,
+            database: 0,
+        }
+    }
+
+    pub fn prefix<A, P>(address: A, prefix: P) -> Self
+    where
+        A: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            target: Target::Prefix(prefix.into()),
+            ..Self::hash(address, "")
+        }
+    }
+
+    pub fn password<T>(mut self, password: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn database(mut self, database: u32) -> Self {
+        self.database = database;
+        self
+    }
+
+    pub fn watch<F>(&self, mut callback: F) -> Watch
+    where
+        F: FnMut(Result<Table, Error>) + Send + 'static,
+    {
+        let source = self.clone();
+
+        Watch::run(move |stop| {
+            let pattern = match &source.target {
+                Target::Hash(key) => format!("__keyspace@{}__:{}", source.database, key),
+                Target::Prefix(prefix) => {
+                    format!("__keyspace@{}__:{}*", source.database, prefix)
+                }
+            };
+            let mut connection = match source.connect() {
+                Ok(connection) => connection,
+                Err(err) => return callback(Err(err)),
+            };
+
+            if let Err(err) = connection
+                .command(&["PSUBSCRIBE", &pattern])
+                .and_then(|_| connection.timeout(Some(Duration::from_millis(100))))
+            {
+                return callback(Err(err));
+            }
+
+            while !stop.load(Ordering::SeqCst) {
+                match connection.read() {
+                    Ok(Reply::Array(message)) => {
+                        if let Some(Reply::Bulk(Some(kind))) = message.first() {
+                            if kind == "pmessage" {
+                                callback(source.collect());
+                            }
+                        }
+                    }
+                    Ok(Reply::Error(message)) => {
+                        return callback(Err(Error::custom(format!("redis error: {}", message))))
+                    }
+                    Ok(_) => {}
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                    Err(err) if err.kind() == io::ErrorKind::TimedOut => {}
+                    Err(err) => return callback(Err(Error::custom(err))),
+                }
+            }
+        })
+    }
+
+    fn connect(&self) -> Result<Connection, Error> {
+        let mut connection = Connection::open(&self.address)?;
+
+        if let Some(password) = &self.password {
+            connection.command(&["AUTH", password])?;
+        }
+
+        if self.database != 0 {
+            connection.command(&["SELECT", &self.database.to_string()])?;
+        }
+
+        Ok(connection)
+    }
+
+    fn key(&self, key: &str) -> Option<String> {
+        let key = match &self.target {
+            Target::Prefix(prefix) => key.strip_prefix(prefix.as_str())?,
+            Target::Hash(_) => key,
+        };
+        let key = key
+            .split(':')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if key.is_empty() {
+            None
+        } else {
+            Some(key)
+        }
+    }
+}
+
+impl Source for RedisSource {
+    fn metadata(&self) -> Metadata {
+        match &self.target {
+            Target::Hash(key) => {
+                Metadata::new("redis").with_origin(format!("{}/{}", self.address, key))
+            }
+            Target::Prefix(prefix) => {
+                Metadata::new("redis").with_origin(format!("{}/{}*", self.address, prefix))
+            }
+        }
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut connection = self.connect()?;
+        let mut table = Table::new();
+
+        let pairs = match &self.target {
+            Target::Hash(key) => {
+                let items = connection.command(&["HGETALL", key])?.into_strings();
+
+                items
+                    .chunks(2)
+                    .filter_map(|pair| match pair {
+                        [field, value] => Some((field.clone(), value.clone())),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            }
+            Target::Prefix(prefix) => {
+                let pattern = format!("{}*", prefix);
+                let mut keys = Vec::new();
+                let mut cursor = String::from("0");
+
+                loop {
+                    let reply = connection
+                        .command(&["SCAN", &cursor, "MATCH", &pattern, "COUNT", "100"])?;
+                    let mut parts = match reply {
+                        Reply::Array(parts) => parts.into_iter(),
+                        _ => return Err(Error::custom("unexpected redis scan reply")),
+                    };
+
+                    cursor = parts
+                        .next()
+                        .and_then(Reply::into_string)
+                        .unwrap_or_else(|| String::from("0"));
+                    keys.extend(parts.next().map(Reply::into_strings).unwrap_or_default());
+
+                    if cursor == "0" {
+                        break;
+                    }
+                }
+
+                keys.sort();
+                keys.dedup();
+
+                let mut pairs = Vec::new();
+
+                for key in keys {
+                    match connection.send(&["GET", &key])? {
+                        Reply::Error(message) if message.starts_with("WRONGTYPE") => {}
+                        Reply::Error(message) => {
+                            return Err(Error::custom(format!("redis error: {}", message)))
+                        }
+                        reply => {
+                            if let Some(value) = reply.into_string() {
+                                pairs.push((key, value));
+                            }
+                        }
+                    }
+                }
+
+                pairs
+            }
+        };
+
+        for (key, value) in pairs {
+            if let Some(key) = self.key(&key) {
+                table.set(key.as_str(), value)?;
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Reply {
+    Status(String),
+    Error(String),
+    Integer(i64),
+    Bulk(Option<String>),
+    Array(Vec<Reply>),
+}
+
+impl Reply {
+    fn into_string(self) -> Option<String> {
+        match self {
+            Reply::Status(string) | Reply::Bulk(Some(string)) => Some(string),
+            Reply::Integer(integer) => Some(integer.to_string()),
+            _ => None,
+        }
+    }
+
+    fn into_strings(self) -> Vec<String> {
+        match self {
+            Reply::Array(items) => items.into_iter().filter_map(Reply::into_string).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+struct Connection {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+}
+
+impl Connection {
+    fn open(address: &str) -> Result<Self, Error> {
+        let address = address.trim_start_matches("redis://");
+        let stream = TcpStream::connect(address).map_err(Error::custom)?;
+
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+        })
+    }
+
+    fn timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.stream.set_read_timeout(timeout).map_err(Error::custom)
+    }
+
+    fn command(&mut self, args: &[&str]) -> Result<Reply, Error> {
+        match self.send(args)? {
+            Reply::Error(message) => Err(Error::custom(format!("redis error: {}", message))),
+            reply => Ok(reply),
+        }
+    }
+
+    fn send(&mut self, args: &[&str]) -> Result<Reply, Error> {
+        let mut request = format!("*{}\r\n", args.len());
+
+        for arg in args {
+            request.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+        }
+
+        self.stream
+            .write_all(request.as_bytes())
+            .map_err(Error::custom)?;
+
+        self.read().map_err(Error::custom)
+    }
+
+    fn read(&mut self) -> io::Result<Reply> {
+        let mut chunk = [0; 4096];
+
+        loop {
+            if let Some((reply, len)) = parse(&self.buffer)? {
+                self.buffer.drain(..len);
+
+                return Ok(reply);
+            }
+
+            match self.stream.read(&mut chunk)? {
+                0 => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "redis connection closed",
+                    ))
+                }
+                len => self.buffer.extend_from_slice(&chunk[..len]),
+            }
+        }
+    }
+}
+
+fn parse(buf: &[u8]) -> io::Result<Option<(Reply, usize)>> {
+    let end = match buf.windows(2).position(|window| window == b"\r\n") {
+        Some(end) => end,
+        None => return Ok(None),
+    };
+    let line = std::str::from_utf8(&buf[..end]).map_err(invalid)?;
+    let (kind, rest) = line.split_at(1.min(line.len()));
+    let start = end + 2;
+    let reply = match kind {
+        "+" => Reply::Status(rest.to_owned()),
+        "-" => Reply::Error(rest.to_owned()),
+        ":" => Reply::Integer(rest.parse().map_err(invalid)?),
+        "$" => match rest.parse::<i64>().map_err(invalid)? {
+            len if len < 0 => Reply::Bulk(None),
+            len => {
+                let stop = start + len as usize;
+
+                if buf.len() < stop + 2 {
+                    return Ok(None);
+                }
+
+                let string = String::from_utf8(buf[start..stop].to_vec()).map_err(invalid)?;
+
+                return Ok(Some((Reply::Bulk(Some(string)), stop + 2)));
+            }
+        },
+        "*" => match rest.parse::<i64>().map_err(invalid)? {
+            len if len < 0 => Reply::Array(Vec::new()),
+            len => {
+                let mut items = Vec::new();
+                let mut offset = start;
+
+                for _ in 0..len {
+                    match parse(&buf[offset..])? {
+                        Some((item, used)) => {
+                            items.push(item);
+                            offset += used;
+                        }
+                        None => return Ok(None),
+                    }
+                }
+
+                return Ok(Some((Reply::Array(items), offset)));
+            }
+        },
+        _ => return Err(invalid(format!("unexpected redis reply '{}'", line))),
+    };
+
+    Ok(Some((reply, start)))
+}
+
+fn invalid<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, RedisSource, Reply};
+
+    #[test]
+    fn test_redis_key() {
+        let source = RedisSource::prefix("127.0.0.1:6379", "app:");
+
+        assert_eq!(source.key("app:db:url"), Some(String::from("db.url")));
+        assert_eq!(source.key("other:db"), None);
+        assert_eq!(
+            RedisSource::hash("127.0.0.1:6379", "flags").key("beta"),
+            Some(String::from("beta"))
+        );
+    }
+
+    #[test]
+    fn test_redis_parse() {
+        let message = b"*2\r\n$7\r\nmessage\r\n:1\r\n";
+
+        assert_eq!(parse(&message[..12]).unwrap(), None);
+        assert_eq!(
+            parse(message).unwrap(),
+            Some((
+                Reply::Array(vec![
+                    Reply::Bulk(Some(String::from("message"))),
+                    Reply::Integer(1)
+                ]),
+                message.len()
+            ))
+        );
+        assert_eq!(
+            parse(b"-WRONGTYPE bad\r\n").unwrap(),
+            Some((Reply::Error(String::from("WRONGTYPE bad")), 16))
+        );
+        assert!(parse(b"?\r\n").is_err());
+    }
+}
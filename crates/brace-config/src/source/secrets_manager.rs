@@ -0,0 +1,151 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value as Json};
+
+use super::aws::{Client, Credentials};
+use super::{Metadata, Source};
+use crate::value::{Error, Table, Value};
+
+#[derive(Clone, Debug)]
+pub struct SecretsManagerSource {
+    secrets: Vec<(String, Option<String>)>,
+    refresh_interval: Option<Duration>,
+    cache: Arc<Mutex<Option<(Instant, Table)>>>,
+    client: Client,
+}
+
+impl SecretsManagerSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn secret<T>(mut self, id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.secrets.push((id.into(), None));
+        self
+    }
+
+    pub fn secret_at<T, K>(mut self, id: T, key: K) -> Self
+    where
+        T: Into<String>,
+        K: Into<String>,
+    {
+        self.secrets.push((id.into(), Some(key.into())));
+        self
+    }
+
+    pub fn refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = Some(refresh_interval);
+        self
+    }
+
+    pub fn region<T>(mut self, region: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.client.region = region.into();
+        self
+    }
+
+    pub fn endpoint<T>(mut self, endpoint: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.client.endpoint = Some(endpoint.into());
+        self
+    }
+
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.client.credentials = Some(credentials);
+        self
+    }
+
+    pub fn invalidate(&self) {
+        *self.cache.lock().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    fn fetch(&self) -> Result<Table, Error> {
+        let mut table = Table::new();
+
+        for (id, key) in &self.secrets {
+            let response = self
+                .client
+                .call("secretsmanager.GetSecretValue", &json!({ "SecretId": id }))?;
+            let string = match response["SecretString"].as_str() {
+                Some(string) => string,
+                None => {
+                    return Err(Error::custom(format!(
+                        "secret '{}' has no string value",
+                        id
+                    )))
+                }
+            };
+            let value = match serde_json::from_str::<Json>(string) {
+                Ok(json) if json.is_object() => Value::deserialize(&json).map_err(Error::custom)?,
+                _ => Value::from(string),
+            };
+
+            match (key, value) {
+                (Some(key), value) => {
+                    table.set(key.as_str(), value)?;
+                }
+                (None, Value::Table(secret)) => table.merge(secret),
+                (None, _) => {
+                    return Err(Error::custom(format!(
+                        "secret '{}' is not a json object and needs a key",
+                        id
+                    )))
+                }
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+impl Default for SecretsManagerSource {
+    fn default() -> Self {
+        Self {
+            secrets: Vec::new(),
+            refresh_interval: None,
+            cache: Arc::new(Mutex::new(None)),
+            client: Client::new("secretsmanager"),
+        }
+    }
+}
+
+impl Source for SecretsManagerSource {
+    fn metadata(&self) -> Metadata {
+        let ids = self
+            .secrets
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect::<Vec<_>>();
+
+        Metadata::new("secrets-manager").with_origin(ids.join(","))
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let refresh_interval = match self.refresh_interval {
+            Some(refresh_interval) => refresh_interval,
+            None => return self.fetch(),
+        };
+        let mut cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+
+        if let Some((fetched, table)) = &*cache {
+            if fetched.elapsed() < refresh_interval {
+                return Ok(table.clone());
+            }
+        }
+
+        let table = self.fetch()?;
+
+        *cache = Some((Instant::now(), table.clone()));
+
+        Ok(table)
+    }
+}
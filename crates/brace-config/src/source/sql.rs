@@ -0,0 +1,250 @@
+use std::thread;
+
+use sqlx::any::install_default_drivers;
+use sqlx::AnyConnection;
+use sqlx::Connection;
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SqlSource {
+    url: String,
+    table: String,
+    key_column: String,
+    value_column: String,
+    filter: Option<(String, String)>,
+}
+
+impl SqlSource {
+    pub fn new<T>(url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            url: url.into(),
+            table: String::from("settings"),
+            key_column: String::from("key"),
+            value_column: String::from("value"),
+            filter: None,
+        }
+    }
+
+    pub fn table<T>(mut self, table: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.table = table.into();
+        self
+    }
+
+    pub fn columns<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.key_column = key.into();
+        self.value_column = value.into();
+        self
+    }
+
+    pub fn filter<C, V>(mut self, column: C, value: V) -> Self
+    where
+        C: Into<String>,
+        V: Into<String>,
+    {
+        self.filter = Some((column.into(), value.into()));
+        self
+    }
+
+    pub async fn load(&self) -> Result<Table, Error> {
+        let query = self.query()?;
+
+        install_default_drivers();
+
+        let mut connection = AnyConnection::connect(&self.url)
+            .await
+            .map_err(Error::custom)?;
+        let query = sqlx::query_as::<_, (String, Option<String>)>(&query);
+        let query = match &self.filter {
+            Some((_, value)) => query.bind(value),
+            None => query,
+        };
+        let rows = query
+            .fetch_all(&mut connection)
+            .await
+            .map_err(Error::custom)?;
+        let mut table = Table::new();
+
+        for (key, value) in rows {
+            if let Some(value) = value {
+                table.set(key.as_str(), value)?;
+            }
+        }
+
+        connection.close().await.map_err(Error::custom)?;
+
+        Ok(table)
+    }
+
+    fn query(&self) -> Result<String, Error> {
+        let dialect = Dialect::from_url(&self.url)?;
+        let mut query = format!(
+            "SELECT {}, {} FROM {}",
+            dialect.identifier(&self.key_column)?,
+            dialect.identifier(&self.value_column)?,
+            dialect.identifier(&self.table)?
+        );
+
+        if let Some((column, _)) = &self.filter {
+            query.push_str(&format!(
+                " WHERE {} = {}",
+                dialect.identifier(column)?,
+                dialect.placeholder()
+            ));
+        }
+
+        query.push_str(&format!(
+            " ORDER BY {}",
+            dialect.identifier(&self.key_column)?
+        ));
+
+        Ok(query)
+    }
+}
+
+impl Source for SqlSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("sql").with_origin(self.table.clone())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let run = || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(Error::custom)?
+                .block_on(self.load())
+        };
+
+        if tokio::runtime::Handle::try_current().is_ok() {
+            thread::scope(|scope| {
+                scope
+                    .spawn(run)
+                    .join()
+                    .unwrap_or_else(|_| Err(Error::custom("sql source panicked")))
+            })
+        } else {
+            run()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    fn from_url(url: &str) -> Result<Self, Error> {
+        let scheme = url.split_once(':').map_or("", |(scheme, _)| scheme);
+
+        match scheme.to_ascii_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(Dialect::Postgres),
+            "mysql" | "mariadb" => Ok(Dialect::MySql),
+            "sqlite" => Ok(Dialect::Sqlite),
+            _ => Err(Error::custom(format!(
+                "unsupported sql url scheme '{}'",
+                scheme
+            ))),
+        }
+    }
+
+    fn placeholder(self) -> &'static str {
+        match self {
+            Dialect::Postgres => "$1",
+            Dialect::MySql | Dialect::Sqlite => "?",
+        }
+    }
+
+    fn identifier(self, name: &str) -> Result<String, Error> {
+        let quote = match self {
+            Dialect::MySql => '`',
+            Dialect::Postgres | Dialect::Sqlite => '"',
+        };
+        let mut parts = Vec::new();
+
+        for part in name.split('.') {
+            let valid =
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+            if !valid {
+                return Err(Error::custom(format!("invalid sql identifier '{}'", name)));
+            }
+
+            parts.push(format!("{}{}{}", quote, part, quote));
+        }
+
+        Ok(parts.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqlSource;
+
+    #[test]
+    fn test_sql_query() {
+        assert_eq!(
+            SqlSource::new("sqlite://settings.db").query().unwrap(),
+            r#"SELECT "key", "value" FROM "settings" ORDER BY "key""#
+        );
+        assert!(SqlSource::new("sqlite://settings.db")
+            .table("settings; DROP TABLE users")
+            .query()
+            .is_err());
+        assert!(SqlSource::new("sqlite://settings.db")
+            .table("public..settings")
+            .query()
+            .is_err());
+        assert!(SqlSource::new("oracle://localhost").query().is_err());
+    }
+
+    #[test]
+    fn test_sql_query_postgres() {
+        let expected = concat!(
+            r#"SELECT "name", "setting" FROM "public"."tenant_settings" "#,
+            r#"WHERE "tenant_id" = $1 ORDER BY "name""#
+        );
+
+        for url in &["postgres://localhost/app", "postgresql://localhost/app"] {
+            assert_eq!(
+                SqlSource::new(*url)
+                    .table("public.tenant_settings")
+                    .columns("name", "setting")
+                    .filter("tenant_id", "acme")
+                    .query()
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_sql_query_mysql() {
+        assert_eq!(
+            SqlSource::new("mysql://localhost/app").query().unwrap(),
+            "SELECT `key`, `value` FROM `settings` ORDER BY `key`"
+        );
+        assert_eq!(
+            SqlSource::new("mysql://localhost/app")
+                .table("app.settings")
+                .filter("tenant_id", "acme")
+                .query()
+                .unwrap(),
+            "SELECT `key`, `value` FROM `app`.`settings` WHERE `tenant_id` = ? ORDER BY `key`"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use serde_json::json;
+
+use super::aws::{Client, Credentials};
+use super::{Metadata, Source};
+use crate::value::{Error, Table};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SsmSource {
+    path: String,
+    decrypt: bool,
+    client: Client,
+}
+
+impl SsmSource {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Self {
+            path: path.into(),
+            decrypt: true,
+            client: Client::new("ssm"),
+        }
+    }
+
+    pub fn region<T>(mut self, region: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.client.region = region.into();
+        self
+    }
+
+    pub fn endpoint<T>(mut self, endpoint: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.client.endpoint = Some(endpoint.into());
+        self
+    }
+
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.client.credentials = Some(credentials);
+        self
+    }
+
+    pub fn decrypt(mut self, decrypt: bool) -> Self {
+        self.decrypt = decrypt;
+        self
+    }
+
+    fn key(&self, name: &str) -> Option<String> {
+        let prefix = self.path.trim_end_matches('/');
+        let rest = name.strip_prefix(prefix)?;
+
+        if !rest.is_empty() && !rest.starts_with('/') && !prefix.is_empty() {
+            return None;
+        }
+
+        let key = rest
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if key.is_empty() {
+            None
+        } else {
+            Some(key)
+        }
+    }
+}
+
+impl Source for SsmSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("ssm").with_origin(self.path.clone())
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut table = Table::new();
+        let mut next_token = None;
+
+        loop {
+            let mut body = json!({
+                "Path": self.path,
+                "Recursive": true,
+                "WithDecryption": self.decrypt,
+            });
+
+            if let Some(token) = next_token.take() {
+                body["NextToken"] = json!(token);
+            }
+
+            let response = self.client.call("AmazonSSM.GetParametersByPath", &body)?;
+
+            for parameter in response["Parameters"].as_array().into_iter().flatten() {
+                let name = parameter["Name"].as_str().unwrap_or("");
+                let value = parameter["Value"].as_str().unwrap_or("");
+
+                if let Some(key) = self.key(name) {
+                    table.set(key.as_str(), value)?;
+                }
+            }
+
+            match response["NextToken"].as_str() {
+                Some(token) if !token.is_empty() => next_token = Some(token.to_owned()),
+                _ => break,
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SsmSource;
+
+    #[test]
+    fn test_ssm_key() {
+        let source = SsmSource::new("/app");
+
+        assert_eq!(source.key("/app/db/url"), Some(String::from("db.url")));
+        assert_eq!(source.key("/app/port"), Some(String::from("port")));
+        assert_eq!(source.key("/application/port"), None);
+        assert_eq!(source.key("/other/port"), None);
+        assert_eq!(
+            SsmSource::new("/").key("/db/url"),
+            Some(String::from("db.url"))
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table};
+
+const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SystemdSource {
+    dir: Option<PathBuf>,
+    key: Option<String>,
+    names: HashMap<String, String>,
+    only: bool,
+    required: bool,
+    trim: bool,
+}
+
+impl SystemdSource {
+    pub fn new() -> Self {
+        Self {
+            trim: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn dir<P>(mut self, dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn key<T>(mut self, key: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn credential<N, K>(mut self, name: N, key: K) -> Self
+    where
+        N: Into<String>,
+        K: Into<String>,
+    {
+        self.names.insert(name.into(), key.into());
+        self
+    }
+
+    pub fn only(mut self, only: bool) -> Self {
+        self.only = only;
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    fn directory(&self) -> Option<PathBuf> {
+        match &self.dir {
+            Some(dir) => Some(dir.clone()),
+            None => env::var_os(CREDENTIALS_DIRECTORY)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
+        }
+    }
+}
+
+impl Source for SystemdSource {
+    fn metadata(&self) -> Metadata {
+        match self.directory() {
+            Some(dir) => Metadata::new("systemd").with_origin(dir.display().to_string()),
+            None => Metadata::new("systemd"),
+        }
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let dir = match self.directory() {
+            Some(dir) => dir,
+            None if self.required => {
+                return Err(Error::custom(format!(
+                    "{} is not set",
+                    CREDENTIALS_DIRECTORY
+                )))
+            }
+            None => return Ok(Table::new()),
+        };
+
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(&dir).map_err(Error::custom)? {
+            let entry = entry.map_err(Error::custom)?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            if name.starts_with('.') || !entry.path().is_file() {
+                continue;
+            }
+
+            let key = match self.names.get(&name) {
+                Some(key) => key.clone(),
+                None if self.only => continue,
+                None => name,
+            };
+
+            entries.push((key, entry.path()));
+        }
+
+        entries.sort();
+
+        let mut table = Table::new();
+
+        for (key, path) in entries {
+            let value = fs::read_to_string(&path).map_err(Error::custom)?;
+            let value = if self.trim {
+                value.trim_end_matches(['\n', '\r']).to_owned()
+            } else {
+                value
+            };
+            let key = match &self.key {
+                Some(prefix) => format!("{}.{}", prefix, key),
+                None => key,
+            };
+
+            table.set(key.as_str(), value)?;
+        }
+
+        Ok(table)
+    }
+}
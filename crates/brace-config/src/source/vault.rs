@@ -0,0 +1,240 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value as Json};
+
+use super::{Metadata, Source};
+use crate::value::{Error, Table, Value};
+
+type LeaseHook = Arc<dyn Fn(&Lease) + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VaultAuth {
+    Token(String),
+    AppRole { role_id: String, secret_id: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lease {
+    pub id: Option<String>,
+    pub duration: u64,
+    pub renewable: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Session {
+    token: String,
+    refresh: Option<Instant>,
+    renewable: bool,
+}
+
+impl Session {
+    fn new(token: String, lease: &Lease) -> Self {
+        let refresh = match lease.duration {
+            0 => None,
+            duration => Some(Instant::now() + Duration::from_secs(duration) * 2 / 3),
+        };
+
+        Self {
+            token,
+            refresh,
+            renewable: lease.renewable,
+        }
+    }
+
+    fn stale(&self) -> bool {
+        self.refresh
+            .is_some_and(|refresh| Instant::now() >= refresh)
+    }
+}
+
+#[derive(Clone)]
+pub struct VaultSource {
+    address: String,
+    mount: String,
+    path: String,
+    key: String,
+    auth: VaultAuth,
+    on_lease: Option<LeaseHook>,
+    session: Arc<Mutex<Option<Session>>>,
+}
+
+impl VaultSource {
+    pub fn new<A, P>(address: A, path: P, auth: VaultAuth) -> Self
+    where
+        A: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            address: address.into().trim_end_matches('/').to_owned(),
+            mount: String::from("secret"),
+            path: path.into().trim_matches('/').to_owned(),
+            key: String::from("secrets"),
+            auth,
+            on_lease: None,
+            session: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn mount<T>(mut self, mount: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.mount = mount.into().trim_matches('/').to_owned();
+        self
+    }
+
+    pub fn key<T>(mut self, key: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.key = key.into();
+        self
+    }
+
+    pub fn on_lease<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Lease) + Send + Sync + 'static,
+    {
+        self.on_lease = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn renew(&self, increment: Option<u64>) -> Result<Lease, Error> {
+        let token = self.token()?;
+        let lease = self.renew_token(&token, increment)?;
+
+        if let VaultAuth::AppRole { .. } = &self.auth {
+            *self.session.lock().unwrap_or_else(|err| err.into_inner()) =
+                Some(Session::new(token, &lease));
+        }
+
+        Ok(lease)
+    }
+
+    fn renew_token(&self, token: &str, increment: Option<u64>) -> Result<Lease, Error> {
+        let body = match increment {
+            Some(increment) => json!({ "increment": format!("{}s", increment) }),
+            None => json!({}),
+        };
+        let response = ureq::post(&self.url("auth/token/renew-self"))
+            .set("X-Vault-Token", token)
+            .send_json(body)
+            .map_err(Error::custom)?
+            .into_json::<Json>()
+            .map_err(Error::custom)?;
+        let lease = auth_lease(&response);
+
+        self.notify(&lease);
+
+        Ok(lease)
+    }
+
+    fn token(&self) -> Result<String, Error> {
+        let (role_id, secret_id) = match &self.auth {
+            VaultAuth::Token(token) => return Ok(token.clone()),
+            VaultAuth::AppRole { role_id, secret_id } => (role_id, secret_id),
+        };
+        let mut session = self.session.lock().unwrap_or_else(|err| err.into_inner());
+
+        if let Some(current) = session.clone() {
+            if !current.stale() {
+                return Ok(current.token);
+            }
+
+            if current.renewable {
+                if let Ok(lease) = self.renew_token(&current.token, None) {
+                    *session = Some(Session::new(current.token.clone(), &lease));
+
+                    return Ok(current.token);
+                }
+            }
+        }
+
+        let response = ureq::post(&self.url("auth/approle/login"))
+            .send_json(json!({ "role_id": role_id, "secret_id": secret_id }))
+            .map_err(Error::custom)?
+            .into_json::<Json>()
+            .map_err(Error::custom)?;
+        let lease = auth_lease(&response);
+
+        self.notify(&lease);
+
+        let token = match response["auth"]["client_token"].as_str() {
+            Some(token) => token.to_owned(),
+            None => return Err(Error::custom("vault approle login returned no token")),
+        };
+
+        *session = Some(Session::new(token.clone(), &lease));
+
+        Ok(token)
+    }
+
+    fn notify(&self, lease: &Lease) {
+        if let Some(hook) = &self.on_lease {
+            hook(lease);
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/v1/{}", self.address, path)
+    }
+}
+
+impl fmt::Debug for VaultSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VaultSource")
+            .field("address", &self.address)
+            .field("mount", &self.mount)
+            .field("path", &self.path)
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl Source for VaultSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("vault").with_origin(format!(
+            "{}/v1/{}/data/{}",
+            self.address, self.mount, self.path
+        ))
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let token = self.token()?;
+        let response = ureq::get(&self.url(&format!("{}/data/{}", self.mount, self.path)))
+            .set("X-Vault-Token", &token)
+            .call()
+            .map_err(Error::custom)?
+            .into_json::<Json>()
+            .map_err(Error::custom)?;
+
+        if response["lease_duration"].as_u64().unwrap_or(0) > 0 {
+            self.notify(&Lease {
+                id: response["lease_id"]
+                    .as_str()
+                    .filter(|id| !id.is_empty())
+                    .map(ToOwned::to_owned),
+                duration: response["lease_duration"].as_u64().unwrap_or(0),
+                renewable: response["renewable"].as_bool().unwrap_or(false),
+            });
+        }
+
+        let secrets = Value::deserialize(&response["data"]["data"]).map_err(Error::custom)?;
+        let mut table = Table::new();
+
+        table.set(self.key.as_str(), secrets)?;
+
+        Ok(table)
+    }
+}
+
+fn auth_lease(response: &Json) -> Lease {
+    Lease {
+        id: response["auth"]["accessor"].as_str().map(ToOwned::to_owned),
+        duration: response["auth"]["lease_duration"].as_u64().unwrap_or(0),
+        renewable: response["auth"]["renewable"].as_bool().unwrap_or(false),
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct Watch {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watch {
+    #[cfg_attr(not(any(feature = "http", feature = "watch")), allow(dead_code))]
+    pub(crate) fn spawn<F>(interval: Duration, mut tick: F) -> Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        Self::run(move |stop| {
+            while !stop.load(Ordering::SeqCst) {
+                let deadline = Instant::now() + interval;
+
+                while !stop.load(Ordering::SeqCst) {
+                    match deadline.checked_duration_since(Instant::now()) {
+                        Some(remaining) if !remaining.is_zero() => thread::park_timeout(remaining),
+                        _ => break,
+                    }
+                }
+
+                if !stop.load(Ordering::SeqCst) {
+                    tick();
+                }
+            }
+        })
+    }
+
+    pub(crate) fn run<F>(task: F) -> Self
+    where
+        F: FnOnce(Arc<AtomicBool>) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let handle = thread::spawn(move || task(stopped));
+
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.stop.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Watch;
+
+    #[test]
+    fn test_watch_drop() {
+        let watch = Watch::spawn(Duration::from_secs(60), || {});
+        let start = Instant::now();
+
+        assert!(watch.is_running());
+
+        drop(watch);
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
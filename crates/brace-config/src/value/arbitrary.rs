@@ -0,0 +1,113 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{intern, Array, Entry, Key, Table, Value};
+
+const MAX_DEPTH: usize = 8;
+
+impl<'a> Arbitrary<'a> for Entry {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=5_u8)? {
+            0 => Entry::null(),
+            1 => Entry::from(bool::arbitrary(u)?),
+            2 => Entry::from(i64::arbitrary(u)?),
+            3 => Entry::from(f64::arbitrary(u)?),
+            4 => Entry::bytes(Vec::<u8>::arbitrary(u)?),
+            _ => Entry::from(String::arbitrary(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Array {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        array(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Table {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        table(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Key {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Key(u.arbitrary()?))
+    }
+}
+
+fn value(u: &mut Unstructured, depth: usize) -> Result<Value> {
+    if depth >= MAX_DEPTH {
+        return Ok(Value::Entry(u.arbitrary()?));
+    }
+
+    Ok(match u.int_in_range(0..=2_u8)? {
+        0 => Value::Array(array(u, depth + 1)?),
+        1 => Value::Table(table(u, depth + 1)?),
+        _ => Value::Entry(u.arbitrary()?),
+    })
+}
+
+fn array(u: &mut Unstructured, depth: usize) -> Result<Array> {
+    let len = u.arbitrary_len::<Value>()?;
+    let mut array = Array::with_capacity(len);
+
+    for _ in 0..len {
+        array.0.push(value(u, depth)?);
+    }
+
+    Ok(array)
+}
+
+fn table(u: &mut Unstructured, depth: usize) -> Result<Table> {
+    let len = u.arbitrary_len::<(String, Value)>()?;
+    let mut table = Table::with_capacity(len);
+
+    for _ in 0..len {
+        let key = String::arbitrary(u)?;
+
+        table.0.insert(intern(key), value(u, depth)?);
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use super::{Key, Table, Value, MAX_DEPTH};
+
+    fn depth(value: &Value) -> usize {
+        match value {
+            Value::Entry(_) => 0,
+            Value::Array(array) => 1 + array.0.iter().map(depth).max().unwrap_or(0),
+            Value::Table(table) => 1 + table.0.values().map(depth).max().unwrap_or(0),
+        }
+    }
+
+    #[test]
+    fn test_arbitrary() {
+        let data = (0..4096_u32)
+            .map(|index| (index * 31 % 251) as u8)
+            .collect::<Vec<_>>();
+        let mut u = Unstructured::new(&data);
+
+        let table = Table::arbitrary(&mut u).unwrap();
+
+        assert!(!table.0.is_empty());
+        assert!(Key::arbitrary(&mut Unstructured::new(&data)).is_ok());
+
+        for byte in [0, 1, 0xff] {
+            let data = vec![byte; 4096];
+            let value = Value::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+            assert!(depth(&value) <= MAX_DEPTH);
+        }
+    }
+}
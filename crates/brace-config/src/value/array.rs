@@ -5,29 +5,38 @@ use std::vec::IntoIter;
 use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
 use serde::ser::{Serialize, SerializeSeq, Serializer};
 
-use super::{de::ValueDeserializer, ser::ValueSerializer, Error, Key, Value};
+use super::{capacity_hint, de::ValueDeserializer, ser::ValueSerializer, Error, Key, Value};
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Array(Vec<Value>);
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Array(pub(crate) Vec<Value>);
 
 impl Array {
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
     pub fn get<'de, K, V>(&'de self, key: K) -> Result<V, Error>
     where
         K: Into<Key>,
         V: 'de + Deserialize<'de>,
     {
-        let mut key = key.into();
+        let key = key.into();
+        let value = self.find(&key, 0)?;
 
-        match key.next() {
+        Ok(V::deserialize(ValueDeserializer::new(value)).map_err(|err| err.at(key.path()))?)
+    }
+
+    pub(crate) fn find(&self, key: &Key, depth: usize) -> Result<&Value, Error> {
+        match key.segment(depth) {
             Some(head) => match head.parse::<usize>() {
                 Ok(head) => match self.0.get(head) {
-                    Some(val) => match key.peek() {
-                        Some(_) => val.get(key),
-                        None => Ok(V::deserialize(ValueDeserializer::new(val))?),
+                    Some(val) => match key.segment(depth + 1) {
+                        Some(_) => val.find(key, depth + 1),
+                        None => Ok(val),
                     },
                     None => Err(Error::custom(format!("missing value for key '{}'", head))),
                 },
@@ -42,59 +51,51 @@ impl Array {
         K: Into<Key>,
         V: Serialize,
     {
-        let mut key = key.into();
+        let key = key.into();
+
+        if key.peek().is_none() {
+            return Err(Error::custom("empty key"));
+        }
+
+        let value = val
+            .serialize(ValueSerializer)
+            .map_err(|err| err.at(key.path()))?;
+
+        self.splice(key, value)?;
+
+        Ok(self)
+    }
+
+    pub(crate) fn splice(&mut self, mut key: Key, value: Value) -> Result<(), Error> {
+        let len = self.0.len();
 
         match key.next() {
             Some(head) => match head.parse::<usize>() {
                 Ok(index) => match self.0.get_mut(index) {
                     Some(item) => match key.peek() {
-                        Some(_) => {
-                            item.set(key, val)?;
-
-                            Ok(self)
-                        }
+                        Some(_) => item.splice(key, value),
                         None => {
-                            *item = val.serialize(ValueSerializer)?;
+                            *item = value;
 
-                            Ok(self)
+                            Ok(())
                         }
                     },
-                    None => {
-                        if index == 0 {
-                            match key.peek() {
-                                Some(_) => {
-                                    let mut value = Value::entry();
-                                    value.set(key, val)?;
-                                    self.0.insert(index, value);
-
-                                    Ok(self)
-                                }
-                                None => {
-                                    self.0.insert(index, val.serialize(ValueSerializer)?);
-
-                                    Ok(self)
-                                }
-                            }
-                        } else {
-                            match self.0.get(index - 1) {
-                                Some(_) => match key.peek() {
-                                    Some(_) => {
-                                        let mut value = Value::entry();
-                                        value.set(key, val)?;
-                                        self.0.insert(index, value);
-
-                                        Ok(self)
-                                    }
-                                    None => {
-                                        self.0.insert(index, val.serialize(ValueSerializer)?);
-
-                                        Ok(self)
-                                    }
-                                },
-                                None => Err(Error::custom(format!("invalid index '{}'", index))),
+                    None if index == len => {
+                        let item = match key.peek() {
+                            Some(_) => {
+                                let mut item = Value::entry();
+
+                                item.splice(key, value)?;
+                                item
                             }
-                        }
+                            None => value,
+                        };
+
+                        self.0.push(item);
+
+                        Ok(())
                     }
+                    None => Err(Error::custom(format!("invalid index '{}'", index))),
                 },
                 Err(_) => Err(Error::custom(format!("invalid key '{}'", head))),
             },
@@ -111,12 +112,6 @@ impl Array {
     }
 }
 
-impl Default for Array {
-    fn default() -> Self {
-        Self(Vec::new())
-    }
-}
-
 impl From<Vec<Value>> for Array {
     fn from(vec: Vec<Value>) -> Self {
         Self(vec)
@@ -163,7 +158,7 @@ impl<'de> Deserialize<'de> for Array {
             where
                 V: SeqAccess<'de>,
             {
-                let mut vec = Vec::new();
+                let mut vec = Vec::with_capacity(capacity_hint(visitor.size_hint()));
 
                 while let Some(elem) = visitor.next_element()? {
                     vec.push(elem);
@@ -212,19 +207,24 @@ mod tests {
     fn test_array() {
         let mut array = Array::new();
 
-        assert!(array.set(0 as usize, "joe.bloggs").is_ok());
-        assert!(array.set(1 as usize, "hunter2").is_ok());
-        assert!(array.set(2 as usize, "42").is_ok());
+        assert!(array.set(0_usize, "joe.bloggs").is_ok());
+        assert!(array.set(1_usize, "hunter2").is_ok());
+        assert!(array.set(2_usize, "42").is_ok());
 
         assert_eq!(
-            array.get::<_, String>(0 as usize),
+            array.get::<_, String>(0_usize),
             Ok(String::from("joe.bloggs"))
         );
-        assert_eq!(
-            array.get::<_, String>(1 as usize),
-            Ok(String::from("hunter2"))
-        );
-        assert_eq!(array.get::<_, String>(2 as usize), Ok(String::from("42")));
-        assert_eq!(array.get::<_, i32>(2 as usize), Ok(42));
+        assert_eq!(array.get::<_, String>(1_usize), Ok(String::from("hunter2")));
+        assert_eq!(array.get::<_, String>(2_usize), Ok(String::from("42")));
+        assert_eq!(array.get::<_, i32>(2_usize), Ok(42));
+    }
+
+    #[test]
+    fn test_array_capacity() {
+        let array = Array::with_capacity(16);
+
+        assert!(array.is_empty());
+        assert!(array.0.capacity() >= 16);
     }
 }
@@ -1,54 +1,126 @@
+use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt::{self, Display};
+use std::iter::Enumerate;
+use std::{slice, vec};
 
-use serde::de::value::{MapDeserializer, SeqDeserializer};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use indexmap::map;
 use serde::de::{
     Deserialize, DeserializeSeed, Deserializer, EnumAccess, Error as DeError, IntoDeserializer,
-    Unexpected, VariantAccess, Visitor,
+    MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
 };
 use serde::forward_to_deserialize_any;
 
-use super::{Array, Entry, Table, Value};
+use super::{Array, Entry, Kind, Table, TableKey, Value};
+use crate::interpolate::Interpolator;
 
-pub struct ValueDeserializer<'de>(&'de Value);
+#[derive(Clone, Copy)]
+pub struct ValueDeserializer<'de>(&'de Value, Option<(&'de Interpolator, &'de Table)>);
 
 impl<'de> ValueDeserializer<'de> {
     pub fn new(value: &'de Value) -> Self {
-        Self(value)
+        Self(value, None)
+    }
+
+    pub(crate) fn with_interpolator(
+        value: &'de Value,
+        interpolator: &'de Interpolator,
+        table: &'de Table,
+    ) -> Self {
+        Self(value, Some((interpolator, table)))
+    }
+
+    fn child(&self, value: &'de Value) -> Self {
+        Self(value, self.1)
+    }
+
+    fn text(&self, entry: &'de Entry) -> Result<Cow<'de, str>, Error> {
+        match self.1 {
+            Some((interpolator, table)) => {
+                interpolator.resolve(&entry.0, table).map_err(Error::custom)
+            }
+            None => Ok(Cow::Borrowed(&entry.0)),
+        }
     }
 
     pub fn deserialize_entry<V>(self, entry: &'de Entry, visitor: V) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_str(&entry.0)
+        match self.text(entry)? {
+            Cow::Borrowed(text) => visitor.visit_borrowed_str(text),
+            Cow::Owned(text) => visitor.visit_string(text),
+        }
     }
 
-    pub fn deserialize_array<V>(self, array: &'de Array, visitor: V) -> Result<V::Value, Error>
+    fn deserialize_scalar<V>(self, entry: &'de Entry, visitor: V) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
-        let mut deserializer = SeqDeserializer::new(array.into_iter());
-        let seq = visitor.visit_seq(&mut deserializer)?;
+        match entry.kind() {
+            Kind::Boolean => match self.text(entry)?.parse::<bool>() {
+                Ok(value) => visitor.visit_bool(value),
+                Err(_) => self.deserialize_entry(entry, visitor),
+            },
+            Kind::Integer => {
+                let text = self.text(entry)?;
+
+                if let Ok(value) = text.parse::<i64>() {
+                    visitor.visit_i64(value)
+                } else if let Ok(value) = text.parse::<u64>() {
+                    visitor.visit_u64(value)
+                } else {
+                    self.deserialize_entry(entry, visitor)
+                }
+            }
+            Kind::Float => match self.text(entry)?.parse::<f64>() {
+                Ok(value) => visitor.visit_f64(value),
+                Err(_) => self.deserialize_entry(entry, visitor),
+            },
+            Kind::Null => visitor.visit_none(),
+            _ => self.deserialize_entry(entry, visitor),
+        }
+    }
 
-        deserializer.end()?;
+    pub fn deserialize_array<V>(self, array: &'de Array, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut access = ArrayAccess {
+            iter: array.into_iter().enumerate(),
+            parent: self,
+        };
+        let seq = visitor.visit_seq(&mut access)?;
 
-        Ok(seq)
+        match access.iter.len() {
+            0 => Ok(seq),
+            remaining => Err(Error::invalid_length(
+                array.len() - remaining,
+                &"fewer elements in array",
+            )),
+        }
     }
 
     pub fn deserialize_table<V>(self, table: &'de Table, visitor: V) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
-        let iter = table
-            .into_iter()
-            .map(|(key, value)| (key.to_owned(), value));
-        let mut deserializer = MapDeserializer::new(iter);
-        let map = visitor.visit_map(&mut deserializer)?;
-
-        deserializer.end()?;
+        let mut access = TableAccess {
+            iter: table.0.iter(),
+            value: None,
+            parent: self,
+        };
+        let map = visitor.visit_map(&mut access)?;
 
-        Ok(map)
+        match access.iter.len() {
+            0 => Ok(map),
+            remaining => Err(Error::invalid_length(
+                table.0.len() - remaining,
+                &"fewer elements in map",
+            )),
+        }
     }
 }
 
@@ -60,7 +132,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         V: Visitor<'de>,
     {
         match self.0 {
-            Value::Entry(entry) => self.deserialize_entry(entry, visitor),
+            Value::Entry(entry) => self.deserialize_scalar(entry, visitor),
             Value::Array(array) => self.deserialize_array(array, visitor),
             Value::Table(table) => self.deserialize_table(table, visitor),
         }
@@ -73,7 +145,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as bool")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as bool")),
-            Value::Entry(entry) => match entry.0.parse::<bool>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<bool>() {
                 Ok(value) => visitor.visit_bool(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -87,7 +159,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as i8")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as i8")),
-            Value::Entry(entry) => match entry.0.parse::<i8>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<i8>() {
                 Ok(value) => visitor.visit_i8(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -101,7 +173,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as i16")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as i16")),
-            Value::Entry(entry) => match entry.0.parse::<i16>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<i16>() {
                 Ok(value) => visitor.visit_i16(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -115,7 +187,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as i32")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as i32")),
-            Value::Entry(entry) => match entry.0.parse::<i32>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<i32>() {
                 Ok(value) => visitor.visit_i32(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -129,7 +201,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as i64")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as i64")),
-            Value::Entry(entry) => match entry.0.parse::<i64>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<i64>() {
                 Ok(value) => visitor.visit_i64(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -143,7 +215,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as i128")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as i128")),
-            Value::Entry(entry) => match entry.0.parse::<i128>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<i128>() {
                 Ok(value) => visitor.visit_i128(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -157,7 +229,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as u8")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as u8")),
-            Value::Entry(entry) => match entry.0.parse::<u8>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<u8>() {
                 Ok(value) => visitor.visit_u8(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -171,7 +243,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as u16")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as u16")),
-            Value::Entry(entry) => match entry.0.parse::<u16>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<u16>() {
                 Ok(value) => visitor.visit_u16(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -185,7 +257,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as u32")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as u32")),
-            Value::Entry(entry) => match entry.0.parse::<u32>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<u32>() {
                 Ok(value) => visitor.visit_u32(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -199,7 +271,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as u64")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as u64")),
-            Value::Entry(entry) => match entry.0.parse::<u64>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<u64>() {
                 Ok(value) => visitor.visit_u64(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -213,7 +285,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as u128")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as u128")),
-            Value::Entry(entry) => match entry.0.parse::<u128>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<u128>() {
                 Ok(value) => visitor.visit_u128(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -227,7 +299,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as f32")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as f32")),
-            Value::Entry(entry) => match entry.0.parse::<f32>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<f32>() {
                 Ok(value) => visitor.visit_f32(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -241,7 +313,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as f64")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as f64")),
-            Value::Entry(entry) => match entry.0.parse::<f64>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<f64>() {
                 Ok(value) => visitor.visit_f64(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -255,7 +327,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as char")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as char")),
-            Value::Entry(entry) => match entry.0.parse::<char>() {
+            Value::Entry(entry) => match self.text(entry)?.parse::<char>() {
                 Ok(value) => visitor.visit_char(value),
                 Err(err) => Err(Error::custom(format!("{}", err))),
             },
@@ -269,7 +341,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as str")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as str")),
-            Value::Entry(entry) => visitor.visit_str(&entry.0),
+            Value::Entry(entry) => self.deserialize_entry(entry, visitor),
         }
     }
 
@@ -280,10 +352,91 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         match self.0 {
             Value::Array(_) => Err(Error::custom("cannot deserialize array variant as string")),
             Value::Table(_) => Err(Error::custom("cannot deserialize table variant as string")),
-            Value::Entry(entry) => visitor.visit_str(&entry.0),
+            Value::Entry(entry) => self.deserialize_entry(entry, visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Entry(entry) if entry.is_null() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Array(array) => self.deserialize_array(array, visitor),
+            Value::Table(_) => Err(Error::custom("cannot deserialize table variant as bytes")),
+            Value::Entry(entry) => match STANDARD.decode(self.text(entry)?.as_bytes()) {
+                Ok(value) => visitor.visit_byte_buf(value),
+                Err(err) => Err(Error::custom(format!("{}", err))),
+            },
         }
     }
 
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Entry(entry) if entry.is_null() || entry.0.is_empty() => visitor.visit_unit(),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = match self.0 {
+            Value::Table(table) => self.deserialize_table(table, visitor),
+            Value::Array(array) => self.deserialize_array(array, visitor),
+            Value::Entry(entry) => self.deserialize_scalar(entry, visitor),
+        };
+
+        result.map_err(|err| err.within(name, fields))
+    }
+
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
@@ -294,7 +447,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         V: Visitor<'de>,
     {
         let (variant, value) = match self.0 {
-            Value::Entry(entry) => (&entry.0, None),
+            Value::Entry(entry) => (self.text(entry)?, None),
             Value::Table(table) => {
                 let mut iter = table.into_iter();
 
@@ -315,7 +468,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
                     ));
                 }
 
-                (variant, Some(value))
+                (Cow::Borrowed(variant), Some(self.child(value)))
             }
             other => {
                 return Err(Error::invalid_type(other.unexpected(), &"string or map"));
@@ -325,14 +478,29 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         visitor.visit_enum(EnumDeserializer { variant, value })
     }
 
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
     forward_to_deserialize_any! {
-        bytes byte_buf option unit unit_struct newtype_struct seq tuple
-        tuple_struct map struct identifier ignored_any
+        seq tuple
+        tuple_struct map identifier
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
     }
 }
 
 impl Value {
-    fn unexpected(&self) -> Unexpected {
+    fn unexpected(&self) -> Unexpected<'_> {
         match *self {
             Value::Entry(ref s) => Unexpected::Str(&s.0),
             Value::Array(_) => Unexpected::Seq,
@@ -341,9 +509,164 @@ impl Value {
     }
 }
 
+struct ArrayAccess<'de> {
+    iter: Enumerate<slice::Iter<'de, Value>>,
+    parent: ValueDeserializer<'de>,
+}
+
+impl<'de> SeqAccess<'de> for ArrayAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((index, value)) => seed
+                .deserialize(self.parent.child(value))
+                .map(Some)
+                .map_err(|err| err.at(index)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct TableAccess<'de> {
+    iter: map::Iter<'de, TableKey, Value>,
+    value: Option<(&'de str, &'de Value)>,
+    parent: ValueDeserializer<'de>,
+}
+
+impl<'de> MapAccess<'de> for TableAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+
+                seed.deserialize(KeyDeserializer(Cow::Borrowed(key)))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+
+        seed.deserialize(self.parent.child(value))
+            .map_err(|err| err.at(key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+macro_rules! deserialize_key {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.0.parse::<$ty>() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(err) => Err(Error::custom(format!("invalid key '{}': {}", self.0, err))),
+                }
+            }
+        )*
+    };
+}
+
+struct KeyDeserializer<'de>(Cow<'de, str>);
+
+impl<'de> Deserializer<'de> for KeyDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Cow::Borrowed(key) => visitor.visit_borrowed_str(key),
+            Cow::Owned(key) => visitor.visit_string(key),
+        }
+    }
+
+    deserialize_key! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(EnumDeserializer {
+            variant: self.0,
+            value: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
 struct EnumDeserializer<'de> {
-    variant: &'de str,
-    value: Option<&'de Value>,
+    variant: Cow<'de, str>,
+    value: Option<ValueDeserializer<'de>>,
 }
 
 impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
@@ -354,14 +677,18 @@ impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        let variant = self.variant.into_deserializer();
         let visitor = VariantDeserializer { value: self.value };
-        seed.deserialize(variant).map(|v| (v, visitor))
+        let variant = match self.variant {
+            Cow::Borrowed(variant) => seed.deserialize(variant.into_deserializer()),
+            Cow::Owned(variant) => seed.deserialize(variant.into_deserializer()),
+        };
+
+        variant.map(|v| (v, visitor))
     }
 }
 
 struct VariantDeserializer<'de> {
-    value: Option<&'de Value>,
+    value: Option<ValueDeserializer<'de>>,
 }
 
 impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
@@ -369,7 +696,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
 
     fn unit_variant(self) -> Result<(), Error> {
         match self.value {
-            Some(value) => Deserialize::deserialize(ValueDeserializer::new(value)),
+            Some(value) => Deserialize::deserialize(value),
             None => Ok(()),
         }
     }
@@ -379,7 +706,7 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
         T: DeserializeSeed<'de>,
     {
         match self.value {
-            Some(value) => seed.deserialize(ValueDeserializer::new(value)),
+            Some(value) => seed.deserialize(value),
             None => Err(Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"newtype variant",
@@ -392,10 +719,10 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Some(Value::Array(array)) => {
-                Deserializer::deserialize_any(SeqDeserializer::new(array.into_iter()), visitor)
+            Some(value @ ValueDeserializer(Value::Array(array), _)) => {
+                value.deserialize_array(array, visitor)
             }
-            Some(other) => Err(Error::invalid_type(other.unexpected(), &"tuple variant")),
+            Some(other) => Err(Error::invalid_type(other.0.unexpected(), &"tuple variant")),
             None => Err(Error::invalid_type(
                 Unexpected::UnitVariant,
                 &"tuple variant",
@@ -412,13 +739,414 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Some(Value::Table(table)) => {
-                let iter = table
-                    .into_iter()
-                    .map(|(key, value)| (key.to_owned(), value));
+            Some(value @ ValueDeserializer(Value::Table(table), _)) => {
+                value.deserialize_table(table, visitor)
+            }
+            Some(other) => Err(Error::invalid_type(other.0.unexpected(), &"struct variant")),
+            _ => Err(Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+macro_rules! deserialize_owned {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self {
+                    Value::Array(_) => Err(Error::custom(concat!(
+                        "cannot deserialize array variant as ",
+                        stringify!($ty)
+                    ))),
+                    Value::Table(_) => Err(Error::custom(concat!(
+                        "cannot deserialize table variant as ",
+                        stringify!($ty)
+                    ))),
+                    Value::Entry(entry) => match entry.0.parse::<$ty>() {
+                        Ok(value) => visitor.$visit(value),
+                        Err(err) => Err(Error::custom(format!("{}", err))),
+                    },
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Entry(entry) => visit_entry(entry, visitor),
+            Value::Array(array) => visit_array(array, visitor),
+            Value::Table(table) => visit_table(table, visitor),
+        }
+    }
+
+    deserialize_owned! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Array(_) => Err(Error::custom("cannot deserialize array variant as string")),
+            Value::Table(_) => Err(Error::custom("cannot deserialize table variant as string")),
+            Value::Entry(entry) => visitor.visit_string(entry.0),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Entry(entry) if entry.is_null() => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Array(array) => visit_array(array, visitor),
+            Value::Table(_) => Err(Error::custom("cannot deserialize table variant as bytes")),
+            Value::Entry(entry) => visitor.visit_byte_buf(entry.to_bytes().map_err(Error::custom)?),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Entry(entry) if entry.is_null() || entry.0.is_empty() => visitor.visit_unit(),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+            .map_err(|err| err.within(name, fields))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (variant, value) = match self {
+            Value::Entry(entry) => (TableKey::from(entry.0), None),
+            Value::Table(table) => {
+                let mut iter = table.0.into_iter();
+
+                match (iter.next(), iter.next()) {
+                    (Some((variant, value)), None) => (variant, Some(value)),
+                    _ => {
+                        return Err(Error::invalid_value(
+                            Unexpected::Map,
+                            &"map with a single key",
+                        ));
+                    }
+                }
+            }
+            other => {
+                return Err(Error::invalid_type(other.unexpected(), &"string or map"));
+            }
+        };
+
+        visitor.visit_enum(OwnedEnumDeserializer { variant, value })
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        seq tuple
+        tuple_struct map identifier
+    }
+}
+
+fn visit_entry<'de, V>(entry: Entry, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    match entry.kind() {
+        Kind::Boolean => match entry.0.parse::<bool>() {
+            Ok(value) => visitor.visit_bool(value),
+            Err(_) => visitor.visit_string(entry.0),
+        },
+        Kind::Integer => {
+            if let Ok(value) = entry.0.parse::<i64>() {
+                visitor.visit_i64(value)
+            } else if let Ok(value) = entry.0.parse::<u64>() {
+                visitor.visit_u64(value)
+            } else {
+                visitor.visit_string(entry.0)
+            }
+        }
+        Kind::Float => match entry.0.parse::<f64>() {
+            Ok(value) => visitor.visit_f64(value),
+            Err(_) => visitor.visit_string(entry.0),
+        },
+        Kind::Null => visitor.visit_none(),
+        _ => visitor.visit_string(entry.0),
+    }
+}
+
+fn visit_array<'de, V>(array: Array, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = array.len();
+    let mut access = OwnedArrayAccess {
+        iter: array.into_iter().enumerate(),
+    };
+    let seq = visitor.visit_seq(&mut access)?;
+
+    match access.iter.len() {
+        0 => Ok(seq),
+        remaining => Err(Error::invalid_length(
+            len - remaining,
+            &"fewer elements in array",
+        )),
+    }
+}
+
+fn visit_table<'de, V>(table: Table, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = table.0.len();
+    let mut access = OwnedTableAccess {
+        iter: table.0.into_iter(),
+        value: None,
+    };
+    let map = visitor.visit_map(&mut access)?;
+
+    match access.iter.len() {
+        0 => Ok(map),
+        remaining => Err(Error::invalid_length(
+            len - remaining,
+            &"fewer elements in map",
+        )),
+    }
+}
+
+struct OwnedArrayAccess {
+    iter: Enumerate<vec::IntoIter<Value>>,
+}
+
+impl<'de> SeqAccess<'de> for OwnedArrayAccess {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((index, value)) => seed
+                .deserialize(value)
+                .map(Some)
+                .map_err(|err| err.at(index)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct OwnedTableAccess {
+    iter: map::IntoIter<TableKey, Value>,
+    value: Option<(TableKey, Value)>,
+}
+
+impl<'de> MapAccess<'de> for OwnedTableAccess {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                let deserializer = KeyDeserializer(Cow::Owned(String::from(&*key)));
 
-                Deserializer::deserialize_any(MapDeserializer::new(iter), visitor)
+                self.value = Some((key, value));
+
+                seed.deserialize(deserializer).map(Some)
             }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+
+        seed.deserialize(value).map_err(|err| err.at(key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct OwnedEnumDeserializer {
+    variant: TableKey,
+    value: Option<Value>,
+}
+
+impl<'de> EnumAccess<'de> for OwnedEnumDeserializer {
+    type Error = Error;
+    type Variant = OwnedVariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let visitor = OwnedVariantDeserializer { value: self.value };
+
+        seed.deserialize((&*self.variant).into_deserializer())
+            .map(|v| (v, visitor))
+    }
+}
+
+struct OwnedVariantDeserializer {
+    value: Option<Value>,
+}
+
+impl<'de> VariantAccess<'de> for OwnedVariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            Some(value) => Deserialize::deserialize(value),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Value::Array(array)) => visit_array(array, visitor),
+            Some(other) => Err(Error::invalid_type(other.unexpected(), &"tuple variant")),
+            None => Err(Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Value::Table(table)) => visit_table(table, visitor),
             Some(other) => Err(Error::invalid_type(other.unexpected(), &"struct variant")),
             _ => Err(Error::invalid_type(
                 Unexpected::UnitVariant,
@@ -429,11 +1157,43 @@ impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Error(String);
+pub struct Error(pub(crate) String, pub(crate) Option<String>);
+
+impl Error {
+    pub fn path(&self) -> Option<&str> {
+        self.1.as_deref()
+    }
+
+    pub(crate) fn at<T: Display>(mut self, segment: T) -> Self {
+        self.1 = Some(match self.1 {
+            Some(path) => format!("{}.{}", segment, path),
+            None => segment.to_string(),
+        });
+        self
+    }
+
+    fn within(self, name: &str, fields: &[&str]) -> Self {
+        if self.1.is_some() {
+            self
+        } else if self.0.starts_with("missing field") {
+            Error::custom(format!(
+                "{}: {} (expected fields: {})",
+                name,
+                self,
+                fields.join(", ")
+            ))
+        } else {
+            Error::custom(format!("{}: {}", name, self))
+        }
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        match &self.1 {
+            Some(path) => write!(f, "{}: {}", path, self.0),
+            None => self.0.fmt(f),
+        }
     }
 }
 
@@ -441,6 +1201,6 @@ impl StdError for Error {}
 
 impl DeError for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        Self(msg.to_string())
+        Self(msg.to_string(), None)
     }
 }
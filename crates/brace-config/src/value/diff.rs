@@ -0,0 +1,140 @@
+use super::{Table, Value};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl Diff {
+    pub fn new(old: &Table, new: &Table) -> Self {
+        let mut diff = Self::default();
+
+        diff_table(&mut diff, old, new, "");
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.added
+            .iter()
+            .chain(&self.removed)
+            .chain(&self.changed)
+            .map(String::as_str)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys().any(|path| path == key)
+    }
+
+    pub fn filter<F>(&self, mut func: F) -> Self
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let mut keep = |paths: &[String]| {
+            paths
+                .iter()
+                .filter(|path| func(path))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            added: keep(&self.added),
+            removed: keep(&self.removed),
+            changed: keep(&self.changed),
+        }
+    }
+}
+
+pub(crate) fn leaves(value: &Value, path: &str, out: &mut Vec<String>) {
+    match value {
+        Value::Table(table) if !table.0.is_empty() => {
+            for (key, value) in table {
+                leaves(value, &join(path, key), out);
+            }
+        }
+        _ => out.push(path.to_owned()),
+    }
+}
+
+fn diff_table(diff: &mut Diff, old: &Table, new: &Table, prefix: &str) {
+    for (key, before) in old {
+        let path = join(prefix, key);
+
+        match new.0.get(key) {
+            None => leaves(before, &path, &mut diff.removed),
+            Some(Value::Table(after)) if before.is_table() => {
+                if let Value::Table(before) = before {
+                    diff_table(diff, before, after, &path);
+                }
+            }
+            Some(after) if after == before => {}
+            Some(after) if after.is_table() || before.is_table() => {
+                leaves(before, &path, &mut diff.removed);
+                leaves(after, &path, &mut diff.added);
+            }
+            Some(_) => diff.changed.push(path),
+        }
+    }
+
+    for (key, after) in new {
+        if !old.0.contains_key(key) {
+            leaves(after, &join(prefix, key), &mut diff.added);
+        }
+    }
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Diff;
+    use crate::table;
+
+    #[test]
+    fn test_diff() {
+        let old = table! {
+            "name" = "app",
+            "server" = { "host" = "localhost", "port" = 80 },
+            "log" = { "level" = "info" },
+            "hosts" = ["a"],
+            "tls" = "off",
+        };
+        let new = table! {
+            "name" = "app",
+            "server" = { "host" = "localhost", "port" = 8080, "workers" = 4 },
+            "hosts" = ["a", "b"],
+            "tls" = { "cert" = "a.pem" },
+            "debug" = true,
+        };
+        let mut diff = Diff::new(&old, &new);
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort();
+
+        assert_eq!(diff.added, vec!["debug", "server.workers", "tls.cert"]);
+        assert_eq!(diff.removed, vec!["log.level", "tls"]);
+        assert_eq!(diff.changed, vec!["hosts", "server.port"]);
+        assert!(diff.contains("tls"));
+        assert!(!diff.contains("name"));
+        assert!(Diff::new(&new, &new).is_empty());
+
+        let server = diff.filter(|path| path.starts_with("server."));
+
+        assert_eq!(server.added, vec!["server.workers"]);
+        assert!(server.removed.is_empty());
+        assert_eq!(server.changed, vec!["server.port"]);
+    }
+}
@@ -1,120 +1,177 @@
 use std::fmt;
 
-use serde::de::{Deserialize, Deserializer, Visitor};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Entry(pub(crate) String);
+use super::Error;
+
+pub(crate) const DATETIME_NAME: &str = "$__brace_config_datetime";
+pub(crate) const TOML_DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Kind {
+    #[default]
+    String,
+    Datetime,
+    Null,
+    Bytes,
+    Boolean,
+    Integer,
+    Float,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Entry(pub(crate) String, pub(crate) Kind);
 
 impl Entry {
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn datetime<T>(value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Entry(value.into(), Kind::Datetime)
+    }
+
+    pub fn null() -> Self {
+        Entry(String::new(), Kind::Null)
+    }
+
+    pub fn bytes<T>(value: T) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        Entry(STANDARD.encode(value), Kind::Bytes)
+    }
+
     pub fn value(&self) -> &str {
         &self.0
     }
-}
 
-impl Default for Entry {
-    fn default() -> Self {
-        Self(String::new())
+    pub fn kind(&self) -> Kind {
+        self.1
+    }
+
+    pub fn is_datetime(&self) -> bool {
+        self.1 == Kind::Datetime
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.1 == Kind::Null
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        self.1 == Kind::Bytes
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        STANDARD.decode(&self.0).map_err(Error::custom)
     }
 }
 
 impl From<bool> for Entry {
     fn from(value: bool) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Boolean)
     }
 }
 
 impl From<i8> for Entry {
     fn from(value: i8) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<i16> for Entry {
     fn from(value: i16) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<i32> for Entry {
     fn from(value: i32) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<i64> for Entry {
     fn from(value: i64) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<i128> for Entry {
     fn from(value: i128) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<u8> for Entry {
     fn from(value: u8) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<u16> for Entry {
     fn from(value: u16) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<u32> for Entry {
     fn from(value: u32) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<u64> for Entry {
     fn from(value: u64) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<u128> for Entry {
     fn from(value: u128) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Integer)
     }
 }
 
 impl From<f32> for Entry {
     fn from(value: f32) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Float)
     }
 }
 
 impl From<f64> for Entry {
     fn from(value: f64) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::Float)
     }
 }
 
 impl From<char> for Entry {
     fn from(value: char) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::String)
     }
 }
 
 impl From<&str> for Entry {
     fn from(value: &str) -> Self {
-        Entry(value.to_string())
+        Entry(value.to_string(), Kind::String)
     }
 }
 
 impl From<String> for Entry {
     fn from(value: String) -> Self {
-        Entry(value)
+        Entry(value, Kind::String)
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
 
@@ -123,7 +180,32 @@ impl Serialize for Entry {
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.0)
+        match self.1 {
+            Kind::String | Kind::Bytes => serializer.serialize_str(&self.0),
+            Kind::Datetime => serializer.serialize_newtype_struct(DATETIME_NAME, &self.0),
+            Kind::Null => serializer.serialize_none(),
+            Kind::Boolean => match self.0.parse::<bool>() {
+                Ok(value) => serializer.serialize_bool(value),
+                Err(_) => serializer.serialize_str(&self.0),
+            },
+            Kind::Integer => {
+                if let Ok(value) = self.0.parse::<i64>() {
+                    serializer.serialize_i64(value)
+                } else if let Ok(value) = self.0.parse::<u64>() {
+                    serializer.serialize_u64(value)
+                } else if let Ok(value) = self.0.parse::<i128>() {
+                    serializer.serialize_i128(value)
+                } else if let Ok(value) = self.0.parse::<u128>() {
+                    serializer.serialize_u128(value)
+                } else {
+                    serializer.serialize_str(&self.0)
+                }
+            }
+            Kind::Float => match self.0.parse::<f64>() {
+                Ok(value) => serializer.serialize_f64(value),
+                Err(_) => serializer.serialize_str(&self.0),
+            },
+        }
     }
 }
 
@@ -181,14 +263,45 @@ impl<'de> Deserialize<'de> for Entry {
                 Ok(Entry::from(value))
             }
 
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Ok(Entry::bytes(value))
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                Ok(Entry::null())
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(Entry::null())
+            }
+
             fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
             where
                 D: Deserializer<'de>,
             {
                 Deserialize::deserialize(deserializer)
             }
+
+            fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                visit_datetime(visitor)
+            }
         }
 
         deserializer.deserialize_any(EntryVisitor)
     }
 }
+
+pub(crate) fn visit_datetime<'de, V>(mut visitor: V) -> Result<Entry, V::Error>
+where
+    V: MapAccess<'de>,
+{
+    match visitor.next_key::<String>()? {
+        Some(key) if key == TOML_DATETIME_FIELD => {
+            Ok(Entry::datetime(visitor.next_value::<String>()?))
+        }
+        _ => Err(serde::de::Error::custom("expected a datetime")),
+    }
+}
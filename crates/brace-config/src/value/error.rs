@@ -2,20 +2,27 @@ use std::error::Error as StdError;
 use std::fmt::{self, Debug, Display};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Error(String);
+pub struct Error(String, Option<String>);
 
 impl Error {
     pub fn custom<T>(msg: T) -> Self
     where
         T: Display,
     {
-        Self(msg.to_string())
+        Self(msg.to_string(), None)
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.1.as_deref()
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        match &self.1 {
+            Some(path) => write!(f, "{}: {}", path, self.0),
+            None => write!(f, "{}", self.0),
+        }
     }
 }
 
@@ -23,12 +30,12 @@ impl StdError for Error {}
 
 impl From<super::ser::Error> for Error {
     fn from(from: super::ser::Error) -> Self {
-        Self::custom(from)
+        Self(from.0, from.1)
     }
 }
 
 impl From<super::de::Error> for Error {
     fn from(from: super::de::Error) -> Self {
-        Self::custom(from)
+        Self(from.0, from.1)
     }
 }
@@ -0,0 +1,148 @@
+use std::ops;
+
+use super::{intern, Array, Entry, Kind, Table, Value};
+
+static NULL: Value = Value::Entry(Entry(String::new(), Kind::Null));
+
+pub trait Index: private::Sealed {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value>;
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> &'v mut Value;
+}
+
+impl Index for usize {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Array(array) => array.0.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        match value {
+            Value::Array(array) => &mut array[*self],
+            _ => panic!("cannot index into {} with an index", kind(value)),
+        }
+    }
+}
+
+impl Index for str {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Table(table) => table.0.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        if matches!(value, Value::Entry(entry) if entry.is_null()) {
+            *value = Value::Table(Table::new());
+        }
+
+        match value {
+            Value::Table(table) => &mut table[self],
+            _ => panic!("cannot index into {} with a key", kind(value)),
+        }
+    }
+}
+
+impl Index for String {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        self.as_str().index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        self.as_str().index_into_mut(value)
+    }
+}
+
+impl<T> Index for &T
+where
+    T: ?Sized + Index,
+{
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        (**self).index_into_mut(value)
+    }
+}
+
+impl<I> ops::Index<I> for Value
+where
+    I: Index,
+{
+    type Output = Value;
+
+    fn index(&self, index: I) -> &Value {
+        index.index_into(self).unwrap_or(&NULL)
+    }
+}
+
+impl<I> ops::IndexMut<I> for Value
+where
+    I: Index,
+{
+    fn index_mut(&mut self, index: I) -> &mut Value {
+        index.index_into_mut(self)
+    }
+}
+
+impl<I> ops::Index<I> for Table
+where
+    I: AsRef<str>,
+{
+    type Output = Value;
+
+    fn index(&self, index: I) -> &Value {
+        self.0.get(index.as_ref()).unwrap_or(&NULL)
+    }
+}
+
+impl<I> ops::IndexMut<I> for Table
+where
+    I: AsRef<str>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Value {
+        self.0
+            .entry(intern(index.as_ref()))
+            .or_insert_with(|| NULL.clone())
+    }
+}
+
+impl ops::Index<usize> for Array {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        self.0.get(index).unwrap_or(&NULL)
+    }
+}
+
+impl ops::IndexMut<usize> for Array {
+    fn index_mut(&mut self, index: usize) -> &mut Value {
+        let len = self.0.len();
+
+        match self.0.get_mut(index) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for array of length {}", index, len),
+        }
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Entry(_) => "an entry",
+        Value::Array(_) => "an array",
+        Value::Table(_) => "a table",
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for usize {}
+    impl Sealed for str {}
+    impl Sealed for String {}
+    impl<T> Sealed for &T where T: ?Sized + Sealed {}
+}
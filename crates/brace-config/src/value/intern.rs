@@ -0,0 +1,138 @@
+#[cfg(feature = "intern")]
+pub use self::imp::release_interned;
+pub(crate) use self::imp::{intern, resolve, TableKey};
+
+#[cfg(feature = "intern")]
+mod imp {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+    use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+    pub(crate) type TableKey = Arc<str>;
+
+    const SHARDS: usize = 16;
+
+    const MIN_LIMIT: usize = 64;
+
+    static KEYS: OnceLock<[Mutex<Shard>; SHARDS]> = OnceLock::new();
+
+    #[derive(Default)]
+    pub(super) struct Shard {
+        pub(super) keys: HashSet<Arc<str>>,
+        limit: usize,
+    }
+
+    impl Shard {
+        fn release(&mut self) {
+            self.keys.retain(|key| Arc::strong_count(key) > 1);
+            self.limit = (self.keys.len() * 2).max(MIN_LIMIT);
+        }
+    }
+
+    pub(super) fn shards() -> &'static [Mutex<Shard>; SHARDS] {
+        KEYS.get_or_init(|| std::array::from_fn(|_| Mutex::default()))
+    }
+
+    fn shard(key: &str) -> MutexGuard<'static, Shard> {
+        let mut hasher = DefaultHasher::new();
+
+        key.hash(&mut hasher);
+
+        shards()[hasher.finish() as usize % SHARDS]
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(crate) fn intern<K>(key: K) -> TableKey
+    where
+        K: AsRef<str> + Into<TableKey>,
+    {
+        let mut shard = shard(key.as_ref());
+
+        if let Some(interned) = shard.keys.get(key.as_ref()) {
+            return Arc::clone(interned);
+        }
+
+        if shard.keys.len() >= shard.limit {
+            shard.release();
+        }
+
+        let interned = key.into();
+
+        shard.keys.insert(Arc::clone(&interned));
+        interned
+    }
+
+    pub(crate) fn resolve(key: TableKey) -> String {
+        String::from(&*key)
+    }
+
+    pub fn release_interned() {
+        if let Some(shards) = KEYS.get() {
+            for shard in shards {
+                shard
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .release();
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "intern"))]
+mod imp {
+    pub(crate) type TableKey = String;
+
+    pub(crate) fn intern<K>(key: K) -> TableKey
+    where
+        K: AsRef<str> + Into<TableKey>,
+    {
+        key.into()
+    }
+
+    pub(crate) fn resolve(key: TableKey) -> String {
+        key
+    }
+}
+
+#[cfg(all(test, feature = "intern"))]
+mod tests {
+    use std::sync::Arc;
+
+    use super::imp::shards;
+    use super::{intern, release_interned};
+
+    #[test]
+    fn test_intern() {
+        let first = intern("interned.key");
+        let second = intern(String::from("interned.key"));
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(&*first, "interned.key");
+
+        drop(second);
+        release_interned();
+
+        assert!(Arc::ptr_eq(&first, &intern("interned.key")));
+
+        for index in 0..10_000 {
+            intern(format!("released.{}", index));
+        }
+
+        let retained = shards()
+            .iter()
+            .map(|shard| {
+                let shard = shard.lock().unwrap();
+
+                shard
+                    .keys
+                    .iter()
+                    .filter(|key| key.starts_with("released."))
+                    .count()
+            })
+            .sum::<usize>();
+
+        assert!(retained < 10_000 / 4);
+    }
+}
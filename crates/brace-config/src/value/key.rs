@@ -2,11 +2,23 @@ use std::collections::VecDeque;
 use std::iter::Iterator;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct Key(VecDeque<String>);
+pub struct Key(pub(crate) VecDeque<String>);
 
 impl Key {
     pub fn peek(&self) -> Option<&str> {
-        self.0.get(0).map(AsRef::as_ref)
+        self.0.front().map(AsRef::as_ref)
+    }
+
+    pub(crate) fn segment(&self, index: usize) -> Option<&str> {
+        self.0.get(index).map(AsRef::as_ref)
+    }
+
+    pub(crate) fn path(&self) -> String {
+        self.0
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(".")
     }
 }
 
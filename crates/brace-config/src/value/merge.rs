@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use super::{Array, Table, Value};
+
+pub const DELETE_MARKER: &str = "~delete";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ArrayMerge {
+    #[default]
+    Replace,
+    Append,
+    Index,
+    Key(String),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TableMerge {
+    #[default]
+    Deep,
+    Replace,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeOptions {
+    pub(crate) arrays: ArrayMerge,
+    pub(crate) paths: HashMap<String, ArrayMerge>,
+    pub(crate) tables: HashMap<String, TableMerge>,
+    pub(crate) deletions: bool,
+}
+
+impl MergeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn deletions(mut self, deletions: bool) -> Self {
+        self.deletions = deletions;
+        self
+    }
+
+    pub fn arrays(mut self, arrays: ArrayMerge) -> Self {
+        self.arrays = arrays;
+        self
+    }
+
+    pub fn strategy<K>(mut self, path: K, strategy: ArrayMerge) -> Self
+    where
+        K: Into<String>,
+    {
+        self.paths.insert(path.into(), strategy);
+        self
+    }
+
+    pub fn table_strategy<K>(mut self, path: K, strategy: TableMerge) -> Self
+    where
+        K: Into<String>,
+    {
+        self.tables.insert(path.into(), strategy);
+        self
+    }
+
+    fn strategy_for(&self, path: &str) -> Option<&ArrayMerge> {
+        self.paths.get(path)
+    }
+
+    fn table_strategy_for(&self, path: &str) -> Option<&TableMerge> {
+        self.tables.get(path)
+    }
+
+    fn is_deletion(&self, value: &Value) -> bool {
+        match value {
+            Value::Entry(entry) => self.deletions && entry.value() == DELETE_MARKER,
+            _ => false,
+        }
+    }
+}
+
+pub(crate) fn merge_table(table: &mut Table, other: Table, options: &MergeOptions) {
+    merge_table_at(table, other, options, "");
+}
+
+fn merge_table_at(table: &mut Table, other: Table, options: &MergeOptions, path: &str) {
+    for (key, val) in other.0 {
+        let path = join(path, &key);
+
+        if options.is_deletion(&val) {
+            table.0.shift_remove(&*key);
+            continue;
+        }
+
+        match table.0.get_mut(&*key) {
+            Some(existing) => merge_value(existing, val, options, &path),
+            None => match val {
+                Value::Table(other) => {
+                    let mut inner = Table::new();
+
+                    merge_table_at(&mut inner, other, options, &path);
+                    table.0.insert(key, Value::Table(inner));
+                }
+                val => {
+                    table.0.insert(key, val);
+                }
+            },
+        }
+    }
+}
+
+fn merge_value(value: &mut Value, other: Value, options: &MergeOptions, path: &str) {
+    match (value, other) {
+        (Value::Table(table), Value::Table(other)) => match options.table_strategy_for(path) {
+            Some(TableMerge::Replace) => *table = strip(other, options),
+            _ => merge_table_at(table, other, options, path),
+        },
+        (Value::Array(array), Value::Array(other)) => merge_array(array, other, options, path),
+        (value, other) => *value = other,
+    }
+}
+
+fn strip(table: Table, options: &MergeOptions) -> Table {
+    if !options.deletions {
+        return table;
+    }
+
+    let mut inner = Table::new();
+
+    merge_table_at(&mut inner, table, options, "");
+    inner
+}
+
+fn merge_array(array: &mut Array, other: Array, options: &MergeOptions, path: &str) {
+    match options.strategy_for(path).unwrap_or(&options.arrays) {
+        ArrayMerge::Replace => *array = other,
+        ArrayMerge::Append => array.0.extend(other),
+        ArrayMerge::Index => {
+            for (index, val) in other.into_iter().enumerate() {
+                let path = join(path, &index.to_string());
+
+                match array.0.get_mut(index) {
+                    Some(existing) => merge_value(existing, val, options, &path),
+                    None => array.0.push(val),
+                }
+            }
+        }
+        ArrayMerge::Key(field) => {
+            for val in other {
+                let existing = identity(&val, field).and_then(|id| {
+                    array
+                        .0
+                        .iter_mut()
+                        .enumerate()
+                        .find(|(_, item)| identity(item, field) == Some(id))
+                });
+
+                match existing {
+                    Some((index, existing)) => {
+                        merge_value(existing, val, options, &join(path, &index.to_string()))
+                    }
+                    None => array.0.push(val),
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn conflicts(table: &Table, other: &Table) -> Vec<String> {
+    let mut keys = Vec::new();
+
+    collect_conflicts(table, other, "", &mut keys);
+
+    keys
+}
+
+fn collect_conflicts(table: &Table, other: &Table, path: &str, keys: &mut Vec<String>) {
+    for (key, val) in &other.0 {
+        let path = join(path, key);
+
+        match (table.0.get(key), val) {
+            (Some(Value::Table(table)), Value::Table(other)) => {
+                collect_conflicts(table, other, &path, keys)
+            }
+            (Some(existing), val) if existing != val => keys.push(path),
+            _ => {}
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn identity<'a>(value: &'a Value, field: &str) -> Option<&'a str> {
+    match value {
+        Value::Table(table) => match table.0.get(field) {
+            Some(Value::Entry(entry)) => Some(entry.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{conflicts, ArrayMerge, MergeOptions, Table, TableMerge};
+    use crate::table;
+
+    #[test]
+    fn test_merge_arrays() {
+        let base = table! {
+            "list" = ["a", "b"],
+            "servers" = [
+                { "name" = "one", "port" = 80 },
+                { "name" = "two", "port" = 81 },
+            ],
+        };
+        let other = table! {
+            "list" = ["c"],
+            "servers" = [
+                { "name" = "two", "port" = 8081 },
+                { "name" = "three", "port" = 82 },
+            ],
+        };
+
+        let mut table = base.clone();
+
+        table.merge(other.clone());
+
+        assert_eq!(table.get("list"), Ok(vec![String::from("c")]));
+        assert_eq!(table.get("servers.0.name"), Ok(String::from("two")));
+
+        let mut table = base.clone();
+
+        table.merge_with(
+            other.clone(),
+            &MergeOptions::new().arrays(ArrayMerge::Append),
+        );
+
+        assert_eq!(
+            table.get("list"),
+            Ok(vec![
+                String::from("a"),
+                String::from("b"),
+                String::from("c")
+            ])
+        );
+        assert_eq!(table.get("servers.3.name"), Ok(String::from("three")));
+
+        let mut table = base.clone();
+
+        table.merge_with(
+            other.clone(),
+            &MergeOptions::new().arrays(ArrayMerge::Index),
+        );
+
+        assert_eq!(
+            table.get("list"),
+            Ok(vec![String::from("c"), String::from("b")])
+        );
+        assert_eq!(table.get("servers.0.name"), Ok(String::from("two")));
+        assert_eq!(table.get("servers.0.port"), Ok(8081));
+        assert_eq!(table.get("servers.1.name"), Ok(String::from("three")));
+
+        let mut table = base;
+        let options = MergeOptions::new().arrays(ArrayMerge::Key(String::from("name")));
+
+        table.merge_with(other, &options);
+
+        assert_eq!(table.get("servers.0.port"), Ok(80));
+        assert_eq!(table.get("servers.1.name"), Ok(String::from("two")));
+        assert_eq!(table.get("servers.1.port"), Ok(8081));
+        assert_eq!(table.get("servers.2.name"), Ok(String::from("three")));
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let table = table! {
+            "name" = "app",
+            "server" = { "host" = "localhost", "port" = 80 },
+        };
+        let other = table! {
+            "name" = "app",
+            "server" = { "port" = 8080, "tls" = true },
+        };
+
+        assert_eq!(conflicts(&table, &other), vec![String::from("server.port")]);
+        assert!(conflicts(&table, &table).is_empty());
+        assert!(conflicts(&table, &table! { "server" = { "port" = "80" } }).is_empty());
+    }
+
+    #[test]
+    fn test_merge_deletions() {
+        let base = table! {
+            "name" = "app",
+            "server" = { "host" = "localhost", "port" = 80 },
+        };
+        let other = table! {
+            "name" = "~delete",
+            "server" = { "port" = "~delete" },
+            "extra" = { "key" = "~delete", "other" = "value" },
+        };
+
+        let mut table = base.clone();
+
+        table.merge(other.clone());
+
+        assert_eq!(table.get("name"), Ok(String::from("~delete")));
+
+        let mut table = base.clone();
+
+        table.merge_with(other.clone(), &MergeOptions::new().deletions(true));
+
+        assert!(table.get::<_, String>("name").is_err());
+        assert!(table.get::<_, String>("server.port").is_err());
+        assert!(table.get::<_, String>("extra.key").is_err());
+        assert_eq!(table.get("server.host"), Ok(String::from("localhost")));
+        assert_eq!(table.get("extra.other"), Ok(String::from("value")));
+
+        let mut table = base;
+        let options = MergeOptions::new()
+            .deletions(true)
+            .table_strategy("server", TableMerge::Replace);
+
+        table.merge_with(other, &options);
+
+        assert_eq!(table.get("server"), Ok(Table::new()),);
+    }
+
+    #[test]
+    fn test_merge_strategies() {
+        let mut table = table! {
+            "middleware" = ["auth"],
+            "plugins" = ["a"],
+            "server" = { "host" = "localhost", "port" = 80 },
+            "log" = { "level" = "info", "file" = "app.log" },
+        };
+        let options = MergeOptions::new()
+            .strategy("middleware", ArrayMerge::Append)
+            .table_strategy("log", TableMerge::Replace);
+
+        table.merge_with(
+            table! {
+                "middleware" = ["cors"],
+                "plugins" = ["b"],
+                "server" = { "port" = 8080 },
+                "log" = { "level" = "debug" },
+            },
+            &options,
+        );
+
+        assert_eq!(
+            table.get("middleware"),
+            Ok(vec![String::from("auth"), String::from("cors")])
+        );
+        assert_eq!(table.get("plugins"), Ok(vec![String::from("b")]));
+        assert_eq!(table.get("server.host"), Ok(String::from("localhost")));
+        assert_eq!(table.get("server.port"), Ok(8080));
+        assert_eq!(table.get("log.level"), Ok(String::from("debug")));
+        assert!(table.get::<_, String>("log.file").is_err());
+    }
+}
@@ -8,28 +8,46 @@ use serde::de::{
 use serde::ser::{Serialize, Serializer};
 
 use self::de::{Error as DeError, ValueDeserializer};
+use self::entry::TOML_DATETIME_FIELD;
 use self::ser::ValueSerializer;
 
 pub use self::array::Array;
-pub use self::entry::Entry;
+pub use self::diff::Diff;
+pub use self::entry::{Entry, Kind};
 pub use self::error::Error;
+pub use self::index::Index;
+#[cfg(feature = "intern")]
+pub use self::intern::release_interned;
 pub use self::key::Key;
-pub use self::table::Table;
+pub use self::merge::{ArrayMerge, MergeOptions, TableMerge, DELETE_MARKER};
+pub use self::ser::{ArraySerializer, TableSerializer};
+pub use self::table::{Table, TableIntoIter, TableIter, TableIterMut};
 
 mod array;
+mod diff;
 mod entry;
 mod error;
+mod index;
+mod intern;
 mod key;
+mod merge;
 mod table;
 
+pub(crate) use self::diff::leaves;
+pub(crate) use self::intern::{intern, resolve, TableKey};
+pub(crate) use self::merge::conflicts;
+
 pub(crate) mod de;
 pub(crate) mod ser;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
 pub fn from_value<T>(value: Value) -> Result<T, Error>
 where
     T: DeserializeOwned,
 {
-    T::deserialize(ValueDeserializer::new(&value)).map_err(Error::custom)
+    T::deserialize(value).map_err(Error::custom)
 }
 
 pub fn to_value<T>(value: T) -> Result<Value, Error>
@@ -39,6 +57,10 @@ where
     value.serialize(ValueSerializer).map_err(Error::custom)
 }
 
+pub(crate) fn capacity_hint(hint: Option<usize>) -> usize {
+    hint.unwrap_or(0).min(4096)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     Entry(Entry),
@@ -71,6 +93,14 @@ impl Value {
         }
     }
 
+    pub(crate) fn find(&self, key: &Key, depth: usize) -> Result<&Value, Error> {
+        match self {
+            Value::Entry(_) => Err(Error::custom("call `get` on entry variant")),
+            Value::Array(array) => array.find(key, depth),
+            Value::Table(table) => table.find(key, depth),
+        }
+    }
+
     pub fn set<K, V>(&mut self, key: K, value: V) -> Result<&mut Self, Error>
     where
         K: Into<Key>,
@@ -78,56 +108,56 @@ impl Value {
     {
         let key = key.into();
 
-        match key.peek() {
-            Some(head) => match self {
-                Value::Entry(_) => match head.parse::<usize>() {
-                    Ok(_) => {
-                        let mut array = Value::array();
-                        array.set(key, value)?;
-                        *self = array;
+        if key.peek().is_none() {
+            return Err(Error::custom("empty key"));
+        }
 
-                        Ok(self)
-                    }
-                    Err(_) => {
-                        let mut table = Value::table();
-                        table.set(key, value)?;
-                        *self = table;
+        let value = value
+            .serialize(ValueSerializer)
+            .map_err(|err| err.at(key.path()))?;
 
-                        Ok(self)
-                    }
-                },
-                Value::Array(array) => match head.parse::<usize>() {
-                    Ok(_) => {
-                        array.set(key, value)?;
+        self.splice(key, value)?;
 
-                        Ok(self)
-                    }
-                    Err(_) => {
-                        let mut table = Value::table();
-                        for (index, item) in array.into_iter().enumerate() {
-                            table.set(index, item)?;
-                        }
-                        table.set(key, value)?;
-                        *self = table;
-
-                        Ok(self)
-                    }
-                },
-                Value::Table(table) => {
-                    table.set(key, value)?;
+        Ok(self)
+    }
 
-                    Ok(self)
-                }
-            },
-            None => Err(Error::custom("empty key")),
+    pub(crate) fn splice(&mut self, key: Key, value: Value) -> Result<(), Error> {
+        let numeric = match key.peek() {
+            Some(head) => head.parse::<usize>().is_ok(),
+            None => return Err(Error::custom("empty key")),
+        };
+
+        match self {
+            Value::Entry(_) => {
+                let mut container = if numeric {
+                    Value::array()
+                } else {
+                    Value::table()
+                };
+
+                container.splice(key, value)?;
+                *self = container;
+
+                Ok(())
+            }
+            Value::Array(array) if numeric => array.splice(key, value),
+            Value::Array(array) => {
+                let table = array
+                    .0
+                    .drain(..)
+                    .enumerate()
+                    .map(|(index, item)| (intern(index.to_string()), item))
+                    .collect();
+
+                *self = Value::Table(Table(table));
+                self.splice(key, value)
+            }
+            Value::Table(table) => table.splice(key, value),
         }
     }
 
     pub fn is_entry(&self) -> bool {
-        match self {
-            Value::Entry(_) => true,
-            _ => false,
-        }
+        matches!(self, Value::Entry(_))
     }
 
     pub fn as_entry(&self) -> Option<&Entry> {
@@ -138,10 +168,7 @@ impl Value {
     }
 
     pub fn is_array(&self) -> bool {
-        match self {
-            Value::Array(_) => true,
-            _ => false,
-        }
+        matches!(self, Value::Array(_))
     }
 
     pub fn as_array(&self) -> Option<&Array> {
@@ -152,10 +179,7 @@ impl Value {
     }
 
     pub fn is_table(&self) -> bool {
-        match self {
-            Value::Table(_) => true,
-            _ => false,
-        }
+        matches!(self, Value::Table(_))
     }
 
     pub fn as_table(&self) -> Option<&Table> {
@@ -229,6 +253,18 @@ impl<'de> Deserialize<'de> for Value {
                 Ok(Value::from(value))
             }
 
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Ok(Value::Entry(Entry::bytes(value)))
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                Ok(Value::Entry(Entry::null()))
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(Value::Entry(Entry::null()))
+            }
+
             fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
             where
                 D: Deserializer<'de>,
@@ -240,7 +276,7 @@ impl<'de> Deserialize<'de> for Value {
             where
                 V: SeqAccess<'de>,
             {
-                let mut vec = Vec::new();
+                let mut vec = Vec::with_capacity(capacity_hint(visitor.size_hint()));
 
                 while let Some(elem) = visitor.next_element()? {
                     vec.push(elem);
@@ -253,13 +289,19 @@ impl<'de> Deserialize<'de> for Value {
             where
                 V: MapAccess<'de>,
             {
-                let mut map = IndexMap::new();
+                let mut table = Table::with_capacity(capacity_hint(visitor.size_hint()));
 
-                while let Some(key) = visitor.next_key()? {
-                    map.insert(key, visitor.next_value()?);
+                while let Some(key) = visitor.next_key::<String>()? {
+                    if table.0.is_empty() && key == TOML_DATETIME_FIELD {
+                        return Ok(Value::from(Entry::datetime(
+                            visitor.next_value::<String>()?,
+                        )));
+                    }
+
+                    table.0.insert(intern(key), visitor.next_value()?);
                 }
 
-                Ok(Value::from(map))
+                Ok(Value::Table(table))
             }
         }
 
@@ -275,6 +317,14 @@ impl<'de> IntoDeserializer<'de, DeError> for &'de Value {
     }
 }
 
+impl<'de> IntoDeserializer<'de, DeError> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
 impl From<Entry> for Value {
     fn from(value: Entry) -> Self {
         Value::Entry(value)
@@ -421,12 +471,15 @@ mod tests {
         assert_eq!(Value::entry().as_entry(), Some(&Entry::new()));
         assert_eq!(
             Value::from("hi").as_entry(),
-            Some(&Entry(String::from("hi")))
+            Some(&Entry::from(String::from("hi")))
         );
         assert_eq!(
             Value::from(String::from("hello")).as_entry(),
-            Some(&Entry(String::from("hello")))
+            Some(&Entry::from(String::from("hello")))
         );
+        assert_eq!(Value::from(1), Value::from("1"));
+        assert_eq!(Value::from(true), Value::from("true"));
+        assert_ne!(Value::from(1), Value::from("1.0"));
     }
 
     #[test]
@@ -448,4 +501,84 @@ mod tests {
 
         assert_eq!(Value::table().as_table(), Some(&Table::new()));
     }
+
+    #[test]
+    fn test_set() {
+        let mut value = Value::entry();
+
+        assert!(value.set("list.0", "a").is_ok());
+        assert!(value.set("list.1", "b").is_ok());
+        assert!(value.set("list.3", "d").is_err());
+        assert_eq!(
+            value.get::<_, Vec<String>>("list"),
+            Ok(vec![String::from("a"), String::from("b")])
+        );
+
+        assert!(value.set("list.name", "c").is_ok());
+        assert_eq!(value.get::<_, String>("list.0"), Ok(String::from("a")));
+        assert_eq!(value.get::<_, String>("list.1"), Ok(String::from("b")));
+        assert_eq!(value.get::<_, String>("list.name"), Ok(String::from("c")));
+
+        let mut entry = Value::from("kept");
+
+        assert!(entry.set("0.2", "x").is_err());
+        assert_eq!(entry, Value::from("kept"));
+
+        let err = value
+            .set("bad", std::collections::HashMap::from([((), 1)]))
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "bad: unsupported key type: unit");
+    }
+
+    #[test]
+    fn test_index() {
+        let mut value = Value::entry();
+
+        assert!(value.set("server.hosts", ["a", "b"]).is_ok());
+
+        assert_eq!(value["server"]["hosts"][1], Value::from("b"));
+        assert_eq!(value["server"]["hosts"][2], Value::from(Entry::null()));
+        assert_eq!(value["missing"]["hosts"][0], Value::from(Entry::null()));
+        assert_eq!(value["server"][String::from("hosts")][0], Value::from("a"));
+
+        value["server"]["hosts"][0] = Value::from("c");
+        value["server"]["port"] = Value::from("8080");
+
+        let mut built = Value::from(Entry::null());
+
+        built["name"]["first"] = Value::from("app");
+
+        assert_eq!(
+            value.get::<_, String>("server.hosts.0"),
+            Ok(String::from("c"))
+        );
+        assert_eq!(value.get::<_, u16>("server.port"), Ok(8080));
+        assert_eq!(
+            built.get::<_, String>("name.first"),
+            Ok(String::from("app"))
+        );
+
+        let mut table = Table::new();
+
+        table["list"] = Value::from(Array::from(vec![Value::from("x")]));
+
+        assert_eq!(table["list"][0], Value::from("x"));
+        assert_eq!(table["other"], Value::from(Entry::null()));
+
+        if let Value::Array(array) = &mut table["list"] {
+            array[0] = Value::from("y");
+
+            assert_eq!(array[0], Value::from("y"));
+            assert_eq!(array[1], Value::from(Entry::null()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot index into an entry with a key")]
+    fn test_index_entry() {
+        let mut value = Value::from("text");
+
+        value["key"] = Value::from("other");
+    }
 }
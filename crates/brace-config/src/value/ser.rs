@@ -8,7 +8,8 @@ use serde::ser::{
     Serializer,
 };
 
-use super::Value;
+use super::entry::DATETIME_NAME;
+use super::{intern, Array, Entry, Table, Value};
 
 pub struct ValueSerializer;
 
@@ -83,8 +84,8 @@ impl Serializer for ValueSerializer {
         Ok(Value::from(value))
     }
 
-    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported value type: byte array"))
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Entry(Entry::bytes(value)))
     }
 
     fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
@@ -95,15 +96,15 @@ impl Serializer for ValueSerializer {
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported value type: none option"))
+        Ok(Value::Entry(Entry::null()))
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported value type: unit"))
+        Ok(Value::Entry(Entry::null()))
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported value type: unit struct"))
+        Ok(Value::Entry(Entry::null()))
     }
 
     fn serialize_unit_variant(
@@ -117,13 +118,18 @@ impl Serializer for ValueSerializer {
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        match value.serialize(self)? {
+            Value::Entry(entry) if name == DATETIME_NAME => {
+                Ok(Value::from(Entry::datetime(entry.0)))
+            }
+            value => Ok(value),
+        }
     }
 
     fn serialize_newtype_variant<T>(
@@ -174,9 +180,9 @@ impl Serializer for ValueSerializer {
         })
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
         Ok(TableMapSerializer {
-            map: IndexMap::new(),
+            map: Table::with_capacity(len.unwrap_or(0)),
             next_key: None,
         })
     }
@@ -194,11 +200,11 @@ impl Serializer for ValueSerializer {
         _name: &'static str,
         _variant_index: u32,
         variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         Ok(TableMapMatrixSerializer {
             name: String::from(variant),
-            map: IndexMap::new(),
+            map: Table::with_capacity(len),
         })
     }
 }
@@ -211,11 +217,17 @@ impl SerializeSeq for ArraySeqSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.seq.push(value.serialize(ValueSerializer)?);
+        let index = self.seq.len();
+
+        self.seq.push(
+            value
+                .serialize(ValueSerializer)
+                .map_err(|err| err.at(index))?,
+        );
 
         Ok(())
     }
@@ -229,11 +241,17 @@ impl SerializeTuple for ArraySeqSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.seq.push(value.serialize(ValueSerializer)?);
+        let index = self.seq.len();
+
+        self.seq.push(
+            value
+                .serialize(ValueSerializer)
+                .map_err(|err| err.at(index))?,
+        );
 
         Ok(())
     }
@@ -247,11 +265,17 @@ impl SerializeTupleStruct for ArraySeqSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.seq.push(value.serialize(ValueSerializer)?);
+        let index = self.seq.len();
+
+        self.seq.push(
+            value
+                .serialize(ValueSerializer)
+                .map_err(|err| err.at(index))?,
+        );
 
         Ok(())
     }
@@ -270,11 +294,17 @@ impl SerializeTupleVariant for ArraySeqMatrixSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.seq.push(value.serialize(ValueSerializer)?);
+        let index = self.seq.len();
+
+        self.seq.push(
+            value
+                .serialize(ValueSerializer)
+                .map_err(|err| err.at(index))?,
+        );
 
         Ok(())
     }
@@ -289,7 +319,7 @@ impl SerializeTupleVariant for ArraySeqMatrixSerializer {
 }
 
 pub struct TableMapSerializer {
-    pub(crate) map: IndexMap<String, Value>,
+    pub(crate) map: Table,
     pub(crate) next_key: Option<String>,
 }
 
@@ -297,29 +327,33 @@ impl SerializeMap for TableMapSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Error>
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         self.next_key = Some(key.serialize(TableKeySerializer)?);
 
         Ok(())
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Error>
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         let key = self.next_key.take();
         let key = key.expect("serialize_value called before serialize_key");
 
-        self.map.insert(key, value.serialize(ValueSerializer)?);
+        let value = value
+            .serialize(ValueSerializer)
+            .map_err(|err| err.at(&key))?;
+
+        self.map.0.insert(intern(key), value);
 
         Ok(())
     }
 
     fn end(self) -> Result<Value, Error> {
-        Ok(Value::from(self.map))
+        Ok(Value::Table(self.map))
     }
 }
 
@@ -327,9 +361,9 @@ impl SerializeStruct for TableMapSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         SerializeMap::serialize_key(self, key)?;
         SerializeMap::serialize_value(self, value)?;
@@ -344,19 +378,22 @@ impl SerializeStruct for TableMapSerializer {
 
 pub struct TableMapMatrixSerializer {
     pub(crate) name: String,
-    pub(crate) map: IndexMap<String, Value>,
+    pub(crate) map: Table,
 }
 
 impl SerializeStructVariant for TableMapMatrixSerializer {
     type Ok = Value;
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
-        self.map
-            .insert(String::from(key), value.serialize(ValueSerializer)?);
+        let value = value
+            .serialize(ValueSerializer)
+            .map_err(|err| err.at(key))?;
+
+        self.map.0.insert(intern(key), value);
 
         Ok(())
     }
@@ -364,12 +401,125 @@ impl SerializeStructVariant for TableMapMatrixSerializer {
     fn end(self) -> Result<Value, Error> {
         let mut map = IndexMap::new();
 
-        map.insert(self.name, Value::from(self.map));
+        map.insert(self.name, Value::Table(self.map));
 
         Ok(Value::from(map))
     }
 }
 
+pub struct TableSerializer<'a> {
+    table: &'a mut Table,
+    next_key: Option<String>,
+}
+
+impl<'a> TableSerializer<'a> {
+    pub fn new(table: &'a mut Table) -> Self {
+        Self {
+            table,
+            next_key: None,
+        }
+    }
+}
+
+impl SerializeMap for TableSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.next_key = Some(key.serialize(TableKeySerializer)?);
+
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.next_key.take();
+        let key = key.expect("serialize_value called before serialize_key");
+        let value = value
+            .serialize(ValueSerializer)
+            .map_err(|err| err.at(&key))?;
+
+        self.table.0.insert(intern(key), value);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl SerializeStruct for TableSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeMap::serialize_entry(self, key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+pub struct ArraySerializer<'a> {
+    array: &'a mut Array,
+}
+
+impl<'a> ArraySerializer<'a> {
+    pub fn new(array: &'a mut Array) -> Self {
+        Self { array }
+    }
+}
+
+impl SerializeSeq for ArraySerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let index = self.array.0.len();
+
+        self.array.0.push(
+            value
+                .serialize(ValueSerializer)
+                .map_err(|err| err.at(index))?,
+        );
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTuple for ArraySerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
 pub struct TableKeySerializer;
 
 impl Serializer for TableKeySerializer {
@@ -392,19 +542,19 @@ impl Serializer for TableKeySerializer {
         Ok(variant.to_owned())
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(
+    fn serialize_newtype_struct<T>(
         self,
         _name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         value.serialize(self)
     }
 
-    fn serialize_bool(self, _value: bool) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported key type: bool"))
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(value.to_string())
     }
 
     fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
@@ -423,6 +573,10 @@ impl Serializer for TableKeySerializer {
         Ok(value.to_string())
     }
 
+    fn serialize_i128(self, value: i128) -> Result<Self::Ok, Self::Error> {
+        Ok(value.to_string())
+    }
+
     fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
         Ok(value.to_string())
     }
@@ -439,12 +593,16 @@ impl Serializer for TableKeySerializer {
         Ok(value.to_string())
     }
 
-    fn serialize_f32(self, _value: f32) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported key type: f32"))
+    fn serialize_u128(self, value: u128) -> Result<Self::Ok, Self::Error> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(value.to_string())
     }
 
-    fn serialize_f64(self, _value: f64) -> Result<Self::Ok, Self::Error> {
-        Err(Error::custom("unsupported key type: f64"))
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(value.to_string())
     }
 
     fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
@@ -467,7 +625,7 @@ impl Serializer for TableKeySerializer {
         Err(Error::custom("unsupported key type: unit struct"))
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
         _variant_index: u32,
@@ -475,7 +633,7 @@ impl Serializer for TableKeySerializer {
         _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::custom("unsupported key type: newtype variant"))
     }
@@ -484,9 +642,9 @@ impl Serializer for TableKeySerializer {
         Err(Error::custom("unsupported key type: none option"))
     }
 
-    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: ?Sized + Serialize,
     {
         Err(Error::custom("unsupported key type: some option"))
     }
@@ -541,11 +699,28 @@ impl Serializer for TableKeySerializer {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Error(String);
+pub struct Error(pub(crate) String, pub(crate) Option<String>);
+
+impl Error {
+    pub fn path(&self) -> Option<&str> {
+        self.1.as_deref()
+    }
+
+    pub(crate) fn at<T: Display>(mut self, segment: T) -> Self {
+        self.1 = Some(match self.1 {
+            Some(path) => format!("{}.{}", segment, path),
+            None => segment.to_string(),
+        });
+        self
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        match &self.1 {
+            Some(path) => write!(f, "{}: {}", path, self.0),
+            None => self.0.fmt(f),
+        }
     }
 }
 
@@ -553,6 +728,6 @@ impl StdError for Error {}
 
 impl SerError for Error {
     fn custom<T: Display>(msg: T) -> Self {
-        Self(msg.to_string())
+        Self(msg.to_string(), None)
     }
 }
@@ -5,28 +5,51 @@ use indexmap::map::{IndexMap, IntoIter, Iter, IterMut};
 use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
 use serde::ser::{Serialize, SerializeMap, Serializer};
 
-use super::{de::ValueDeserializer, ser::ValueSerializer, Error, Key, Value};
+use super::de::ValueDeserializer;
+use super::merge::{merge_table, MergeOptions};
+use super::ser::{ArraySerializer, TableSerializer, ValueSerializer};
+use super::{capacity_hint, intern, resolve, Array, Error, Key, TableKey, Value};
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct Table(IndexMap<String, Value>);
+#[cfg(feature = "ahash")]
+type Hasher = ahash::RandomState;
+
+#[cfg(not(feature = "ahash"))]
+type Hasher = std::collections::hash_map::RandomState;
+
+pub(crate) type TableMap = IndexMap<TableKey, Value, Hasher>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table(pub(crate) TableMap);
 
 impl Table {
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexMap::with_capacity_and_hasher(
+            capacity,
+            Hasher::default(),
+        ))
+    }
+
     pub fn get<'de, K, V>(&'de self, key: K) -> Result<V, Error>
     where
         K: Into<Key>,
         V: 'de + Deserialize<'de>,
     {
-        let mut key = key.into();
+        let key = key.into();
+        let value = self.find(&key, 0)?;
 
-        match key.next() {
-            Some(head) => match self.0.get(&head) {
-                Some(val) => match key.peek() {
-                    Some(_) => val.get(key),
-                    None => Ok(V::deserialize(ValueDeserializer::new(val))?),
+        Ok(V::deserialize(ValueDeserializer::new(value)).map_err(|err| err.at(key.path()))?)
+    }
+
+    pub(crate) fn find(&self, key: &Key, depth: usize) -> Result<&Value, Error> {
+        match key.segment(depth) {
+            Some(head) => match self.0.get(head) {
+                Some(val) => match key.segment(depth + 1) {
+                    Some(_) => val.find(key, depth + 1),
+                    None => Ok(val),
                 },
                 None => Err(Error::custom(format!("missing value for key '{}'", head))),
             },
@@ -39,33 +62,117 @@ impl Table {
         K: Into<Key>,
         V: Serialize,
     {
-        let mut key = key.into();
+        let key = key.into();
+
+        if key.peek().is_none() {
+            return Err(Error::custom("empty key"));
+        }
+
+        let value = val
+            .serialize(ValueSerializer)
+            .map_err(|err| err.at(key.path()))?;
+
+        self.splice(key, value)?;
 
+        Ok(self)
+    }
+
+    pub(crate) fn splice(&mut self, mut key: Key, value: Value) -> Result<(), Error> {
         match key.next() {
             Some(head) => {
-                let item = self.0.entry(head).or_insert_with(Value::entry);
+                let item = self.0.entry(intern(head)).or_insert_with(Value::entry);
 
                 match key.peek() {
-                    Some(_) => {
-                        item.set(key, val)?;
-
-                        Ok(self)
-                    }
+                    Some(_) => item.splice(key, value),
                     None => {
-                        *item = val.serialize(ValueSerializer)?;
+                        *item = value;
 
-                        Ok(self)
+                        Ok(())
                     }
                 }
             }
             None => Err(Error::custom("empty key")),
         }
     }
-}
 
-impl Default for Table {
-    fn default() -> Self {
-        Self(IndexMap::new())
+    pub fn lookup<K>(&self, key: K) -> Option<&Value>
+    where
+        K: Into<Key>,
+    {
+        let mut key = key.into();
+        let mut value = self.0.get(key.next()?.as_str())?;
+
+        for part in key {
+            value = match value {
+                Value::Table(table) => table.0.get(part.as_str())?,
+                Value::Array(array) => array.0.get(part.parse::<usize>().ok()?)?,
+                Value::Entry(_) => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    pub fn lookup_mut<K>(&mut self, key: K) -> Option<&mut Value>
+    where
+        K: Into<Key>,
+    {
+        let mut key = key.into();
+        let mut value = self.0.get_mut(key.next()?.as_str())?;
+
+        for part in key {
+            value = match value {
+                Value::Table(table) => table.0.get_mut(part.as_str())?,
+                Value::Array(array) => array.0.get_mut(part.parse::<usize>().ok()?)?,
+                Value::Entry(_) => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    pub fn table_serializer<K>(&mut self, key: K) -> Result<TableSerializer<'_>, Error>
+    where
+        K: Into<Key>,
+    {
+        let key = key.into();
+
+        if !self.lookup(key.clone()).is_some_and(Value::is_table) {
+            self.set(key.clone(), Table::new())?;
+        }
+
+        match self.lookup_mut(key) {
+            Some(Value::Table(table)) => Ok(TableSerializer::new(table)),
+            _ => Err(Error::custom("expected a table")),
+        }
+    }
+
+    pub fn array_serializer<K>(&mut self, key: K) -> Result<ArraySerializer<'_>, Error>
+    where
+        K: Into<Key>,
+    {
+        let key = key.into();
+
+        if !self.lookup(key.clone()).is_some_and(Value::is_array) {
+            self.set(key.clone(), Array::new())?;
+        }
+
+        match self.lookup_mut(key) {
+            Some(Value::Array(array)) => Ok(ArraySerializer::new(array)),
+            _ => Err(Error::custom("expected an array")),
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.0.shift_remove(key)
+    }
+
+    pub fn merge(&mut self, other: Table) {
+        self.merge_with(other, &MergeOptions::default());
+    }
+
+    pub fn merge_with(&mut self, other: Table, options: &MergeOptions) {
+        merge_table(self, other, options);
     }
 }
 
@@ -77,7 +184,7 @@ impl Serialize for Table {
         let mut map = serializer.serialize_map(Some(self.0.len()))?;
 
         for (key, value) in &self.0 {
-            map.serialize_entry(&key, &value)?;
+            map.serialize_entry(&**key, value)?;
         }
 
         map.end()
@@ -109,13 +216,13 @@ impl<'de> Deserialize<'de> for Table {
             where
                 V: MapAccess<'de>,
             {
-                let mut map = IndexMap::new();
+                let mut table = Table::with_capacity(capacity_hint(visitor.size_hint()));
 
-                while let Some(key) = visitor.next_key()? {
-                    map.insert(key, visitor.next_value()?);
+                while let Some(key) = visitor.next_key::<String>()? {
+                    table.0.insert(intern(key), visitor.next_value()?);
                 }
 
-                Ok(Table(map))
+                Ok(table)
             }
         }
 
@@ -123,54 +230,132 @@ impl<'de> Deserialize<'de> for Table {
     }
 }
 
+pub struct TableIntoIter(IntoIter<TableKey, Value>);
+
+pub struct TableIter<'a>(Iter<'a, TableKey, Value>);
+
+pub struct TableIterMut<'a>(IterMut<'a, TableKey, Value>);
+
+impl Iterator for TableIntoIter {
+    type Item = (String, Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, val)| (resolve(key), val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for TableIntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, val)| (resolve(key), val))
+    }
+}
+
+impl ExactSizeIterator for TableIntoIter {}
+
+impl<'a> Iterator for TableIter<'a> {
+    type Item = (&'a str, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, val)| (&**key, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for TableIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, val)| (&**key, val))
+    }
+}
+
+impl ExactSizeIterator for TableIter<'_> {}
+
+impl<'a> Iterator for TableIterMut<'a> {
+    type Item = (&'a str, &'a mut Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, val)| (&**key, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for TableIterMut<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(key, val)| (&**key, val))
+    }
+}
+
+impl ExactSizeIterator for TableIterMut<'_> {}
+
 impl IntoIterator for Table {
     type Item = (String, Value);
-    type IntoIter = IntoIter<String, Value>;
+    type IntoIter = TableIntoIter;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+    fn into_iter(self) -> TableIntoIter {
+        TableIntoIter(self.0.into_iter())
     }
 }
 
 impl<'a> IntoIterator for &'a Table {
-    type Item = (&'a String, &'a Value);
-    type IntoIter = Iter<'a, String, Value>;
+    type Item = (&'a str, &'a Value);
+    type IntoIter = TableIter<'a>;
 
-    fn into_iter(self) -> Iter<'a, String, Value> {
-        self.0.iter()
+    fn into_iter(self) -> TableIter<'a> {
+        TableIter(self.0.iter())
     }
 }
 
 impl<'a> IntoIterator for &'a mut Table {
-    type Item = (&'a String, &'a mut Value);
-    type IntoIter = IterMut<'a, String, Value>;
+    type Item = (&'a str, &'a mut Value);
+    type IntoIter = TableIterMut<'a>;
 
-    fn into_iter(self) -> IterMut<'a, String, Value> {
-        self.0.iter_mut()
+    fn into_iter(self) -> TableIterMut<'a> {
+        TableIterMut(self.0.iter_mut())
     }
 }
 
 impl From<HashMap<String, Value>> for Table {
     fn from(from: HashMap<String, Value>) -> Self {
-        let mut map = IndexMap::new();
+        let mut table = Table::with_capacity(from.len());
 
         for (key, val) in from {
-            map.insert(key, val);
+            table.0.insert(intern(key), val);
         }
 
-        Self(map)
+        table
     }
 }
 
+#[cfg(not(any(feature = "intern", feature = "ahash")))]
 impl From<IndexMap<String, Value>> for Table {
     fn from(from: IndexMap<String, Value>) -> Self {
         Self(from)
     }
 }
 
+#[cfg(any(feature = "intern", feature = "ahash"))]
+impl From<IndexMap<String, Value>> for Table {
+    fn from(from: IndexMap<String, Value>) -> Self {
+        Self(
+            from.into_iter()
+                .map(|(key, val)| (intern(key), val))
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Table;
+    use super::{Table, Value};
 
     #[test]
     fn test_table() {
@@ -191,4 +376,114 @@ mod tests {
         assert_eq!(table.get::<_, String>("age"), Ok(String::from("42")));
         assert_eq!(table.get::<_, i32>("age"), Ok(42));
     }
+
+    #[test]
+    fn test_table_capacity() {
+        let table = Table::with_capacity(16);
+
+        assert!(table.0.is_empty());
+        assert!(table.0.capacity() >= 16);
+    }
+
+    #[test]
+    fn test_table_iter() {
+        let mut table = Table::new();
+
+        table.set("a", 1).unwrap();
+        table.set("b", 2).unwrap();
+
+        for (_, value) in &mut table {
+            *value = Value::from(3);
+        }
+
+        assert_eq!(
+            (&table)
+                .into_iter()
+                .rev()
+                .map(|(key, _)| key)
+                .collect::<Vec<&str>>(),
+            vec!["b", "a"]
+        );
+        assert_eq!(
+            table.into_iter().collect::<Vec<(String, Value)>>(),
+            vec![
+                (String::from("a"), Value::from(3)),
+                (String::from("b"), Value::from(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_merge() {
+        let mut table = Table::new();
+        let mut other = Table::new();
+
+        assert!(table.set("server.host", "localhost").is_ok());
+        assert!(table.set("server.port", "80").is_ok());
+        assert!(table.set("name", "app").is_ok());
+        assert!(other.set("server.port", "8080").is_ok());
+        assert!(other.set("debug", "true").is_ok());
+
+        table.merge(other);
+
+        assert_eq!(
+            table.get::<_, String>("server.host"),
+            Ok(String::from("localhost"))
+        );
+        assert_eq!(table.get::<_, i32>("server.port"), Ok(8080));
+        assert_eq!(table.get::<_, bool>("debug"), Ok(true));
+        assert_eq!(table.remove("name"), Some(Value::from("app")));
+        assert_eq!(table.remove("name"), None);
+    }
+
+    #[test]
+    fn test_table_serializer() {
+        use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
+
+        let mut table = Table::new();
+
+        assert!(table.set("server.host", "localhost").is_ok());
+
+        let mut server = table.table_serializer("server").unwrap();
+
+        assert!(server.serialize_entry("port", &8080).is_ok());
+        assert!(SerializeStruct::serialize_field(&mut server, "debug", &true).is_ok());
+        assert!(SerializeMap::end(server).is_ok());
+
+        let mut hosts = table.array_serializer("server.hosts").unwrap();
+
+        for host in ["a", "b"] {
+            assert!(hosts.serialize_element(host).is_ok());
+        }
+
+        assert!(hosts.end().is_ok());
+
+        let mut hosts = table.array_serializer("server.hosts").unwrap();
+
+        assert!(hosts.serialize_element("c").is_ok());
+
+        assert_eq!(
+            table.get::<_, String>("server.host"),
+            Ok(String::from("localhost"))
+        );
+        assert_eq!(table.get::<_, u16>("server.port"), Ok(8080));
+        assert_eq!(table.get::<_, bool>("server.debug"), Ok(true));
+        assert_eq!(
+            table.get::<_, Vec<String>>("server.hosts"),
+            Ok(vec![
+                String::from("a"),
+                String::from("b"),
+                String::from("c")
+            ])
+        );
+
+        let mut name = table.table_serializer("server.host").unwrap();
+
+        assert!(name.serialize_entry(&1, &"first").is_ok());
+        assert!(table.lookup("server.host").is_some_and(Value::is_table));
+        assert_eq!(
+            table.get::<_, String>("server.host.1"),
+            Ok(String::from("first"))
+        );
+    }
 }
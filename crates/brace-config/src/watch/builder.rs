@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::ConfigWatcher;
+use crate::file::LoadOptions;
+use crate::value::Error;
+use crate::Config;
+
+pub(crate) const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+pub struct WatcherBuilder {
+    pub(crate) path: PathBuf,
+    pub(crate) config: Option<Config>,
+    pub(crate) debounce: Duration,
+    pub(crate) poll: Option<Duration>,
+    pub(crate) options: LoadOptions,
+}
+
+impl WatcherBuilder {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            config: None,
+            debounce: DEFAULT_DEBOUNCE,
+            poll: None,
+            options: LoadOptions::default(),
+        }
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn options(mut self, options: LoadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn debounce(mut self, interval: Duration) -> Self {
+        self.debounce = interval;
+        self
+    }
+
+    pub fn poll(mut self, interval: Duration) -> Self {
+        self.poll = Some(interval);
+        self
+    }
+
+    pub fn build(self) -> Result<ConfigWatcher, Error> {
+        ConfigWatcher::start(self)
+    }
+}
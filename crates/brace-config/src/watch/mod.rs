@@ -0,0 +1,317 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::file::LoadOptions;
+use crate::schema::matches;
+use crate::source::Watch;
+use crate::value::{Diff, Error, Table};
+use crate::Config;
+
+pub use self::builder::WatcherBuilder;
+
+mod builder;
+mod poll;
+
+type Callback = dyn FnMut(&Config, &Diff) + Send;
+
+struct Subscription {
+    pattern: Option<Vec<String>>,
+    callback: Box<Callback>,
+}
+
+impl Subscription {
+    fn notify(&mut self, config: &Config, diff: &Diff) {
+        let diff = match &self.pattern {
+            Some(pattern) => diff.filter(|path| subscribed(pattern, path)),
+            None => diff.clone(),
+        };
+
+        if !diff.is_empty() {
+            (self.callback)(config, &diff);
+        }
+    }
+}
+
+const IDLE_TIMEOUT: Duration = Duration::from_millis(100);
+
+const DATA_LINKS: [&str; 2] = ["..data", "..data_tmp"];
+
+struct Shared {
+    config: RwLock<Arc<Config>>,
+    error: Mutex<Option<Error>>,
+    version: AtomicU64,
+    subscriptions: Mutex<Vec<Subscription>>,
+    options: LoadOptions,
+}
+
+impl Shared {
+    fn reload(&self, path: &Path) -> Result<(), Error> {
+        match Config::load_with(path, &self.options) {
+            Ok(loaded) => {
+                let config = Arc::new(self.rebase(loaded, path));
+                let previous = std::mem::replace(
+                    &mut *self.config.write().unwrap_or_else(|err| err.into_inner()),
+                    config.clone(),
+                );
+
+                self.version.fetch_add(1, Ordering::SeqCst);
+                *self.error.lock().unwrap_or_else(|err| err.into_inner()) = None;
+
+                let diff = previous.diff(&config);
+
+                if !diff.is_empty() {
+                    let mut subscriptions = std::mem::take(
+                        &mut *self
+                            .subscriptions
+                            .lock()
+                            .unwrap_or_else(|err| err.into_inner()),
+                    );
+
+                    for subscription in subscriptions.iter_mut() {
+                        subscription.notify(&config, &diff);
+                    }
+
+                    let mut current = self
+                        .subscriptions
+                        .lock()
+                        .unwrap_or_else(|err| err.into_inner());
+                    let added = std::mem::replace(&mut *current, subscriptions);
+
+                    current.extend(added);
+                }
+
+                Ok(())
+            }
+            Err(err) => {
+                self.fail(err.clone());
+
+                Err(err)
+            }
+        }
+    }
+
+    fn rebase(&self, loaded: Config, path: &Path) -> Config {
+        let mut config = Config::clone(&self.config.read().unwrap_or_else(|err| err.into_inner()));
+
+        match config.layer_mut(&path.display().to_string()) {
+            Some(layer) => *layer = Table::from(loaded),
+            None => return loaded,
+        }
+
+        config.remerge();
+        config
+    }
+
+    fn fail(&self, err: Error) {
+        *self.error.lock().unwrap_or_else(|err| err.into_inner()) = Some(err);
+    }
+}
+
+pub struct ConfigWatcher {
+    path: PathBuf,
+    shared: Arc<Shared>,
+    _watcher: Option<RecommendedWatcher>,
+    _watch: Watch,
+}
+
+impl ConfigWatcher {
+    pub fn builder<P>(path: P) -> WatcherBuilder
+    where
+        P: AsRef<Path>,
+    {
+        WatcherBuilder::new(path)
+    }
+
+    pub fn watch<P>(config: Config, path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::builder(path).config(config).build()
+    }
+
+    fn start(builder: WatcherBuilder) -> Result<Self, Error> {
+        let path = builder.path;
+        let config = match builder.config {
+            Some(config) => config,
+            None => Config::load_with(&path, &builder.options)?,
+        };
+        let shared = Arc::new(Shared {
+            config: RwLock::new(Arc::new(config)),
+            error: Mutex::new(None),
+            version: AtomicU64::new(0),
+            subscriptions: Mutex::new(Vec::new()),
+            options: builder.options,
+        });
+        let (watcher, watch) = match builder.poll {
+            Some(interval) => (None, poll::spawn(shared.clone(), path.clone(), interval)),
+            None => {
+                let (watcher, watch) = notify(shared.clone(), path.clone(), builder.debounce)?;
+
+                (Some(watcher), watch)
+            }
+        };
+
+        Ok(Self {
+            path,
+            shared,
+            _watcher: watcher,
+            _watch: watch,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn snapshot(&self) -> Arc<Config> {
+        self.shared
+            .config
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    pub fn on_change<F>(&self, callback: F)
+    where
+        F: FnMut(&Config, &Diff) + Send + 'static,
+    {
+        self.add_subscription(None, Box::new(callback));
+    }
+
+    pub fn subscribe<P, F>(&self, pattern: P, callback: F)
+    where
+        P: AsRef<str>,
+        F: FnMut(&Config, &Diff) + Send + 'static,
+    {
+        let pattern = pattern.as_ref().split('.').map(ToOwned::to_owned).collect();
+
+        self.add_subscription(Some(pattern), Box::new(callback));
+    }
+
+    fn add_subscription(&self, pattern: Option<Vec<String>>, callback: Box<Callback>) {
+        self.shared
+            .subscriptions
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(Subscription { pattern, callback });
+    }
+
+    pub fn version(&self) -> u64 {
+        self.shared.version.load(Ordering::SeqCst)
+    }
+
+    pub fn reload(&self) -> Result<(), Error> {
+        self.shared.reload(&self.path)
+    }
+
+    pub fn error(&self) -> Option<Error> {
+        self.shared
+            .error
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+}
+
+impl fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+fn notify(
+    shared: Arc<Shared>,
+    path: PathBuf,
+    debounce: Duration,
+) -> Result<(RecommendedWatcher, Watch), Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    })
+    .map_err(Error::custom)?;
+
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(Error::custom)?;
+
+    let watch = Watch::run(move |stop| {
+        let mut pending: Option<Instant> = None;
+
+        while !stop.load(Ordering::SeqCst) {
+            let timeout = match pending {
+                Some(last) => debounce.saturating_sub(last.elapsed()),
+                None => IDLE_TIMEOUT,
+            };
+
+            match rx.recv_timeout(timeout) {
+                Ok(Ok(event)) => {
+                    if affects(&event, &path) {
+                        pending = Some(Instant::now());
+                    }
+                }
+                Ok(Err(err)) => shared.fail(Error::custom(err)),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if let Some(last) = pending {
+                if last.elapsed() >= debounce {
+                    pending = None;
+
+                    let _ = shared.reload(&path);
+                }
+            }
+        }
+    });
+
+    Ok((watcher, watch))
+}
+
+fn subscribed(pattern: &[String], path: &str) -> bool {
+    let pattern = pattern.iter().map(String::as_str).collect::<Vec<_>>();
+    let path = path.split('.').map(ToOwned::to_owned).collect::<Vec<_>>();
+
+    (0..=path.len()).any(|len| matches(&pattern, &path[..len]))
+}
+
+fn affects(event: &Event, path: &Path) -> bool {
+    !event.kind.is_access()
+        && event.paths.iter().any(|changed| {
+            changed.file_name() == path.file_name()
+                || changed
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| DATA_LINKS.contains(&name))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::subscribed;
+
+    fn pattern(pattern: &str) -> Vec<String> {
+        pattern.split('.').map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn test_watch_subscribed() {
+        assert!(subscribed(&pattern("logging.*"), "logging.level"));
+        assert!(subscribed(&pattern("logging.*"), "logging.file.path"));
+        assert!(!subscribed(&pattern("logging.*"), "logging"));
+        assert!(!subscribed(&pattern("logging.*"), "server.port"));
+        assert!(subscribed(&pattern("server"), "server.port"));
+        assert!(subscribed(&pattern("**.port"), "a.b.port"));
+    }
+}
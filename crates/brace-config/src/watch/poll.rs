@@ -0,0 +1,60 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use super::Shared;
+use crate::source::Watch;
+use crate::value::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Fingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+impl Fingerprint {
+    fn read(path: &Path, previous: Option<&Fingerprint>) -> Result<Self, Error> {
+        let metadata = fs::metadata(path).map_err(Error::custom)?;
+        let modified = metadata.modified().ok();
+        let len = metadata.len();
+
+        if let Some(previous) = previous {
+            if modified.is_some() && previous.modified == modified && previous.len == len {
+                return Ok(previous.clone());
+            }
+        }
+
+        let mut hasher = DefaultHasher::new();
+
+        fs::read(path).map_err(Error::custom)?.hash(&mut hasher);
+
+        Ok(Self {
+            modified,
+            len,
+            hash: hasher.finish(),
+        })
+    }
+}
+
+pub(crate) fn spawn(shared: Arc<Shared>, path: PathBuf, interval: Duration) -> Watch {
+    let mut current = Fingerprint::read(&path, None).ok();
+
+    Watch::spawn(interval, move || {
+        match Fingerprint::read(&path, current.as_ref()) {
+            Ok(next) => {
+                let changed = current.as_ref().map(|current| current.hash) != Some(next.hash);
+
+                current = Some(next);
+
+                if changed {
+                    let _ = shared.reload(&path);
+                }
+            }
+            Err(err) => shared.fail(err),
+        }
+    })
+}
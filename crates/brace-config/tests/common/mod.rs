@@ -0,0 +1,102 @@
+#![allow(dead_code)]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+pub fn serve(
+    routes: Vec<(&'static str, u16, &'static str)>,
+    connections: usize,
+) -> (String, mpsc::Receiver<String>) {
+    serve_with(connections, move |request| {
+        let (status, body) = routes
+            .iter()
+            .find(|(route, _, _)| request.contains(route))
+            .map(|(_, status, body)| (*status, *body))
+            .unwrap_or((404, ""));
+
+        response(status, &[("Content-Type", "application/json")], body)
+    })
+}
+
+pub fn serve_sequence(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+    let connections = responses.len();
+    let mut responses = responses.into_iter();
+
+    serve_with(connections, move |_| responses.next().unwrap())
+}
+
+pub fn response(status: u16, headers: &[(&str, &str)], body: &str) -> String {
+    let mut response = format!("HTTP/1.1 {} Status\r\n", status);
+
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    response.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    ));
+
+    response
+}
+
+fn serve_with<F>(connections: usize, mut respond: F) -> (String, mpsc::Receiver<String>)
+where
+    F: FnMut(&str) -> String + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for _ in 0..connections {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&mut stream);
+            let response = respond(&request);
+
+            let _ = sender.send(request);
+
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (format!("http://{}", addr), receiver)
+}
+
+fn read_request<R>(stream: &mut R) -> String
+where
+    R: Read,
+{
+    let mut request = Vec::new();
+    let mut buf = [0; 4096];
+
+    loop {
+        let len = stream.read(&mut buf).unwrap();
+
+        if len == 0 {
+            return String::from_utf8_lossy(&request).into_owned();
+        }
+
+        request.extend_from_slice(&buf[..len]);
+
+        let string = String::from_utf8_lossy(&request).into_owned();
+
+        if let Some(index) = string.find("\r\n\r\n") {
+            let length = string
+                .lines()
+                .find_map(|line| {
+                    line.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|len| len.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+
+            if string.len() >= index + 4 + length {
+                return string;
+            }
+        }
+    }
+}
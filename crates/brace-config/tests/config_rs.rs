@@ -0,0 +1,48 @@
+#![cfg(feature = "config-rs")]
+
+use std::convert::{TryFrom, TryInto};
+
+use brace_config::source::Source;
+use brace_config::{config, Config, Value};
+
+#[test]
+fn test_config_rs_convert() {
+    let cfg = config! {
+        "name" = "app",
+        "server" = {
+            "host" = "localhost",
+            "port" = "8080",
+        },
+        "tags" = ["a", "b"],
+    };
+
+    let other: config_rs::Config = cfg.clone().try_into().unwrap();
+
+    assert_eq!(other.get::<String>("name").unwrap(), "app");
+    assert_eq!(other.get::<u16>("server.port").unwrap(), 8080);
+    assert_eq!(other.get::<Vec<String>>("tags").unwrap(), vec!["a", "b"]);
+    assert_eq!(Config::try_from(other).unwrap(), cfg);
+
+    let value = config_rs::Value::from(Value::from("hello"));
+
+    assert_eq!(value.clone().into_string().unwrap(), "hello");
+    assert_eq!(Value::try_from(value).unwrap(), Value::from("hello"));
+}
+
+#[test]
+fn test_config_rs_source() {
+    let other = config_rs::Config::builder()
+        .set_default("name", "config-rs")
+        .unwrap()
+        .set_default("server.port", 9090)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(other.metadata().name, "config-rs");
+
+    let cfg = Config::builder().add_source(other).build().unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("config-rs")));
+    assert_eq!(cfg.get("server.port"), Ok(9090));
+}
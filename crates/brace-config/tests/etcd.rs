@@ -0,0 +1,64 @@
+#![cfg(feature = "etcd")]
+
+use std::sync::mpsc;
+
+use brace_config::source::{EtcdSource, Source};
+use brace_config::ConfigBuilder;
+
+mod common;
+
+const RANGE: &str = r#"{
+    "header": {"revision": "7"},
+    "kvs": [
+        {"key": "L2FwcC9kYi91cmw=", "value": "cG9zdGdyZXM6Ly9sb2NhbGhvc3Q=", "mod_revision": "5"},
+        {"key": "L2FwcC9wb3J0", "value": "ODA4MA==", "mod_revision": "7"}
+    ]
+}"#;
+
+const WATCH: &str = r#"{"result": {"header": {"revision": "8"}, "events": [{"type": "PUT"}]}}"#;
+
+#[test]
+fn test_etcd_source() {
+    let (endpoint, requests) = common::serve(vec![("POST /v3/kv/range", 200, RANGE)], 1);
+    let cfg = ConfigBuilder::new()
+        .add_source(EtcdSource::new(endpoint, "/app/"))
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("db.url"), Ok(String::from("postgres://localhost")));
+    assert_eq!(cfg.get("port"), Ok(8080));
+
+    let request = requests.recv().unwrap();
+
+    assert!(request.starts_with("POST /v3/kv/range"));
+    assert!(request.contains("\"key\":\"L2FwcC8=\""));
+    assert!(request.contains("\"range_end\":\"L2FwcDA=\""));
+}
+
+#[test]
+fn test_etcd_watch() {
+    let (endpoint, requests) = common::serve(
+        vec![
+            ("POST /v3/kv/range", 200, RANGE),
+            ("POST /v3/watch", 200, WATCH),
+        ],
+        3,
+    );
+    let (sender, receiver) = mpsc::channel();
+    let handle = EtcdSource::new(endpoint, "/app/").watch(move |table| {
+        sender.send(table).unwrap();
+    });
+
+    let table = receiver.recv().unwrap().unwrap();
+
+    assert_eq!(table.get::<_, u16>("port"), Ok(8080));
+    assert!(handle.join().unwrap().is_ok());
+
+    let requests = requests.iter().take(3).collect::<Vec<_>>();
+
+    assert!(requests[1].starts_with("POST /v3/watch"));
+    assert!(requests[1].contains("\"start_revision\":\"8\""));
+    assert!(EtcdSource::new("http://127.0.0.1:1", "/")
+        .collect()
+        .is_err());
+}
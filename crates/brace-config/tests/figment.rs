@@ -0,0 +1,50 @@
+#![cfg(feature = "figment")]
+
+use brace_config::source::FigmentSource;
+use brace_config::{config, Config};
+use figment::providers::Serialized;
+use figment::Figment;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: String,
+}
+
+#[test]
+fn test_figment_provider() {
+    let cfg = config! {
+        "server" = {
+            "host" = "localhost",
+            "port" = "8080",
+        },
+    };
+
+    let server: Server = Figment::from(cfg).extract_inner("server").unwrap();
+
+    assert_eq!(
+        server,
+        Server {
+            host: String::from("localhost"),
+            port: String::from("8080"),
+        }
+    );
+}
+
+#[test]
+fn test_figment_source() {
+    let figment = Figment::new()
+        .merge(Serialized::default("name", "figment"))
+        .merge(Serialized::default("server.port", 9090))
+        .merge(Serialized::default("debug", true));
+
+    let cfg = Config::builder()
+        .add_source(FigmentSource::from(figment))
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("figment")));
+    assert_eq!(cfg.get("server.port"), Ok(9090));
+    assert_eq!(cfg.get("debug"), Ok(true));
+}
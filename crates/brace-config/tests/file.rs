@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use brace_config::{file, Config};
+use brace_config::file::{self, Backup, Format, LoadOptions, SaveOptions};
+use brace_config::Config;
 
 #[test]
 fn test_file_json() {
@@ -124,3 +125,760 @@ fn test_file_invalid() {
 
     assert!(res.is_err());
 }
+
+#[test]
+fn test_file_auto() {
+    for path in &[
+        "tests/assets/example_json",
+        "tests/assets/example_toml",
+        "tests/assets/example_yaml",
+        "tests/assets/example.json",
+    ] {
+        let cfg = file::load_auto(path).unwrap();
+
+        assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+        assert_eq!(cfg.get("two.b"), Ok(String::from("second")));
+        assert_eq!(cfg.get("three"), Ok(vec![1, 25, 150]));
+    }
+
+    assert!(file::load("tests/assets/example_json").is_err());
+    assert!(file::load_auto("tests/assets/example").is_err());
+    assert!(file::load_auto("tests/assets/example.txt").is_err());
+}
+
+#[test]
+fn test_file_string() {
+    let cfg = file::json::from_str(r#"{ "one": "Hello world", "two": { "a": "first" } }"#).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+    assert_eq!(cfg.get("two.a"), Ok(String::from("first")));
+
+    let json = file::json::to_string(&cfg).unwrap();
+    let toml = file::toml::to_string(&cfg).unwrap();
+    let yaml = file::yaml::to_string(&cfg).unwrap();
+
+    assert_eq!(file::json::from_str(&json).unwrap(), cfg);
+    assert_eq!(file::toml::from_str(&toml).unwrap(), cfg);
+    assert_eq!(file::yaml::from_str(&yaml).unwrap(), cfg);
+
+    for format in &[Format::Json, Format::Toml, Format::Yaml] {
+        let string = file::to_string(&cfg, *format).unwrap();
+
+        assert_eq!(file::from_str(&string, *format).unwrap(), cfg);
+    }
+
+    assert!(file::json::from_str("{").is_err());
+    assert!(file::toml::from_str("a =").is_err());
+    assert!(file::yaml::from_str("a: [").is_err());
+}
+
+#[test]
+fn test_file_backup() {
+    let path = "tests/outputs/backup.json";
+    let backup = "tests/outputs/backup.json.bak";
+    let options = SaveOptions::new().backup(Backup::Suffix);
+
+    let _ = std::fs::remove_file(path);
+    let _ = std::fs::remove_file(backup);
+
+    let mut cfg = Config::new();
+
+    cfg.set("version", 1).unwrap();
+    cfg.save_with(path, &options).unwrap();
+
+    assert!(!std::path::Path::new(backup).exists());
+
+    cfg.set("version", 2).unwrap();
+    cfg.save_with(path, &options).unwrap();
+
+    assert_eq!(Config::load(path).unwrap().get("version"), Ok(2));
+    assert_eq!(file::json::load(backup).unwrap().get("version"), Ok(1));
+}
+
+#[test]
+fn test_file_options() {
+    let cfg = file::json::from_str(r#"{ "a": "b", "c": ["d", "e"], "f": { "g": "h" } }"#).unwrap();
+
+    let compact = SaveOptions::new().compact();
+    let indent = SaveOptions::new().indent(4);
+    let flow = SaveOptions::new().flow(true);
+
+    assert_eq!(
+        file::json::to_string_with(&cfg, &compact).unwrap(),
+        r#"{"a":"b","c":["d","e"],"f":{"g":"h"}}"#
+    );
+    assert!(file::json::to_string_with(&cfg, &indent)
+        .unwrap()
+        .contains("\n    \"a\": \"b\""));
+    assert_eq!(
+        file::yaml::to_string_with(&cfg, &flow).unwrap(),
+        "{\"a\": \"b\", \"c\": [\"d\", \"e\"], \"f\": {\"g\": \"h\"}}\n"
+    );
+    assert_eq!(
+        file::toml::to_string_with(&cfg, &compact).unwrap(),
+        "a = \"b\"\nc = [\"d\", \"e\"]\n\n[f]\ng = \"h\"\n"
+    );
+
+    for format in &[Format::Json, Format::Toml, Format::Yaml] {
+        for options in &[&compact, &indent, &flow] {
+            let string = file::to_string_with(&cfg, *format, options).unwrap();
+
+            assert_eq!(file::from_str(&string, *format).unwrap(), cfg);
+        }
+    }
+}
+
+#[test]
+fn test_file_typed() {
+    let mut cfg = Config::new();
+
+    cfg.set("port", 8080).unwrap();
+    cfg.set("ratio", 0.5).unwrap();
+    cfg.set("debug", true).unwrap();
+    cfg.set("name", "8080").unwrap();
+    cfg.set("none", None::<u16>).unwrap();
+
+    let compact = SaveOptions::new().compact();
+    let flow = SaveOptions::new().flow(true);
+
+    assert_eq!(
+        file::json::to_string_with(&cfg, &compact).unwrap(),
+        r#"{"port":8080,"ratio":0.5,"debug":true,"name":"8080","none":null}"#
+    );
+    assert_eq!(
+        file::yaml::to_string_with(&cfg, &flow).unwrap(),
+        "{\"port\": 8080, \"ratio\": 0.5, \"debug\": true, \"name\": \"8080\", \"none\": null}\n"
+    );
+    assert_eq!(
+        file::toml::to_string_with(&cfg, &compact).unwrap(),
+        "port = 8080\nratio = 0.5\ndebug = true\nname = \"8080\"\n"
+    );
+
+    for format in &[Format::Json, Format::Yaml] {
+        let string = file::to_string(&cfg, *format).unwrap();
+
+        assert_eq!(file::from_str(&string, *format).unwrap(), cfg);
+    }
+}
+
+#[test]
+fn test_file_wide_integers() {
+    let mut cfg = Config::new();
+
+    cfg.set("min", i128::MIN).unwrap();
+    cfg.set("max", u128::MAX).unwrap();
+    cfg.set("small", 42_u128).unwrap();
+    cfg.set("large", u64::MAX).unwrap();
+
+    for format in &[Format::Json, Format::Toml, Format::Yaml] {
+        let string = file::to_string(&cfg, *format).unwrap();
+        let loaded = file::from_str(&string, *format).unwrap();
+
+        assert_eq!(loaded.get("min"), Ok(i128::MIN));
+        assert_eq!(loaded.get("max"), Ok(u128::MAX));
+        assert_eq!(loaded.get("small"), Ok(42_u128));
+        assert_eq!(loaded.get("large"), Ok(u64::MAX));
+    }
+
+    assert_eq!(
+        file::json::to_string_with(&cfg, &SaveOptions::new().compact()).unwrap(),
+        format!(
+            r#"{{"min":"{}","max":"{}","small":42,"large":{}}}"#,
+            i128::MIN,
+            u128::MAX,
+            u64::MAX
+        )
+    );
+    assert!(file::yaml::to_string(&cfg)
+        .unwrap()
+        .contains(&format!("min: {}\n", i128::MIN)));
+    assert!(file::toml::to_string(&cfg)
+        .unwrap()
+        .contains(&format!("large = '{}'\n", u64::MAX)));
+}
+
+#[cfg(feature = "toml-edit")]
+#[test]
+fn test_file_toml_preserve() {
+    let path = "tests/outputs/preserve.toml";
+    let original = "# Application settings\nname = \"app\" # inline\n\n[server]\n# Bind address\nhost = \"localhost\"\nport = \"80\"\n";
+
+    std::fs::write(path, original).unwrap();
+
+    let mut cfg = Config::load(path).unwrap();
+
+    cfg.set("server.port", 8080).unwrap();
+    cfg.set("server.tls", true).unwrap();
+    cfg.save(path).unwrap();
+
+    assert!(!std::fs::read_to_string(path).unwrap().contains('#'));
+
+    std::fs::write(path, original).unwrap();
+    cfg.save_with(path, &SaveOptions::new().preserve(true))
+        .unwrap();
+
+    let string = std::fs::read_to_string(path).unwrap();
+
+    assert!(string.starts_with("# Application settings\nname = \"app\" # inline\n"));
+    assert!(string.contains("# Bind address\nhost = \"localhost\"\nport = 8080\n"));
+    assert!(string.contains("tls = true"));
+    assert_eq!(Config::load(path).unwrap().get("server.port"), Ok(8080));
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn test_file_order() {
+    let json = r#"{"zulu":"1","alpha":"2","list":["c","a","b"],"mike":{"yankee":"3","bravo":"4"}}"#;
+    let cfg = file::json::from_str(json).unwrap();
+
+    for format in &[Format::Json, Format::Toml, Format::Yaml] {
+        let path = format!("tests/outputs/order.{:?}", format).to_lowercase();
+
+        file::save_as(&path, &cfg, *format).unwrap();
+
+        let cfg = file::load_as(&path, *format).unwrap();
+        let options = SaveOptions::new().compact();
+
+        assert_eq!(file::json::to_string_with(&cfg, &options).unwrap(), json);
+    }
+}
+
+#[cfg(feature = "lazy")]
+#[test]
+fn test_file_lazy() {
+    let path = "tests/outputs/lazy.json";
+    let json = r#"{"name":"app","server":{"host":"localhost","ports":["80","443"]},"tags":["a","b"],"db\\x":{"user":"root"}}"#;
+
+    std::fs::write(path, json).unwrap();
+
+    let options = LoadOptions::new().lazy(true);
+    let mut cfg = Config::load_with(path, &options).unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("app")));
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+    assert_eq!(cfg.get("server.ports.1"), Ok(443));
+    assert_eq!(cfg.get("db\\x.user"), Ok(String::from("root")));
+    assert!(cfg.lookup("server").is_some_and(|value| value.is_table()));
+    assert!(cfg.get::<_, String>("server.missing").is_err());
+    assert_eq!(cfg, Config::load(path).unwrap());
+    assert_eq!(
+        file::json::to_string_with(&cfg, &SaveOptions::new().compact()).unwrap(),
+        json
+    );
+
+    cfg.set("name", "api").unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("api")));
+    assert_eq!(cfg.get("tags.1"), Ok(String::from("b")));
+
+    cfg.set("server.port", 8080).unwrap();
+
+    assert_eq!(cfg.get("server.port"), Ok(8080));
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+}
+
+#[cfg(all(feature = "gzip", feature = "zstd"))]
+#[test]
+fn test_file_compressed() {
+    let cfg = file::load("tests/assets/example.json").unwrap();
+
+    for path in &[
+        "tests/outputs/example.json.gz",
+        "tests/outputs/example.toml.gz",
+        "tests/outputs/example.yaml.zst",
+    ] {
+        file::save(path, &cfg).unwrap();
+
+        let bytes = std::fs::read(path).unwrap();
+
+        assert!(String::from_utf8(bytes).is_err());
+        assert_eq!(file::load(path).unwrap(), cfg);
+    }
+
+    assert!(file::load("tests/outputs/example.gz").is_err());
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn test_file_encrypted() {
+    let key = [42; 32];
+    let cfg = file::load("tests/assets/example.json").unwrap();
+
+    for path in &["tests/outputs/secret.json.enc", "tests/outputs/secret.yaml"] {
+        cfg.save_encrypted(path, &key).unwrap();
+
+        let string = std::fs::read_to_string(path).unwrap_or_default();
+
+        assert!(!string.contains("Hello world"));
+        assert_eq!(Config::load_encrypted(path, &key).unwrap(), cfg);
+        assert!(Config::load_encrypted(path, &[0; 32]).is_err());
+        assert!(file::load(path).is_err());
+    }
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn test_file_encrypted_values() {
+    let key = [42; 32];
+    let password = file::encryption::encrypt_value("hunter2", &key).unwrap();
+
+    std::fs::write(
+        "tests/outputs/encrypted_values.toml",
+        format!("user = \"app\"\npassword = \"{}\"\n", password),
+    )
+    .unwrap();
+
+    let options = LoadOptions::new().value_key(key);
+    let mut cfg = Config::load_with("tests/outputs/encrypted_values.toml", &options).unwrap();
+
+    assert_eq!(cfg.get("user"), Ok(String::from("app")));
+    assert_eq!(cfg.get("password"), Ok(String::from("hunter2")));
+
+    cfg.set_encrypted("token", "abc123").unwrap();
+
+    assert_eq!(cfg.get("token"), Ok(String::from("abc123")));
+    assert!(cfg
+        .lookup("token")
+        .unwrap()
+        .as_entry()
+        .unwrap()
+        .value()
+        .starts_with("enc:v1:"));
+
+    let cfg = Config::load("tests/outputs/encrypted_values.toml").unwrap();
+
+    assert_eq!(cfg.get("password"), Ok(password));
+
+    let options = LoadOptions::new().value_key([0; 32]);
+    let cfg = Config::load_with("tests/outputs/encrypted_values.toml", &options).unwrap();
+
+    assert!(cfg.get::<_, String>("password").is_err());
+}
+
+#[test]
+fn test_file_reader() {
+    let json = std::fs::File::open("tests/assets/example.json").unwrap();
+    let cfg = file::from_reader(json, Format::Json).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+    assert_eq!(cfg.get("three"), Ok(vec![1, 25, 150]));
+
+    let yaml = "one: Hello world\n".as_bytes();
+    let cfg = file::from_reader(yaml, Format::Yaml).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+
+    assert!(file::save("-", &cfg).is_err());
+}
+
+#[test]
+fn test_file_error_path() {
+    let err = file::load("tests/assets/missing.json").unwrap_err();
+
+    assert!(err.is_not_found());
+    assert!(err.io_error().is_some());
+    assert_eq!(
+        err.path(),
+        Some(std::path::Path::new("tests/assets/missing.json"))
+    );
+    assert!(err.to_string().starts_with("tests/assets/missing.json: "));
+
+    std::fs::write("tests/outputs/invalid.json", "{").unwrap();
+
+    let err = file::load("tests/outputs/invalid.json").unwrap_err();
+
+    assert!(!err.is_not_found());
+    assert!(err.io_error().is_none());
+    assert_eq!(
+        err.path(),
+        Some(std::path::Path::new("tests/outputs/invalid.json"))
+    );
+
+    let err = file::json::load("tests/assets/missing.json").unwrap_err();
+
+    assert!(err.is_not_found());
+    assert_eq!(
+        err.path(),
+        Some(std::path::Path::new("tests/assets/missing.json"))
+    );
+
+    let err = file::save("tests/missing/example.json", &Config::new()).unwrap_err();
+
+    assert!(err.io_error().is_some());
+    assert_eq!(
+        err.path(),
+        Some(std::path::Path::new("tests/missing/example.json"))
+    );
+}
+
+#[test]
+fn test_file_max_size() {
+    let options = LoadOptions::new().max_size(8);
+    let err = file::load_with("tests/assets/example.json", &options).unwrap_err();
+
+    assert!(matches!(err, file::error::Error::FileTooLarge(8, _)));
+    assert_eq!(
+        err.path(),
+        Some(std::path::Path::new("tests/assets/example.json"))
+    );
+
+    let options = LoadOptions::new().unlimited();
+    let cfg = Config::load_with("tests/assets/example.json", &options).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+
+    let options = LoadOptions::new().max_size(8).detect(true);
+
+    assert!(file::load_with("tests/assets/example_json", &options).is_err());
+    assert!(file::load_auto("tests/assets/example_json").is_ok());
+}
+
+#[test]
+fn test_file_error_location() {
+    std::fs::write(
+        "tests/outputs/location.json",
+        "{\n  \"one\": \"a\",\n  \"two\" 2\n}\n",
+    )
+    .unwrap();
+
+    let err = file::load("tests/outputs/location.json").unwrap_err();
+    let location = err.location().unwrap();
+
+    assert_eq!((location.line, location.column), (3, 9));
+    assert_eq!(location.snippet.as_deref(), Some("  \"two\" 2"));
+    assert!(err
+        .to_string()
+        .starts_with("tests/outputs/location.json:3:9: "));
+
+    let err = file::from_str("one = \"a\"\ntwo = \n", Format::Toml).unwrap_err();
+    let location = err.location().unwrap();
+
+    assert_eq!(location.line, 2);
+    assert_eq!(location.snippet.as_deref(), Some("two ="));
+
+    let err = file::from_str("one: a\n  two: [b\n", Format::Yaml).unwrap_err();
+
+    assert!(err.location().is_some());
+}
+
+#[test]
+fn test_file_toml_datetime() {
+    std::fs::write(
+        "tests/outputs/datetime.toml",
+        "created = 1979-05-27T07:32:00Z\n\n[release]\ndate = 1979-05-27\ndates = [07:32:00]\n",
+    )
+    .unwrap();
+
+    let cfg = Config::load("tests/outputs/datetime.toml").unwrap();
+
+    assert_eq!(cfg.get("created"), Ok(String::from("1979-05-27T07:32:00Z")));
+    assert_eq!(cfg.get("release.date"), Ok(String::from("1979-05-27")));
+
+    cfg.save("tests/outputs/datetime.toml").unwrap();
+
+    let string = std::fs::read_to_string("tests/outputs/datetime.toml").unwrap();
+
+    assert!(string.contains("created = 1979-05-27T07:32:00Z"));
+    assert!(string.contains("date = 1979-05-27\n"));
+    assert!(string.contains("07:32:00"));
+    assert!(!string.contains("'07:32:00'"));
+    assert_eq!(Config::load("tests/outputs/datetime.toml").unwrap(), cfg);
+
+    let json = file::to_string(&cfg, Format::Json).unwrap();
+
+    assert!(json.contains("\"created\": \"1979-05-27T07:32:00Z\""));
+}
+
+#[test]
+fn test_file_load_any() {
+    std::fs::write("tests/outputs/any.yaml", "source: yaml\n").unwrap();
+    std::fs::write("tests/outputs/any.json", "{\"source\": \"json\"}").unwrap();
+
+    let cfg = Config::load_any("tests/outputs/any").unwrap();
+
+    assert_eq!(cfg.get("source"), Ok(String::from("yaml")));
+
+    let cfg = Config::load_any_with("tests/outputs/any", &[Format::Json, Format::Yaml]).unwrap();
+
+    assert_eq!(cfg.get("source"), Ok(String::from("json")));
+
+    let err = file::load_any_with("tests/outputs/any", &[Format::Toml]).unwrap_err();
+
+    assert!(err.is_not_found());
+    assert_eq!(err.path(), Some(std::path::Path::new("tests/outputs/any")));
+}
+
+#[test]
+fn test_file_include() {
+    std::fs::create_dir_all("tests/outputs/include").unwrap();
+    std::fs::write(
+        "tests/outputs/include/main.toml",
+        "\"$include\" = [\"base.yaml\", \"secrets.json\"]\nname = \"main\"\n\n[server]\nport = 8080\n",
+    )
+    .unwrap();
+    std::fs::write(
+        "tests/outputs/include/base.yaml",
+        "$include: nested.toml\nname: base\nserver:\n  host: localhost\n  port: 80\n",
+    )
+    .unwrap();
+    std::fs::write("tests/outputs/include/nested.toml", "level = \"nested\"\n").unwrap();
+    std::fs::write(
+        "tests/outputs/include/secrets.json",
+        "{\"password\": \"hunter2\"}",
+    )
+    .unwrap();
+
+    let cfg = Config::load("tests/outputs/include/main.toml").unwrap();
+
+    assert!(cfg.get::<_, String>("level").is_err());
+
+    let options = LoadOptions::new().includes(true);
+    let cfg = Config::load_with("tests/outputs/include/main.toml", &options).unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("main")));
+    assert_eq!(cfg.get("level"), Ok(String::from("nested")));
+    assert_eq!(cfg.get("password"), Ok(String::from("hunter2")));
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+    assert_eq!(cfg.get("server.port"), Ok(8080));
+    assert!(cfg.get::<_, String>(file::INCLUDE_KEY).is_err());
+
+    let options = LoadOptions::new().includes(true).include_depth(1);
+    let err = file::load_with("tests/outputs/include/main.toml", &options).unwrap_err();
+
+    assert!(err.to_string().contains("maximum include depth"));
+
+    std::fs::write(
+        "tests/outputs/include/nested.toml",
+        "\"$include\" = \"base.yaml\"\n",
+    )
+    .unwrap();
+
+    let options = LoadOptions::new().includes(true);
+    let err = file::load_with("tests/outputs/include/main.toml", &options).unwrap_err();
+
+    assert!(matches!(err, file::error::Error::IncludeError(..)));
+    assert!(err.to_string().contains("include cycle"));
+}
+
+#[test]
+fn test_file_extends() {
+    std::fs::create_dir_all("tests/outputs/extends/app").unwrap();
+    std::fs::write(
+        "tests/outputs/extends/base.toml",
+        "name = \"base\"\nlevel = \"base\"\n\n[server]\nhost = \"localhost\"\nport = 80\n",
+    )
+    .unwrap();
+    std::fs::write("tests/outputs/extends/shared.yaml", "level: shared\n").unwrap();
+    std::fs::write(
+        "tests/outputs/extends/app/config.toml",
+        "extends = [\"../base.toml\", \"../shared.yaml\"]\nname = \"app\"\n\n[server]\nport = 8080\n",
+    )
+    .unwrap();
+
+    let cfg = Config::load("tests/outputs/extends/app/config.toml").unwrap();
+
+    assert!(cfg.get::<_, String>("level").is_err());
+
+    let options = LoadOptions::new().extends(true);
+    let cfg = Config::load_with("tests/outputs/extends/app/config.toml", &options).unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("app")));
+    assert_eq!(cfg.get("level"), Ok(String::from("shared")));
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+    assert_eq!(cfg.get("server.port"), Ok(8080));
+    assert!(cfg.get::<_, String>(file::EXTENDS_KEY).is_err());
+
+    std::fs::write(
+        "tests/outputs/extends/shared.yaml",
+        "extends: app/config.toml\n",
+    )
+    .unwrap();
+
+    let err = file::load_with("tests/outputs/extends/app/config.toml", &options).unwrap_err();
+
+    assert!(err.to_string().contains("extends cycle"));
+}
+
+#[test]
+fn test_file_resolve_path() {
+    std::fs::create_dir_all("tests/outputs/resolve").unwrap();
+    std::fs::write(
+        "tests/outputs/resolve/config.toml",
+        "[tls]\ncert_file = \"certs/server.pem\"\nkey_file = \"/etc/ssl/server.key\"\n",
+    )
+    .unwrap();
+
+    let cfg = Config::load("tests/outputs/resolve/config.toml").unwrap();
+
+    assert_eq!(
+        cfg.origin(),
+        Some(std::path::Path::new("tests/outputs/resolve/config.toml"))
+    );
+    assert_eq!(
+        cfg.resolve_path("tls.cert_file").unwrap(),
+        std::path::PathBuf::from("tests/outputs/resolve/certs/server.pem")
+    );
+    assert_eq!(
+        cfg.resolve_path("tls.key_file").unwrap(),
+        std::path::PathBuf::from("/etc/ssl/server.key")
+    );
+    assert!(cfg.resolve_path("tls.missing").is_err());
+    assert!(Config::new().resolve_path("tls.cert_file").is_err());
+
+    let options = LoadOptions::new()
+        .resolve_path("tls.cert_file")
+        .resolve_path("tls.missing");
+    let cfg = Config::load_with("tests/outputs/resolve/config.toml", &options).unwrap();
+
+    assert_eq!(
+        cfg.get("tls.cert_file"),
+        Ok(String::from("tests/outputs/resolve/certs/server.pem"))
+    );
+}
+
+#[test]
+#[cfg(not(windows))]
+fn test_file_load_standard() {
+    let root = std::env::current_dir()
+        .unwrap()
+        .join("tests/outputs/standard");
+
+    std::fs::create_dir_all(root.join("system/brace-test")).unwrap();
+    std::fs::create_dir_all(root.join("user/brace-test")).unwrap();
+    std::fs::write(
+        root.join("system/brace-test/config.yaml"),
+        "name: system\nlevel: system\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("user/brace-test/config.toml"),
+        "name = \"user\"\n",
+    )
+    .unwrap();
+
+    std::env::set_var("XDG_CONFIG_DIRS", root.join("system"));
+    std::env::set_var("XDG_CONFIG_HOME", root.join("user"));
+
+    let dirs = file::standard_dirs("brace-test");
+
+    assert_eq!(dirs.last(), Some(&root.join("user/brace-test")));
+
+    let cfg = Config::load_standard("brace-test").unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("user")));
+    assert_eq!(cfg.get("level"), Ok(String::from("system")));
+    assert_eq!(
+        Config::load_standard("brace-missing").unwrap(),
+        Config::new()
+    );
+}
+
+#[test]
+fn test_file_discover() {
+    let root = std::env::current_dir()
+        .unwrap()
+        .join("tests/outputs/discover");
+    let nested = root.join("project/src/deep");
+
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(root.join(".bracerc"), "name = \"root\"\nlevel = \"root\"\n").unwrap();
+    std::fs::write(root.join("project/.bracerc"), "{\"name\": \"project\"}").unwrap();
+
+    let cfg = Config::discover_from(&nested, ".bracerc").unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("project")));
+    assert_eq!(cfg.get("level"), Ok(String::from("root")));
+    assert_eq!(
+        Config::discover_from(&nested, ".missingrc").unwrap(),
+        Config::new()
+    );
+}
+
+#[test]
+fn test_file_conditions() {
+    std::fs::create_dir_all("tests/outputs/conditions").unwrap();
+    std::fs::write(
+        "tests/outputs/conditions/config.toml",
+        format!(
+            "name = \"app\"\nlevel = \"info\"\n\n[when.'cfg(target_os = \"{}\")']\nplatform = \"native\"\n\n[when.'cfg(target_os = \"plan9\")']\nplatform = \"plan9\"\n\n[when.env.BRACE_CONDITION_STAGE.prod]\nlevel = \"warn\"\n\n[when.env.BRACE_CONDITION_STAGE.dev]\nlevel = \"debug\"\n",
+            std::env::consts::OS
+        ),
+    )
+    .unwrap();
+
+    std::env::set_var("BRACE_CONDITION_STAGE", "prod");
+
+    let cfg = Config::load("tests/outputs/conditions/config.toml").unwrap();
+
+    assert!(cfg.get::<_, String>("platform").is_err());
+
+    let options = LoadOptions::new().conditions(true);
+    let cfg = Config::load_with("tests/outputs/conditions/config.toml", &options).unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("app")));
+    assert_eq!(cfg.get("platform"), Ok(String::from("native")));
+    assert_eq!(cfg.get("level"), Ok(String::from("warn")));
+    assert!(cfg.get::<_, String>(file::WHEN_KEY).is_err());
+
+    std::fs::write(
+        "tests/outputs/conditions/invalid.toml",
+        "[when.'cfg(target_os'] \nname = \"x\"\n",
+    )
+    .unwrap();
+
+    let err = file::load_with("tests/outputs/conditions/invalid.toml", &options).unwrap_err();
+
+    assert!(matches!(err, file::error::Error::ConditionError(..)));
+}
+
+#[test]
+fn test_file_load_with_profile() {
+    std::fs::create_dir_all("tests/outputs/profile").unwrap();
+    std::fs::write(
+        "tests/outputs/profile/app.toml",
+        "name = \"app\"\nlevel = \"info\"\nport = 80\n",
+    )
+    .unwrap();
+    std::fs::write(
+        "tests/outputs/profile/app.prod.toml",
+        "level = \"warn\"\nport = 443\n",
+    )
+    .unwrap();
+    std::fs::write("tests/outputs/profile/app.local.toml", "port = 8443\n").unwrap();
+
+    let cfg = Config::load_with_profile("tests/outputs/profile/app.toml", "prod").unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("app")));
+    assert_eq!(cfg.get("level"), Ok(String::from("warn")));
+    assert_eq!(cfg.get("port"), Ok(8443));
+
+    let cfg = Config::load_with_profile("tests/outputs/profile/app.toml", "dev").unwrap();
+
+    assert_eq!(cfg.get("level"), Ok(String::from("info")));
+    assert_eq!(cfg.get("port"), Ok(8443));
+    assert!(Config::load_with_profile("tests/outputs/profile/missing.toml", "prod").is_err());
+}
+
+#[test]
+fn test_file_save_redacted() {
+    let mut cfg = brace_config::config! {
+        "database" = {
+            "user" = "app",
+            "password" = "hunter2",
+        },
+    };
+
+    cfg.mark_sensitive("database.password");
+    cfg.save_redacted("tests/outputs/redacted.json").unwrap();
+
+    let saved = Config::load("tests/outputs/redacted.json").unwrap();
+
+    assert_eq!(saved.get("database.user"), Ok(String::from("app")));
+    assert_eq!(saved.get("database.password"), Ok(String::from("***")));
+
+    let string = cfg.to_string_redacted(Format::Toml).unwrap();
+
+    assert!(string.contains("***"));
+    assert!(!string.contains("hunter2"));
+    assert_eq!(cfg.get("database.password"), Ok(String::from("hunter2")));
+}
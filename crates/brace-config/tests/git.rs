@@ -0,0 +1,90 @@
+#![cfg(feature = "git")]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use brace_config::source::{GitSource, Source};
+use brace_config::Config;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
+
+#[test]
+fn test_git_source() {
+    let root = std::env::current_dir().unwrap().join("tests/outputs/git");
+    let repo = root.join("repo");
+    let checkout = root.join("checkout.git");
+
+    let _ = fs::remove_dir_all(&root);
+
+    fs::create_dir_all(repo.join("config")).unwrap();
+    git(&repo, &["init", "--quiet", "--initial-branch", "main"]);
+
+    fs::write(
+        repo.join("config/base.toml"),
+        "name = \"app\"\n[server]\nport = \"80\"\n",
+    )
+    .unwrap();
+    fs::write(repo.join("config/prod.yaml"), "server:\n  port: \"443\"\n").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "--quiet", "-m", "initial"]);
+    git(&repo, &["tag", "v1"]);
+
+    let source = GitSource::new(repo.display().to_string(), &checkout)
+        .file("config/base.toml")
+        .file("config/prod.yaml");
+
+    let cfg = Config::builder()
+        .add_source(source.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("app")));
+    assert_eq!(cfg.get("server.port"), Ok(String::from("443")));
+
+    fs::write(repo.join("config/base.toml"), "name = \"next\"\n").unwrap();
+    git(&repo, &["commit", "--quiet", "-am", "update"]);
+
+    let table = source.collect().unwrap();
+
+    assert_eq!(table.get("name"), Ok(String::from("next")));
+
+    let table = source.clone().reference("v1").collect().unwrap();
+
+    assert_eq!(table.get("name"), Ok(String::from("app")));
+    assert_ne!(
+        source.revision().unwrap(),
+        source.clone().reference("v1").revision().unwrap()
+    );
+    assert!(GitSource::new("repo", &checkout).collect().is_err());
+    assert!(source.file("missing.toml").collect().is_err());
+}
+
+#[test]
+fn test_git_source_option_url() {
+    let root = std::env::current_dir()
+        .unwrap()
+        .join("tests/outputs/git-option");
+    let marker = root.join("marker");
+
+    let _ = fs::remove_dir_all(&root);
+
+    fs::create_dir_all(&root).unwrap();
+
+    let url = format!("--upload-pack=touch {}", marker.display());
+    let source = GitSource::new(url, root.join("checkout.git")).file("config.toml");
+
+    assert!(source.collect().is_err());
+    assert!(!marker.exists());
+    assert!(source.reference("--output=x").revision().is_err());
+}
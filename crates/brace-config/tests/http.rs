@@ -0,0 +1,70 @@
+#![cfg(feature = "http")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread;
+
+use brace_config::Config;
+
+mod common;
+
+fn serve(content_type: &str, body: &str) -> String {
+    let response = common::response(200, &[("Content-Type", content_type)], body);
+
+    common::serve_sequence(vec![response]).0
+}
+
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        match Pin::new(&mut future).as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_http_content_type() {
+    let url = serve("application/json", r#"{"one": "Hello world"}"#);
+    let cfg = Config::load_url(&format!("{}/config", url)).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+}
+
+#[test]
+fn test_http_extension() {
+    let url = serve("text/plain", "one: Hello world\n");
+    let cfg = Config::load_url(&format!("{}/config.yaml?version=1", url)).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+}
+
+#[test]
+fn test_http_async() {
+    let url = serve("text/plain", "one = \"Hello world\"\n");
+    let cfg = block_on(Config::load_url_async(&format!("{}/config", url))).unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+}
+
+#[test]
+fn test_http_error() {
+    assert!(Config::load_url("http://127.0.0.1:1/config.json").is_err());
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "k8s")]
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use brace_config::source::{K8sSource, Source};
+
+mod common;
+
+fn mount(dir: &Path, version: &str, port: &str) {
+    let data = dir.join(version);
+
+    fs::create_dir_all(&data).unwrap();
+    fs::write(data.join("db.url"), "postgres://localhost\n").unwrap();
+    fs::write(data.join("port"), port).unwrap();
+
+    let _ = fs::remove_file(dir.join("..data_tmp"));
+    symlink(version, dir.join("..data_tmp")).unwrap();
+    fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+
+    for name in ["db.url", "port"] {
+        if fs::symlink_metadata(dir.join(name)).is_err() {
+            symlink(Path::new("..data").join(name), dir.join(name)).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_k8s_mount() {
+    let dir = Path::new("tests/outputs/k8s");
+
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    mount(dir, "..2024_01", "8080");
+
+    let source = K8sSource::mount(dir);
+    let table = source.collect().unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("db.url"),
+        Ok(String::from("postgres://localhost"))
+    );
+    assert_eq!(table.get::<_, u16>("port"), Ok(8080));
+    assert!(table.get::<_, String>("..data").is_err());
+
+    let (sender, receiver) = mpsc::channel();
+    let watch = source
+        .key("app")
+        .watch(Duration::from_millis(10), move |table| {
+            let _ = sender.send(table);
+        });
+
+    mount(dir, "..2024_02", "9090");
+
+    let table = receiver
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(table.get::<_, u16>("app.port"), Ok(9090));
+
+    watch.stop();
+}
+
+#[test]
+fn test_k8s_api() {
+    const CONFIG_MAP: &str = r#"{
+        "metadata": {"name": "app", "resourceVersion": "12"},
+        "data": {"log_level": "debug"}
+    }"#;
+    const SECRET: &str = r#"{
+        "metadata": {"name": "app", "resourceVersion": "13"},
+        "data": {"password": "aHVudGVyMg=="}
+    }"#;
+
+    let (endpoint, requests) = common::serve(
+        vec![
+            ("/api/v1/namespaces/prod/configmaps/app", 200, CONFIG_MAP),
+            ("/api/v1/namespaces/prod/secrets/app", 200, SECRET),
+        ],
+        2,
+    );
+    let table = K8sSource::config_map(endpoint.as_str(), "prod", "app")
+        .token("token")
+        .collect()
+        .unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("log_level"),
+        Ok(String::from("debug"))
+    );
+    assert!(requests
+        .recv()
+        .unwrap()
+        .to_ascii_lowercase()
+        .contains("authorization: bearer token"));
+
+    let table = K8sSource::secret(endpoint.as_str(), "prod", "app")
+        .key("secrets")
+        .collect()
+        .unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("secrets.password"),
+        Ok(String::from("hunter2"))
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "http")]
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use brace_config::source::{PollingSource, Source};
+
+mod common;
+
+fn ok(etag: &str, body: &str) -> String {
+    common::response(
+        200,
+        &[("Content-Type", "application/json"), ("ETag", etag)],
+        body,
+    )
+}
+
+fn not_modified() -> String {
+    common::response(304, &[], "")
+}
+
+#[test]
+fn test_poll_source() {
+    let (url, requests) = common::serve_sequence(vec![
+        ok("\"v1\"", "{\"port\": \"80\"}"),
+        not_modified(),
+        ok("\"v2\"", "{\"port\": \"80\"}"),
+        ok("\"v3\"", "{\"port\": \"8080\"}"),
+    ]);
+    let source = PollingSource::new(url);
+
+    assert_eq!(source.collect().unwrap().get::<_, u16>("port"), Ok(80));
+    assert_eq!(source.collect().unwrap().get::<_, u16>("port"), Ok(80));
+    assert_eq!(source.refresh().unwrap(), None);
+    assert_eq!(
+        source.refresh().unwrap().unwrap().get::<_, u16>("port"),
+        Ok(8080)
+    );
+
+    let requests = requests.iter().take(4).collect::<Vec<_>>();
+
+    assert!(!requests[0].to_ascii_lowercase().contains("if-none-match"));
+    assert!(requests[1]
+        .to_ascii_lowercase()
+        .contains("if-none-match: \"v1\""));
+    assert!(requests[3]
+        .to_ascii_lowercase()
+        .contains("if-none-match: \"v2\""));
+}
+
+#[test]
+fn test_poll_source_watch() {
+    let (url, _) = common::serve_sequence(vec![
+        ok("\"v1\"", "{\"port\": \"80\"}"),
+        not_modified(),
+        ok("\"v2\"", "{\"port\": \"8080\"}"),
+    ]);
+    let source = PollingSource::new(url);
+    let (sender, receiver) = mpsc::channel();
+
+    source.collect().unwrap();
+
+    let watch = source.poll(Duration::from_millis(10), move |table| {
+        let _ = sender.send(table);
+    });
+    let table = receiver
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(table.get::<_, u16>("port"), Ok(8080));
+
+    watch.stop();
+}
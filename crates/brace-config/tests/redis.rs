@@ -0,0 +1,165 @@
+#![cfg(feature = "redis")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use brace_config::source::{RedisSource, Source};
+
+fn bulk(value: &str) -> String {
+    format!("${}\r\n{}\r\n", value.len(), value)
+}
+
+fn array(items: &[String]) -> String {
+    format!("*{}\r\n{}", items.len(), items.concat())
+}
+
+fn command(reader: &mut BufReader<TcpStream>) -> Option<Vec<String>> {
+    let mut line = String::new();
+
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+
+    let len = line.trim()[1..].parse::<usize>().unwrap();
+    let mut args = Vec::new();
+
+    for _ in 0..len {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+
+        let mut buf = vec![0; line.trim()[1..].parse::<usize>().unwrap() + 2];
+
+        reader.read_exact(&mut buf).unwrap();
+        buf.truncate(buf.len() - 2);
+        args.push(String::from_utf8(buf).unwrap());
+    }
+
+    Some(args)
+}
+
+fn serve() -> (String, mpsc::Receiver<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let sender = sender.clone();
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            thread::spawn(move || {
+                while let Some(args) = command(&mut reader) {
+                    let reply = match args[0].as_str() {
+                        "AUTH" if args[1] == "wrong" => {
+                            String::from("-WRONGPASS invalid password\r\n")
+                        }
+                        "AUTH" | "SELECT" => String::from("+OK\r\n"),
+                        "HGETALL" => {
+                            array(&[bulk("beta"), bulk("true"), bulk("limits:rate"), bulk("100")])
+                        }
+                        "SCAN" => {
+                            array(&[
+                            bulk("0"),
+                            array(&[bulk("app:db:url"), bulk("app:list"), bulk("app:port")]),
+                        ])
+                        }
+                        "GET" if args[1] == "app:port" => bulk("8080"),
+                        "GET" if args[1] == "app:list" => String::from(
+                            "-WRONGTYPE Operation against a key holding the wrong kind of value\r\n",
+                        ),
+                        "GET" => bulk("postgres://localhost"),
+                        "PSUBSCRIBE" => {
+                            array(&[bulk("psubscribe"), bulk(&args[1]), String::from(":1\r\n")])
+                                + &array(&[
+                                    bulk("pmessage"),
+                                    bulk(&args[1]),
+                                    bulk("__keyspace@0__:app:port"),
+                                    bulk("set"),
+                                ])
+                        }
+                        _ => String::from("-ERR unknown command\r\n"),
+                    };
+
+                    let (head, tail) = match args[0].as_str() {
+                        "PSUBSCRIBE" => reply.split_at(reply.len() - 8),
+                        _ => reply.split_at(reply.len()),
+                    };
+
+                    let _ = sender.send(args);
+
+                    stream.write_all(head.as_bytes()).unwrap();
+
+                    if !tail.is_empty() {
+                        thread::sleep(Duration::from_millis(250));
+                        stream.write_all(tail.as_bytes()).unwrap();
+                    }
+                }
+            });
+        }
+    });
+
+    (addr.to_string(), receiver)
+}
+
+#[test]
+fn test_redis_hash() {
+    let (address, commands) = serve();
+    let table = RedisSource::hash(address, "flags")
+        .password("secret")
+        .database(2)
+        .collect()
+        .unwrap();
+
+    assert_eq!(table.get::<_, bool>("beta"), Ok(true));
+    assert_eq!(table.get::<_, u32>("limits.rate"), Ok(100));
+    assert_eq!(
+        commands.iter().take(3).collect::<Vec<_>>(),
+        vec![
+            vec![String::from("AUTH"), String::from("secret")],
+            vec![String::from("SELECT"), String::from("2")],
+            vec![String::from("HGETALL"), String::from("flags")],
+        ]
+    );
+}
+
+#[test]
+fn test_redis_prefix() {
+    let (address, _) = serve();
+    let source = RedisSource::prefix(format!("redis://{}", address), "app:");
+    let table = source.collect().unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("db.url"),
+        Ok(String::from("postgres://localhost"))
+    );
+    assert_eq!(table.get::<_, u16>("port"), Ok(8080));
+
+    let (sender, receiver) = mpsc::channel();
+    let watch = source.watch(move |table| {
+        let _ = sender.send(table);
+    });
+    let table = receiver
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(table.get::<_, u16>("port"), Ok(8080));
+
+    watch.stop();
+}
+
+#[test]
+fn test_redis_error() {
+    let (address, _) = serve();
+    let err = RedisSource::hash(address, "flags")
+        .password("wrong")
+        .collect()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "redis error: WRONGPASS invalid password");
+    assert!(RedisSource::hash("127.0.0.1:1", "flags").collect().is_err());
+}
@@ -0,0 +1,538 @@
+use brace_config::config;
+use brace_config::schema::{Schema, Type};
+
+#[test]
+fn test_schema_config_validate() {
+    let cfg = config! {
+        "server" = {
+            "host" = "localhost",
+            "port" = "http",
+        },
+    };
+    let schema = Schema::new()
+        .key("server.host", Type::String)
+        .required()
+        .key("server.port", Type::U16)
+        .required()
+        .key("server.workers", Type::U32)
+        .required();
+
+    let report = cfg.validate(&schema);
+
+    assert!(!report.is_valid());
+    assert_eq!(report.errors().len(), 2);
+    assert_eq!(
+        report.errors()[0].to_string(),
+        "server.port: expected u16, found 'http'"
+    );
+    assert_eq!(
+        report.errors()[1].to_string(),
+        "server.workers: missing required key"
+    );
+    assert_eq!(
+        cfg.lookup("server.host")
+            .unwrap()
+            .as_entry()
+            .unwrap()
+            .value(),
+        "localhost"
+    );
+}
+
+#[test]
+#[cfg(feature = "json-schema")]
+fn test_schema_json_schema() {
+    use brace_config::schema::JsonSchema;
+
+    let schema = JsonSchema::parse(
+        r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "required": ["name", "server"],
+            "properties": {
+                "name": { "type": "string" },
+                "server": {
+                    "type": "object",
+                    "properties": {
+                        "host": { "type": "string" },
+                        "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+                        "tls": { "type": "boolean" }
+                    },
+                    "additionalProperties": false
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let cfg = config! {
+        "name" = "app",
+        "server" = {
+            "host" = "localhost",
+            "port" = 8080,
+            "tls" = true,
+        },
+    };
+
+    assert!(cfg.validate_json_schema(&schema).is_valid());
+
+    let cfg = config! {
+        "server" = {
+            "port" = 70000,
+            "tls" = "maybe",
+            "extra" = "x",
+        },
+    };
+
+    let report = cfg.validate_json_schema(&schema);
+    let mut paths = report
+        .errors()
+        .iter()
+        .map(|error| error.path())
+        .collect::<Vec<_>>();
+
+    paths.sort_unstable();
+
+    assert_eq!(paths, vec!["", "server", "server.port", "server.tls"]);
+    assert!(JsonSchema::parse(r#"{"type": 5}"#).is_err());
+}
+
+#[test]
+#[cfg(feature = "json-schema")]
+fn test_schema_json_schema_refs() {
+    use brace_config::schema::JsonSchema;
+
+    let schema = JsonSchema::parse(
+        r##"{
+            "type": "object",
+            "properties": {
+                "inner": { "$ref": "#/$defs/Inner" },
+                "limit": { "oneOf": [{ "type": "integer" }, { "type": "null" }] }
+            },
+            "$defs": {
+                "Inner": {
+                    "type": "object",
+                    "properties": { "version": { "type": "string" } }
+                }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    assert!(config! { "inner" = { "version" = "1" }, "limit" = "10" }
+        .validate_json_schema(&schema)
+        .is_valid());
+    assert!(!config! { "inner" = { "version" = ["1"] } }
+        .validate_json_schema(&schema)
+        .is_valid());
+}
+
+#[test]
+fn test_schema_constraints() {
+    let schema = Schema::new()
+        .key("server.port", Type::U32)
+        .range(1, 65535)
+        .key("log.level", Type::String)
+        .one_of(vec!["debug", "info", "warn"])
+        .key("name", Type::String)
+        .length(2, 8)
+        .key("hosts", Type::Array)
+        .items(1, 3);
+
+    let cfg = config! {
+        "server" = { "port" = 8080 },
+        "log" = { "level" = "info" },
+        "name" = "app",
+        "hosts" = ["a"],
+    };
+
+    assert!(cfg.validate(&schema).is_valid());
+
+    let cfg = config! {
+        "server" = { "port" = 70000 },
+        "log" = { "level" = "trace" },
+        "name" = "application",
+        "hosts" = [],
+    };
+
+    let errors = cfg
+        .validate(&schema)
+        .errors()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        vec![
+            "server.port: value '70000' is greater than the maximum of 65535",
+            "log.level: value 'trace' is not one of 'debug', 'info', 'warn'",
+            "name: value 'application' is longer than 8 characters",
+            "hosts: array has 0 items, expected at least 1",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_report() {
+    let schema = Schema::new()
+        .key("name", Type::String)
+        .required()
+        .key("workers", Type::U8)
+        .warning();
+
+    let report = config! { "workers" = 1000 }.validate(&schema);
+
+    assert!(!report.is_valid());
+    assert_eq!(report.issues().len(), 2);
+    assert_eq!(
+        report.to_string(),
+        "configuration has 1 error and 1 warning:\n  error: name: missing required key\n  warning: workers: expected u8, found '1000'"
+    );
+
+    let report = config! { "name" = "app", "workers" = 1000 }.validate(&schema);
+
+    assert!(report.is_valid());
+    assert_eq!(report.warnings().len(), 1);
+}
+
+#[test]
+#[cfg(all(feature = "json-schema", feature = "schemars"))]
+fn test_schema_struct_schema() {
+    use brace_config::schema::{struct_schema, JsonSchema};
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    assert_eq!(
+        struct_schema::<Server>()["properties"]["port"]["type"],
+        "integer"
+    );
+
+    let schema = JsonSchema::for_type::<Server>().unwrap();
+
+    assert!(config! { "host" = "localhost", "port" = 8080 }
+        .validate_json_schema(&schema)
+        .is_valid());
+    assert!(!config! { "host" = "localhost", "port" = "http" }
+        .validate_json_schema(&schema)
+        .is_valid());
+}
+
+#[test]
+#[cfg(all(feature = "json-schema", feature = "schemars"))]
+fn test_schema_struct_schema_nested() {
+    use brace_config::schema::JsonSchema;
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Inner {
+        version: String,
+        port: u16,
+    }
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Outer {
+        inner: Inner,
+        backup: Option<Inner>,
+    }
+
+    let schema = JsonSchema::for_type::<Outer>().unwrap();
+
+    assert!(schema.schema()["properties"]["inner"].get("$ref").is_some());
+    assert!(config! {
+        "inner" = { "version" = "1", "port" = 8080 },
+        "backup" = { "version" = "2", "port" = "8081" },
+    }
+    .validate_json_schema(&schema)
+    .is_valid());
+    assert!(!config! { "inner" = { "version" = "1", "port" = "http" } }
+        .validate_json_schema(&schema)
+        .is_valid());
+}
+
+#[test]
+#[cfg(feature = "json-schema")]
+fn test_schema_generated_json_schema() {
+    use brace_config::schema::JsonSchema;
+
+    let schema = Schema::new()
+        .key("server.port", Type::U16)
+        .required()
+        .range(1, 9999);
+    let json = JsonSchema::new(schema.to_json_schema()).unwrap();
+
+    assert!(config! { "server" = { "port" = 8080 } }
+        .validate_json_schema(&json)
+        .is_valid());
+    assert!(!config! { "server" = { "port" = 10000 } }
+        .validate_json_schema(&json)
+        .is_valid());
+}
+
+#[test]
+fn test_schema_validators() {
+    let mut calls = 0;
+    let schema = Schema::new()
+        .validator("servers.*.port", |value| {
+            match value.as_entry().map(|entry| entry.value()) {
+                Some(port) if port.starts_with('8') => Ok(()),
+                _ => Err(String::from("ports must be in the 8000 range")),
+            }
+        })
+        .validator("**.secret", move |_| {
+            calls += 1;
+            Err(format!("secrets must not be inline (seen {})", calls))
+        });
+
+    let cfg = config! {
+        "servers" = [
+            { "port" = 8080 },
+            { "port" = 9090 },
+        ],
+        "db" = { "secret" = "hunter2" },
+    };
+
+    let errors = cfg
+        .validate(&schema)
+        .errors()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        vec![
+            "servers.1.port: ports must be in the 8000 range",
+            "db.secret: secrets must not be inline (seen 1)",
+        ]
+    );
+    assert!(cfg.validate(&schema).errors()[1]
+        .message()
+        .ends_with("(seen 2)"));
+}
+
+#[test]
+fn test_schema_validate_on_set() {
+    let schema = Schema::new()
+        .key("server.port", Type::U16)
+        .range(1, 9999)
+        .key("name", Type::String)
+        .required();
+    let mut cfg = config! { "server" = { "port" = 8080 } };
+
+    cfg.attach_schema(schema.clone(), false);
+    cfg.set("server.port", "http").unwrap();
+
+    assert_eq!(cfg.get("server.port"), Ok(String::from("http")));
+    assert!(cfg.schema().is_some());
+
+    cfg.attach_schema(schema, true);
+
+    let err = cfg.set("server.port", 100000).unwrap_err();
+
+    assert_eq!(err.to_string(), "server.port: expected u16, found '100000'");
+    assert_eq!(cfg.get("server.port"), Ok(String::from("http")));
+
+    cfg.set("server.port", 9000).unwrap();
+    cfg.set("server", brace_config::table! { "port" = 9001 })
+        .unwrap();
+
+    assert_eq!(cfg.get("server.port"), Ok(9001));
+    assert!(cfg
+        .set("server", brace_config::table! { "port" = 0 })
+        .is_err());
+    assert!(cfg.detach_schema().is_some());
+
+    cfg.set("server.port", 0).unwrap();
+}
+
+#[test]
+fn test_schema_check_against() {
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Server {
+        host: String,
+        port: u16,
+        workers: u32,
+    }
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct App {
+        name: String,
+        server: Server,
+        ports: Vec<u16>,
+    }
+
+    let cfg = config! {
+        "server" = {
+            "host" = "localhost",
+            "port" = "http",
+        },
+        "ports" = [80, "https", 8080],
+    };
+
+    let report = cfg.check_against::<App>();
+    let mut paths = report
+        .errors()
+        .iter()
+        .map(|error| error.path().to_owned())
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    assert!(!report.is_valid());
+    assert_eq!(
+        paths,
+        vec!["name", "ports.1", "server.port", "server.workers"]
+    );
+    assert!(report
+        .errors()
+        .iter()
+        .any(|error| error.path() == "name" && error.message() == "missing required key"));
+    assert_eq!(cfg.get("server.port"), Ok(String::from("http")));
+
+    let cfg = config! {
+        "name" = "app",
+        "server" = {
+            "host" = "localhost",
+            "port" = 8080,
+            "workers" = 4,
+        },
+        "ports" = [80],
+    };
+
+    assert!(cfg.check_against::<App>().is_valid());
+}
+
+#[test]
+fn test_schema_apply_defaults() {
+    let schema = Schema::new()
+        .key("server.host", Type::String)
+        .default_value("localhost")
+        .key("server.port", Type::U16)
+        .default_value(8080)
+        .key("debug", Type::Bool)
+        .default_value(false)
+        .key("name", Type::String);
+    let mut cfg = config! {
+        "server" = { "port" = 9000 },
+    };
+
+    schema.apply_defaults(&mut cfg).unwrap();
+
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+    assert_eq!(cfg.get("server.port"), Ok(9000));
+    assert_eq!(cfg.get("debug"), Ok(false));
+    assert!(cfg.lookup("name").is_none());
+}
+
+#[test]
+fn test_schema_semantic() {
+    let schema = Schema::new()
+        .key("server.url", Type::String)
+        .url()
+        .key("server.bind", Type::String)
+        .ip()
+        .key("server.port", Type::U16)
+        .port()
+        .key("admin", Type::String)
+        .email()
+        .key("tls.cert", Type::String)
+        .readable_file()
+        .key("data", Type::String)
+        .path_exists();
+
+    let cfg = config! {
+        "server" = {
+            "url" = "https://example.com",
+            "bind" = "0.0.0.0",
+            "port" = 8080,
+        },
+        "admin" = "admin@example.com",
+        "tls" = { "cert" = "Cargo.toml" },
+        "data" = "tests",
+    };
+
+    assert!(cfg.validate(&schema).is_valid());
+
+    let cfg = config! {
+        "server" = {
+            "url" = "example.com",
+            "bind" = "localhost",
+            "port" = 0,
+        },
+        "admin" = "admin",
+        "tls" = { "cert" = "tests" },
+        "data" = "tests/missing",
+    };
+
+    let paths = cfg
+        .validate(&schema)
+        .errors()
+        .iter()
+        .map(|error| error.path().to_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        vec![
+            "server.url",
+            "server.bind",
+            "server.port",
+            "admin",
+            "tls.cert",
+            "data"
+        ]
+    );
+}
+
+#[test]
+fn test_schema_rules() {
+    let schema = Schema::new()
+        .key("tls.enabled", Type::Bool)
+        .required_if("tls.cert", "tls.enabled", true)
+        .less_or_equal("pool.min", "pool.max")
+        .rule("server", |table| {
+            match (table.lookup("server.socket"), table.lookup("server.port")) {
+                (Some(_), Some(_)) => Err(String::from("socket and port are exclusive")),
+                _ => Ok(()),
+            }
+        });
+
+    let cfg = config! {
+        "tls" = { "enabled" = true, "cert" = "cert.pem" },
+        "pool" = { "min" = 1, "max" = 8 },
+        "server" = { "port" = 80 },
+    };
+
+    assert!(cfg.validate(&schema).is_valid());
+
+    let cfg = config! {
+        "tls" = { "enabled" = true },
+        "pool" = { "min" = 10, "max" = 8 },
+        "server" = { "port" = 80, "socket" = "/tmp/app.sock" },
+    };
+
+    let report = cfg.validate(&schema);
+    let errors = report
+        .errors()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        vec![
+            "tls.cert: required when 'tls.enabled' is 'true'",
+            "pool.min: value 10 is greater than 'pool.max' (8)",
+            "server: socket and port are exclusive",
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "secrets-manager")]
+
+use std::time::Duration;
+
+use brace_config::source::{Credentials, SecretsManagerSource, Source};
+
+mod common;
+
+const DATABASE: &str = r#"{
+    "Name": "prod/db",
+    "SecretString": "{\"db\": {\"user\": \"admin\", \"password\": \"hunter2\"}}"
+}"#;
+
+const TOKEN: &str = r#"{"Name": "prod/token", "SecretString": "abc123"}"#;
+
+#[test]
+fn test_secrets_manager_source() {
+    let (endpoint, requests) = common::serve(
+        vec![
+            ("\"SecretId\":\"prod/db\"", 200, DATABASE),
+            ("\"SecretId\":\"prod/token\"", 200, TOKEN),
+        ],
+        4,
+    );
+    let source = SecretsManagerSource::new()
+        .secret("prod/db")
+        .secret_at("prod/token", "api.token")
+        .endpoint(endpoint)
+        .credentials(Credentials::new("AKIDEXAMPLE", "secret"))
+        .refresh_interval(Duration::from_secs(60));
+    let table = source.collect().unwrap();
+
+    assert_eq!(table.get::<_, String>("db.user"), Ok(String::from("admin")));
+    assert_eq!(
+        table.get::<_, String>("db.password"),
+        Ok(String::from("hunter2"))
+    );
+    assert_eq!(
+        table.get::<_, String>("api.token"),
+        Ok(String::from("abc123"))
+    );
+
+    assert_eq!(source.collect().unwrap(), table);
+    assert_eq!(requests.try_iter().count(), 2);
+
+    source.invalidate();
+
+    assert_eq!(source.collect().unwrap(), table);
+    assert!(requests
+        .recv()
+        .unwrap()
+        .to_ascii_lowercase()
+        .contains("x-amz-target: secretsmanager.getsecretvalue"));
+}
+
+#[test]
+fn test_secrets_manager_source_plain() {
+    let (endpoint, _) = common::serve(vec![("POST / ", 200, TOKEN)], 1);
+    let err = SecretsManagerSource::new()
+        .secret("prod/token")
+        .endpoint(endpoint)
+        .credentials(Credentials::new("AKIDEXAMPLE", "secret"))
+        .collect()
+        .unwrap_err();
+
+    assert!(err.to_string().contains("needs a key"));
+}
+
+#[test]
+fn test_secrets_manager_source_session() {
+    let (endpoint, requests) = common::serve(vec![("POST / ", 200, DATABASE)], 1);
+    let credentials = Credentials::new("AKIDEXAMPLE", "secret").session_token("session");
+    let table = SecretsManagerSource::new()
+        .secret("prod/db")
+        .endpoint(endpoint)
+        .credentials(credentials)
+        .collect()
+        .unwrap();
+    let request = requests.recv().unwrap();
+
+    assert_eq!(table.get::<_, String>("db.user"), Ok(String::from("admin")));
+    assert!(request
+        .to_ascii_lowercase()
+        .contains("x-amz-security-token: session"));
+    assert!(request
+        .contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target"));
+}
@@ -0,0 +1,425 @@
+use brace_config::source::{DirSource, FileSource, MemorySource, Metadata, Source, SystemdSource};
+use brace_config::value::{ArrayMerge, Error, TableMerge, DELETE_MARKER};
+use brace_config::{table, Config, ConfigBuilder, InterpolationMode, Table};
+
+struct StaticSource(&'static str, &'static str);
+
+impl Source for StaticSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("static")
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        let mut table = Table::new();
+
+        table.set(self.0, self.1)?;
+
+        Ok(table)
+    }
+}
+
+struct FailingSource;
+
+impl Source for FailingSource {
+    fn metadata(&self) -> Metadata {
+        Metadata::new("failing").with_origin("nowhere")
+    }
+
+    fn collect(&self) -> Result<Table, Error> {
+        Err(Error::custom("unavailable"))
+    }
+}
+
+#[test]
+fn test_source_builder() {
+    let cfg = Config::builder()
+        .add_source(StaticSource("one", "default"))
+        .add_source(FileSource::new("tests/assets/example.toml"))
+        .add_source(FileSource::new("tests/assets/missing.toml").optional())
+        .add_boxed_source(Box::new(StaticSource("two.b", "override")))
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("one"), Ok(String::from("Hello world")));
+    assert_eq!(cfg.get("two.a"), Ok(String::from("first")));
+    assert_eq!(cfg.get("two.b"), Ok(String::from("override")));
+    assert_eq!(cfg.get("three.2"), Ok(150));
+}
+
+#[test]
+fn test_source_metadata() {
+    let builder = ConfigBuilder::new()
+        .add_source(FileSource::new("config.toml"))
+        .add_source(StaticSource("one", "two"));
+
+    assert_eq!(
+        builder.sources(),
+        vec![
+            Metadata::new("file").with_origin("config.toml"),
+            Metadata::new("static"),
+        ]
+    );
+}
+
+#[test]
+fn test_source_errors() {
+    let err = ConfigBuilder::new()
+        .add_source(FileSource::new("tests/assets/missing.toml"))
+        .build()
+        .unwrap_err();
+
+    assert!(err
+        .to_string()
+        .starts_with("file source 'tests/assets/missing.toml': "));
+
+    let err = ConfigBuilder::new()
+        .add_source(FailingSource)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "failing source 'nowhere': unavailable");
+}
+
+#[test]
+fn test_source_memory() {
+    let cfg = Config::builder()
+        .add_source(MemorySource::from(table! {
+            "name" = "base",
+            "server" = {
+                "host" = "localhost",
+                "port" = 8080,
+            },
+        }))
+        .add_source(
+            MemorySource::from(table! {
+                "server" = {
+                    "port" = 9090,
+                },
+            })
+            .name("overrides"),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("name"), Ok(String::from("base")));
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+    assert_eq!(cfg.get("server.port"), Ok(9090));
+
+    let metadata = MemorySource::default().name("overrides").metadata();
+
+    assert_eq!(metadata.name, "memory");
+    assert_eq!(metadata.origin, Some(String::from("overrides")));
+}
+
+#[test]
+fn test_source_systemd() {
+    let dir = std::env::current_dir()
+        .unwrap()
+        .join("tests/outputs/credentials");
+
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("db.password"), "hunter2\n").unwrap();
+    std::fs::write(dir.join("api_token"), "abc123").unwrap();
+
+    let table = SystemdSource::new().dir(&dir).collect().unwrap();
+
+    assert_eq!(table.get("db.password"), Ok(String::from("hunter2")));
+    assert_eq!(table.get("api_token"), Ok(String::from("abc123")));
+
+    let table = SystemdSource::new()
+        .dir(&dir)
+        .key("secrets")
+        .credential("api_token", "api.token")
+        .only(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(table.get("secrets.api.token"), Ok(String::from("abc123")));
+    assert!(table.get::<_, String>("secrets.db.password").is_err());
+
+    let missing = SystemdSource::new().dir(dir.join("missing"));
+
+    assert!(missing.collect().is_err());
+}
+
+#[test]
+fn test_source_dir() {
+    let dir = std::env::current_dir()
+        .unwrap()
+        .join("tests/outputs/conf.d");
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for index in 0..32 {
+        std::fs::write(
+            dir.join(format!("{:02}-fragment.toml", index)),
+            format!(
+                "last = \"{}\"\n[fragments]\nf{} = \"{}\"\n",
+                index, index, index
+            ),
+        )
+        .unwrap();
+    }
+
+    std::fs::write(
+        dir.join("50-server.json"),
+        r#"{ "server": { "port": 8080 } }"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("README"), "ignored").unwrap();
+    std::fs::write(dir.join(".hidden.toml"), "last = \"hidden\"").unwrap();
+
+    let table = DirSource::new(&dir).collect().unwrap();
+
+    assert_eq!(table.get("last"), Ok(31));
+    assert_eq!(table.get("fragments.f0"), Ok(0));
+    assert_eq!(table.get("fragments.f31"), Ok(31));
+    assert_eq!(table.get("server.port"), Ok(8080));
+
+    std::fs::write(dir.join("99-invalid.json"), "{").unwrap();
+
+    assert!(DirSource::new(&dir).collect().is_err());
+    assert!(DirSource::new(dir.join("missing")).collect().is_err());
+    assert_eq!(
+        DirSource::new(dir.join("missing")).optional().collect(),
+        Ok(Table::new())
+    );
+
+    std::fs::remove_file(dir.join("99-invalid.json")).unwrap();
+}
+
+#[test]
+fn test_source_dir_glob() {
+    let dir = std::env::current_dir()
+        .unwrap()
+        .join("tests/outputs/conf.glob");
+
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("10-server.toml"), "port = \"80\"\n").unwrap();
+    std::fs::write(dir.join("20-server.toml"), "port = \"8080\"\n").unwrap();
+    std::fs::write(dir.join("30-extra.json"), r#"{ "port": "9090" }"#).unwrap();
+
+    let source = DirSource::glob(dir.join("*-server.toml"));
+
+    assert_eq!(source.collect().unwrap().get("port"), Ok(8080));
+    assert_eq!(source.fragments().unwrap().len(), 2);
+    assert_eq!(
+        source.metadata().origin,
+        Some(dir.join("*-server.toml").display().to_string())
+    );
+
+    let err = Config::builder()
+        .add_source(source)
+        .strict(true)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "conflicting values for key 'port' in dir source '{}' and dir source '{}'",
+            dir.join("10-server.toml").display(),
+            dir.join("20-server.toml").display()
+        )
+    );
+
+    let cfg = Config::builder()
+        .add_source(DirSource::glob(dir.join("*.json")))
+        .add_source(DirSource::glob(dir.join("1*.toml")))
+        .strict(true)
+        .keep_layers(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("port"), Ok(80));
+    assert_eq!(cfg.layers().len(), 2);
+}
+
+#[test]
+fn test_source_array_merge() {
+    let build = |arrays| {
+        Config::builder()
+            .array_merge(arrays)
+            .add_source(MemorySource::from(table! { "list" = ["a", "b"] }))
+            .add_source(MemorySource::from(table! { "list" = ["c"] }))
+            .build()
+            .unwrap()
+            .get::<_, Vec<String>>("list")
+            .unwrap()
+    };
+
+    assert_eq!(build(ArrayMerge::Replace), vec!["c"]);
+    assert_eq!(build(ArrayMerge::Append), vec!["a", "b", "c"]);
+    assert_eq!(build(ArrayMerge::Index), vec!["c", "b"]);
+
+    let cfg = Config::builder()
+        .merge_strategy("middleware", ArrayMerge::Append)
+        .table_strategy("log", TableMerge::Replace)
+        .add_source(MemorySource::from(
+            table! { "middleware" = ["auth"], "list" = ["a"], "log" = { "level" = "info", "file" = "a.log" } },
+        ))
+        .add_source(MemorySource::from(
+            table! { "middleware" = ["cors"], "list" = ["b"], "log" = { "level" = "debug" } },
+        ))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        cfg.get("middleware"),
+        Ok(vec![String::from("auth"), String::from("cors")])
+    );
+    assert_eq!(cfg.get("list"), Ok(vec![String::from("b")]));
+    assert_eq!(cfg.get("log.level"), Ok(String::from("debug")));
+    assert!(cfg.get::<_, String>("log.file").is_err());
+}
+
+#[test]
+fn test_source_deletions() {
+    std::fs::create_dir_all("tests/outputs/deletions").unwrap();
+    std::fs::write(
+        "tests/outputs/deletions/base.toml",
+        "debug = \"true\"\n[server]\nhost = \"localhost\"\ntls = \"on\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        "tests/outputs/deletions/local.toml",
+        format!(
+            "debug = \"{}\"\n[server]\ntls = \"{}\"\n",
+            DELETE_MARKER, DELETE_MARKER
+        ),
+    )
+    .unwrap();
+
+    let build = |deletions| {
+        Config::builder()
+            .add_source(FileSource::new("tests/outputs/deletions/base.toml"))
+            .add_source(FileSource::new("tests/outputs/deletions/local.toml"))
+            .deletions(deletions)
+            .build()
+            .unwrap()
+    };
+
+    let cfg = build(true);
+
+    assert!(cfg.get::<_, String>("debug").is_err());
+    assert!(cfg.get::<_, String>("server.tls").is_err());
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+
+    let cfg = build(false);
+
+    assert_eq!(cfg.get("debug"), Ok(String::from(DELETE_MARKER)));
+    assert_eq!(cfg.get("server.tls"), Ok(String::from(DELETE_MARKER)));
+    assert_eq!(cfg.get("server.host"), Ok(String::from("localhost")));
+}
+
+#[test]
+fn test_source_layers() {
+    let builder = Config::builder()
+        .add_source(MemorySource::from(table! { "name" = "base", "port" = 80 }).name("defaults"))
+        .add_source(MemorySource::from(table! { "port" = 8080 }).name("overrides"));
+
+    assert!(builder.build().unwrap().layers().is_empty());
+
+    let mut cfg = builder.keep_layers(true).build().unwrap();
+
+    assert_eq!(cfg.layers().len(), 2);
+    assert_eq!(cfg.layer("defaults").unwrap().get("port"), Ok(80));
+    assert_eq!(cfg.layer("overrides").unwrap().get("port"), Ok(8080));
+    assert!(cfg.layer("missing").is_none());
+
+    let provenance = cfg
+        .provenance("port")
+        .iter()
+        .map(|layer| layer.metadata().origin.clone().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(provenance, vec!["defaults", "overrides"]);
+    assert_eq!(cfg.provenance("name").len(), 1);
+
+    cfg.layer_mut("overrides")
+        .unwrap()
+        .set("port", 9090)
+        .unwrap();
+    cfg.remerge();
+
+    assert_eq!(cfg.get("port"), Ok(9090));
+    assert_eq!(cfg.get("name"), Ok(String::from("base")));
+}
+
+#[test]
+fn test_source_strict() {
+    let builder = Config::builder()
+        .strict(true)
+        .add_source(MemorySource::from(table! { "name" = "base" }).name("defaults"))
+        .add_source(MemorySource::from(table! { "name" = "app" }).name("env"))
+        .add_source_with_priority(
+            MemorySource::from(table! { "port" = 80, "host" = "a" }).name("conf.d/a"),
+            1,
+        )
+        .add_source_with_priority(
+            MemorySource::from(table! { "port" = 8080, "host" = "a" }).name("conf.d/b"),
+            1,
+        )
+        .add_source_with_priority(
+            MemorySource::from(table! { "host" = "a" }).name("conf.d/c"),
+            1,
+        )
+        .add_source_with_priority(
+            MemorySource::from(table! { "port" = 9090 }).name("overrides"),
+            2,
+        );
+
+    assert_eq!(
+        builder.build().unwrap_err().to_string(),
+        "conflicting values for key 'port' in memory source 'conf.d/a' and memory source 'conf.d/b'"
+    );
+
+    let cfg = builder.strict(false).build().unwrap();
+    let conflicts = cfg.conflicts();
+
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].key, "port");
+    assert_eq!(conflicts[0].first.origin, Some(String::from("conf.d/a")));
+    assert_eq!(conflicts[0].second.origin, Some(String::from("conf.d/b")));
+    assert_eq!(cfg.get("port"), Ok(9090));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_source_allow_commands() {
+    let cfg = Config::builder()
+        .add_source(StaticSource("version", "${cmd:echo 1.2.3}"))
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("version"), Ok(String::from("${cmd:echo 1.2.3}")));
+
+    let cfg = Config::builder()
+        .add_source(StaticSource("version", "${cmd:echo 1.2.3}"))
+        .allow_commands(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.get("version"), Ok(String::from("1.2.3")));
+}
+
+#[test]
+fn test_source_interpolation_mode() {
+    let cfg = Config::builder()
+        .add_source(StaticSource("host", "localhost"))
+        .add_source(StaticSource("url", "${host}:8080"))
+        .interpolation_mode(InterpolationMode::Eager)
+        .build()
+        .unwrap();
+
+    assert_eq!(cfg.interpolation_mode(), InterpolationMode::Eager);
+    assert_eq!(cfg.get("url"), Ok(String::from("localhost:8080")));
+
+    let err = Config::builder()
+        .add_source(StaticSource("url", "${host}:8080"))
+        .interpolation_mode(InterpolationMode::Eager)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "unknown reference 'host'");
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "sql-sqlite")]
+
+use brace_config::source::{Source, SqlSource};
+use sqlx::{Connection, SqliteConnection};
+
+fn setup(path: &str) -> String {
+    let _ = std::fs::remove_file(path);
+    let url = format!("sqlite://{}?mode=rwc", path);
+
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut connection = SqliteConnection::connect(&url).await.unwrap();
+
+            sqlx::query("CREATE TABLE tenant_settings (tenant TEXT, name TEXT, setting TEXT)")
+                .execute(&mut connection)
+                .await
+                .unwrap();
+            sqlx::query(
+                "INSERT INTO tenant_settings VALUES
+                    ('acme', 'db.url', 'postgres://acme'),
+                    ('acme', 'limits.rate', '100'),
+                    ('acme', 'legacy', NULL),
+                    ('other', 'db.url', 'postgres://other')",
+            )
+            .execute(&mut connection)
+            .await
+            .unwrap();
+
+            connection.close().await.unwrap();
+        });
+
+    url
+}
+
+#[test]
+fn test_sql_source() {
+    let url = setup("tests/outputs/settings.db");
+    let source = SqlSource::new(url)
+        .table("tenant_settings")
+        .columns("name", "setting")
+        .filter("tenant", "acme");
+    let table = source.collect().unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("db.url"),
+        Ok(String::from("postgres://acme"))
+    );
+    assert_eq!(table.get::<_, u32>("limits.rate"), Ok(100));
+    assert!(table.get::<_, String>("legacy").is_err());
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    assert_eq!(runtime.block_on(source.load()).unwrap(), table);
+    assert_eq!(runtime.block_on(async { source.collect() }).unwrap(), table);
+}
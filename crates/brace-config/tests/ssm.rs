@@ -0,0 +1,77 @@
+#![cfg(feature = "ssm")]
+
+use brace_config::source::{Credentials, Source, SsmSource};
+
+mod common;
+
+const FIRST: &str = r#"{
+    "Parameters": [
+        {"Name": "/app/db/url", "Type": "String", "Value": "postgres://localhost"},
+        {"Name": "/app/db/password", "Type": "SecureString", "Value": "hunter2"}
+    ],
+    "NextToken": "page-2"
+}"#;
+
+const LAST: &str = r#"{
+    "Parameters": [{"Name": "/app/port", "Type": "String", "Value": "8080"}]
+}"#;
+
+const DENIED: &str = r#"{"__type": "AccessDeniedException", "Message": "not allowed"}"#;
+
+#[test]
+fn test_ssm_source() {
+    let (endpoint, requests) = common::serve(
+        vec![
+            ("\"NextToken\":\"page-2\"", 200, LAST),
+            ("POST / ", 200, FIRST),
+        ],
+        2,
+    );
+    let credentials = Credentials::new("AKIDEXAMPLE", "secret").session_token("session");
+    let table = SsmSource::new("/app")
+        .endpoint(endpoint)
+        .region("eu-west-1")
+        .credentials(credentials)
+        .collect()
+        .unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("db.url"),
+        Ok(String::from("postgres://localhost"))
+    );
+    assert_eq!(
+        table.get::<_, String>("db.password"),
+        Ok(String::from("hunter2"))
+    );
+    assert_eq!(table.get::<_, u16>("port"), Ok(8080));
+
+    let first = requests.recv().unwrap();
+    let lower = first.to_ascii_lowercase();
+
+    assert!(lower.contains("x-amz-target: amazonssm.getparametersbypath"));
+    assert!(lower.contains("x-amz-security-token: session"));
+    assert!(first.contains("Credential=AKIDEXAMPLE/"));
+    assert!(first.contains("/eu-west-1/ssm/aws4_request"));
+    assert!(first
+        .contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target"));
+    assert!(first.contains("\"WithDecryption\":true"));
+    assert!(requests
+        .recv()
+        .unwrap()
+        .contains("\"NextToken\":\"page-2\""));
+}
+
+#[test]
+fn test_ssm_source_error() {
+    let (endpoint, _) = common::serve(vec![("POST / ", 400, DENIED)], 1);
+    let err = SsmSource::new("/app")
+        .endpoint(endpoint)
+        .credentials(Credentials::new("AKIDEXAMPLE", "secret"))
+        .collect()
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "AmazonSSM.GetParametersByPath request failed with status 400: not allowed"
+    );
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "vault")]
+
+use std::sync::{Arc, Mutex};
+
+use brace_config::source::{Lease, Source, VaultAuth, VaultSource};
+
+mod common;
+
+const SECRET: &str = r#"{
+    "lease_id": "",
+    "lease_duration": 0,
+    "renewable": false,
+    "data": {
+        "data": {"db": {"password": "hunter2"}, "api_key": "abc123"},
+        "metadata": {"version": 3}
+    }
+}"#;
+
+const LOGIN: &str = r#"{
+    "auth": {
+        "client_token": "s.approle",
+        "accessor": "accessor-1",
+        "lease_duration": 3600,
+        "renewable": true
+    }
+}"#;
+
+#[test]
+fn test_vault_token() {
+    let (address, requests) = common::serve(vec![("GET /v1/secret/data/app", 200, SECRET)], 1);
+    let table = VaultSource::new(address, "app", VaultAuth::Token(String::from("s.token")))
+        .collect()
+        .unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("secrets.db.password"),
+        Ok(String::from("hunter2"))
+    );
+    assert_eq!(
+        table.get::<_, String>("secrets.api_key"),
+        Ok(String::from("abc123"))
+    );
+
+    let request = requests.recv().unwrap().to_ascii_lowercase();
+
+    assert!(request.contains("x-vault-token: s.token"));
+}
+
+#[test]
+fn test_vault_approle() {
+    let (address, requests) = common::serve(
+        vec![
+            ("POST /v1/auth/approle/login", 200, LOGIN),
+            ("GET /v1/kv/data/team/app", 200, SECRET),
+        ],
+        2,
+    );
+    let leases = Arc::new(Mutex::new(Vec::new()));
+    let recorded = leases.clone();
+    let auth = VaultAuth::AppRole {
+        role_id: String::from("role"),
+        secret_id: String::from("secret"),
+    };
+    let table = VaultSource::new(address, "/team/app/", auth)
+        .mount("kv")
+        .key("vault")
+        .on_lease(move |lease| recorded.lock().unwrap().push(lease.clone()))
+        .collect()
+        .unwrap();
+
+    assert_eq!(
+        table.get::<_, String>("vault.db.password"),
+        Ok(String::from("hunter2"))
+    );
+    assert_eq!(
+        leases.lock().unwrap().as_slice(),
+        &[Lease {
+            id: Some(String::from("accessor-1")),
+            duration: 3600,
+            renewable: true,
+        }]
+    );
+
+    let login = requests.recv().unwrap();
+    let read = requests.recv().unwrap().to_ascii_lowercase();
+
+    assert!(login.contains("\"role_id\":\"role\""));
+    assert!(read.contains("x-vault-token: s.approle"));
+}
+
+#[test]
+fn test_vault_approle_session() {
+    let (address, requests) = common::serve(
+        vec![
+            ("POST /v1/auth/approle/login", 200, LOGIN),
+            ("POST /v1/auth/token/renew-self", 200, LOGIN),
+            ("GET /v1/secret/data/app", 200, SECRET),
+        ],
+        4,
+    );
+    let auth = VaultAuth::AppRole {
+        role_id: String::from("role"),
+        secret_id: String::from("secret"),
+    };
+    let source = VaultSource::new(address, "app", auth);
+
+    assert!(source.collect().is_ok());
+    assert!(source.collect().is_ok());
+    assert_eq!(source.renew(Some(60)).unwrap().duration, 3600);
+
+    let requests = requests.iter().take(4).collect::<Vec<_>>();
+    let logins = requests
+        .iter()
+        .filter(|request| request.contains("/v1/auth/approle/login"))
+        .count();
+
+    assert_eq!(logins, 1);
+    assert!(requests[3].contains("/v1/auth/token/renew-self"));
+    assert!(requests[3]
+        .to_ascii_lowercase()
+        .contains("x-vault-token: s.approle"));
+}
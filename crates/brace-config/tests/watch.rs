@@ -0,0 +1,281 @@
+#![cfg(feature = "watch")]
+
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use brace_config::source::{FileSource, MemorySource};
+use brace_config::watch::ConfigWatcher;
+use brace_config::{table, Config};
+
+fn wait_for<F>(mut done: F) -> bool
+where
+    F: FnMut() -> bool,
+{
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_secs(10) {
+        if done() {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    false
+}
+
+#[test]
+fn test_watch_reload() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write("tests/outputs/watch/reload.toml", "port = \"80\"\n").unwrap();
+
+    let path = "tests/outputs/watch/reload.toml";
+    let watcher = ConfigWatcher::watch(Config::load(path).unwrap(), path).unwrap();
+    let snapshot = watcher.snapshot();
+
+    assert_eq!(snapshot.get::<_, u16>("port"), Ok(80));
+
+    std::fs::write(path, "port = \"8080\"\n").unwrap();
+
+    assert!(wait_for(
+        || watcher.snapshot().get::<_, u16>("port") == Ok(8080)
+    ));
+    assert_eq!(snapshot.get::<_, u16>("port"), Ok(80));
+    assert!(watcher.error().is_none());
+
+    std::fs::write(path, "port = \n").unwrap();
+
+    assert!(wait_for(|| watcher.error().is_some()));
+    assert_eq!(watcher.snapshot().get::<_, u16>("port"), Ok(8080));
+
+    std::fs::write(path, "port = \"9000\"\n").unwrap();
+
+    assert!(wait_for(
+        || watcher.snapshot().get::<_, u16>("port") == Ok(9000)
+    ));
+    assert!(wait_for(|| watcher.error().is_none()));
+    assert!(watcher.reload().is_ok());
+}
+
+#[test]
+fn test_watch_debounce() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write("tests/outputs/watch/debounce.toml", "count = \"0\"\n").unwrap();
+
+    let path = "tests/outputs/watch/debounce.toml";
+    let watcher = ConfigWatcher::builder(path)
+        .debounce(Duration::from_millis(300))
+        .build()
+        .unwrap();
+
+    assert_eq!(watcher.snapshot().get::<_, u32>("count"), Ok(0));
+
+    for count in 1..=5 {
+        std::fs::write(path, format!("count = \"{}\"\n", count)).unwrap();
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    assert!(wait_for(|| watcher.version() > 0));
+
+    thread::sleep(Duration::from_millis(500));
+
+    assert_eq!(watcher.version(), 1);
+    assert_eq!(watcher.snapshot().get::<_, u32>("count"), Ok(5));
+}
+
+#[test]
+fn test_watch_on_change() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write(
+        "tests/outputs/watch/change.toml",
+        "name = \"app\"\n[server]\nport = \"80\"\n",
+    )
+    .unwrap();
+
+    let path = "tests/outputs/watch/change.toml";
+    let watcher = ConfigWatcher::builder(path).build().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    watcher.on_change(move |config, diff| {
+        tx.send((config.get::<_, u16>("server.port"), diff.clone()))
+            .unwrap();
+    });
+
+    std::fs::write(
+        path,
+        "name = \"app\"\n[server]\nport = \"8080\"\nhost = \"localhost\"\n",
+    )
+    .unwrap();
+
+    let (port, diff) = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+
+    assert_eq!(port, Ok(8080));
+    assert_eq!(diff.added, vec!["server.host"]);
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed, vec!["server.port"]);
+
+    thread::sleep(Duration::from_millis(200));
+    watcher.reload().unwrap();
+
+    assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_watch_poll() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write("tests/outputs/watch/poll.toml", "port = \"80\"\n").unwrap();
+
+    let path = "tests/outputs/watch/poll.toml";
+    let watcher = ConfigWatcher::builder(path)
+        .poll(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    std::fs::write(path, "port = \"8080\"\n").unwrap();
+
+    assert!(wait_for(
+        || watcher.snapshot().get::<_, u16>("port") == Ok(8080)
+    ));
+
+    let version = watcher.version();
+
+    std::fs::write(path, "port = \"8080\"\n").unwrap();
+    thread::sleep(Duration::from_millis(200));
+
+    assert_eq!(watcher.version(), version);
+
+    std::fs::remove_file(path).unwrap();
+
+    assert!(wait_for(|| watcher.error().is_some()));
+    assert_eq!(watcher.snapshot().get::<_, u16>("port"), Ok(8080));
+}
+
+#[test]
+fn test_watch_subscribe() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write(
+        "tests/outputs/watch/subscribe.toml",
+        "[logging]\nlevel = \"info\"\n[server]\nport = \"80\"\n",
+    )
+    .unwrap();
+
+    let path = "tests/outputs/watch/subscribe.toml";
+    let watcher = ConfigWatcher::builder(path).build().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    watcher.subscribe("logging.*", move |_, diff| {
+        tx.send(diff.clone()).unwrap();
+    });
+
+    std::fs::write(
+        path,
+        "[logging]\nlevel = \"info\"\n[server]\nport = \"8080\"\n",
+    )
+    .unwrap();
+
+    assert!(wait_for(
+        || watcher.snapshot().get::<_, u16>("server.port") == Ok(8080)
+    ));
+    assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+
+    std::fs::write(
+        path,
+        "[logging]\nlevel = \"debug\"\n[server]\nport = \"9000\"\n",
+    )
+    .unwrap();
+
+    let diff = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+
+    assert_eq!(diff.changed, vec!["logging.level"]);
+    assert!(diff.added.is_empty());
+}
+
+#[test]
+fn test_watch_subscribe_in_callback() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write("tests/outputs/watch/reentrant.toml", "port = \"80\"\n").unwrap();
+
+    let path = "tests/outputs/watch/reentrant.toml";
+    let watcher = Arc::new(ConfigWatcher::builder(path).build().unwrap());
+    let (tx, rx) = mpsc::channel();
+    let inner = Arc::downgrade(&watcher);
+
+    watcher.on_change(move |_, _| {
+        let watcher = inner.upgrade().unwrap();
+        let tx = tx.clone();
+
+        watcher.subscribe("port", move |config, _| {
+            tx.send(config.get::<_, u16>("port").unwrap()).unwrap();
+        });
+    });
+
+    std::fs::write(path, "port = \"8080\"\n").unwrap();
+
+    assert!(wait_for(|| watcher.version() > 0));
+
+    std::fs::write(path, "port = \"9000\"\n").unwrap();
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(9000));
+}
+
+#[test]
+fn test_watch_layers() {
+    std::fs::create_dir_all("tests/outputs/watch").unwrap();
+    std::fs::write(
+        "tests/outputs/watch/layers.toml",
+        "host = \"localhost\"\nport = \"80\"\n",
+    )
+    .unwrap();
+
+    let path = "tests/outputs/watch/layers.toml";
+    let config = Config::builder()
+        .add_source(FileSource::new(path))
+        .add_source(MemorySource::from(table! { "port" = 8080 }).name("overrides"))
+        .keep_layers(true)
+        .build()
+        .unwrap();
+    let watcher = ConfigWatcher::builder(path)
+        .config(config)
+        .poll(Duration::from_millis(20))
+        .build()
+        .unwrap();
+
+    std::fs::write(path, "host = \"example.com\"\nport = \"81\"\n").unwrap();
+
+    assert!(wait_for(
+        || watcher.snapshot().get::<_, String>("host") == Ok(String::from("example.com"))
+    ));
+    assert_eq!(watcher.snapshot().get::<_, u16>("port"), Ok(8080));
+    assert_eq!(watcher.snapshot().layers().len(), 2);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_watch_symlinked_data_dir() {
+    use std::os::unix::fs::symlink;
+
+    let dir = std::path::Path::new("tests/outputs/watch/configmap");
+
+    let _ = std::fs::remove_dir_all(dir);
+    std::fs::create_dir_all(dir.join("..2024_01_01")).unwrap();
+    std::fs::write(dir.join("..2024_01_01/app.toml"), "port = \"80\"\n").unwrap();
+    symlink("..2024_01_01", dir.join("..data")).unwrap();
+    symlink("..data/app.toml", dir.join("app.toml")).unwrap();
+
+    let watcher = ConfigWatcher::builder(dir.join("app.toml"))
+        .build()
+        .unwrap();
+
+    assert_eq!(watcher.snapshot().get::<_, u16>("port"), Ok(80));
+
+    std::fs::create_dir_all(dir.join("..2024_01_02")).unwrap();
+    std::fs::write(dir.join("..2024_01_02/app.toml"), "port = \"8080\"\n").unwrap();
+    symlink("..2024_01_02", dir.join("..data_tmp")).unwrap();
+    std::fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+    std::fs::remove_dir_all(dir.join("..2024_01_01")).unwrap();
+
+    assert!(wait_for(
+        || watcher.snapshot().get::<_, u16>("port") == Ok(8080)
+    ));
+}